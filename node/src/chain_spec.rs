@@ -22,9 +22,8 @@
 use cumulus_primitives_core::ParaId;
 use primitives::{AccountId, Balance, Signature};
 use runtime_eden::{
-	constants::{EXISTENTIAL_DEPOSIT, NODL},
-	AuraId, BalancesConfig, CollatorSelectionConfig, GenesisConfig, ParachainInfoConfig, PolkadotXcmConfig,
-	SessionConfig, SessionKeys, SystemConfig, TechnicalMembershipConfig, WASM_BINARY,
+	constants::NODL, AuraId, BalancesConfig, GenesisConfig, ParachainInfoConfig, PolkadotXcmConfig, SessionConfig,
+	SessionKeys, StakingConfig, SystemConfig, TechnicalMembershipConfig, WASM_BINARY,
 };
 use sc_chain_spec::{ChainSpecExtension, ChainSpecGroup};
 use sc_service::ChainType;
@@ -110,6 +109,7 @@ fn eden_testnet_genesis(
 	});
 
 	const ENDOWMENT: Balance = 10_000 * NODL;
+	const COLLATOR_BOND: Balance = 1_000 * NODL;
 
 	GenesisConfig {
 		// Core
@@ -124,10 +124,13 @@ fn eden_testnet_genesis(
 		vesting: Default::default(),
 
 		// Consensus
-		collator_selection: CollatorSelectionConfig {
+		staking: StakingConfig {
+			stakers: collators
+				.iter()
+				.cloned()
+				.map(|(acc, _)| (acc, None, COLLATOR_BOND))
+				.collect(),
 			invulnerables: collators.iter().cloned().map(|(acc, _)| acc).collect(),
-			candidacy_bond: EXISTENTIAL_DEPOSIT * 16,
-			..Default::default()
 		},
 		session: SessionConfig {
 			keys: collators
@@ -20,6 +20,7 @@
 
 use std::sync::Arc;
 
+use pallet_staking::{NominatorInfo, PendingSlash, RewardRate, ValidatorInfo};
 use primitives::{AccountId, Balance, Block, Index as Nonce};
 
 use sc_client_api::AuxStore;
@@ -54,10 +55,20 @@ where
 		+ 'static,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
 	C::Api: frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
+	C::Api: pallet_staking_rpc::StakingRuntimeApi<
+		Block,
+		AccountId,
+		Balance,
+		ValidatorInfo<AccountId, Balance>,
+		NominatorInfo<AccountId, Balance>,
+		RewardRate,
+		PendingSlash<AccountId, Balance>,
+	>,
 	C::Api: BlockBuilder<Block>,
 	P: TransactionPool + Sync + Send + 'static,
 {
 	use frame_rpc_system::{System, SystemApiServer};
+	use pallet_staking_rpc::{Staking, StakingApiServer};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
 
 	let mut module = RpcExtension::new(());
@@ -68,7 +79,8 @@ where
 	} = deps;
 
 	module.merge(System::new(client.clone(), pool, deny_unsafe).into_rpc())?;
-	module.merge(TransactionPayment::new(client).into_rpc())?;
+	module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
+	module.merge(Staking::new(client).into_rpc())?;
 
 	Ok(module)
 }
@@ -206,7 +206,16 @@ where
 		+ sp_block_builder::BlockBuilder<Block>
 		+ cumulus_primitives_core::CollectCollationInfo<Block>
 		+ pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>
-		+ frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
+		+ frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>
+		+ pallet_staking_rpc::StakingRuntimeApi<
+			Block,
+			AccountId,
+			Balance,
+			pallet_staking::ValidatorInfo<AccountId, Balance>,
+			pallet_staking::NominatorInfo<AccountId, Balance>,
+			pallet_staking::RewardRate,
+			pallet_staking::PendingSlash<AccountId, Balance>,
+		>,
 	sc_client_api::StateBackendFor<ParachainBackend, Block>: sp_api::StateBackend<BlakeTwo256>,
 	RB: Fn(Arc<ParachainClient<RuntimeApi>>) -> Result<jsonrpsee::RpcModule<()>, sc_service::Error>,
 	BIQ: FnOnce(
@@ -40,7 +40,7 @@ use sp_runtime::{
 	DispatchResult, Perbill, RuntimeDebug,
 };
 use sp_std::prelude::*;
-use support::WithAccountId;
+use support::{IsShutdown, ProtocolFeeHandler, WithAccountId};
 
 pub mod weights;
 pub use weights::WeightInfo;
@@ -178,7 +178,7 @@ pub mod pallet {
 
 		#[pallet::constant]
 		type ProtocolFee: Get<Perbill>;
-		type ProtocolFeeReceiver: WithAccountId<Self::AccountId>;
+		type ProtocolFeeReceiver: ProtocolFeeHandler<Self::AccountId, BalanceOf<Self>>;
 
 		/// Runtime existential deposit
 		#[pallet::constant]
@@ -199,6 +199,9 @@ pub mod pallet {
 		/// pace of the relay chain for timing.
 		type BlockNumberProvider: BlockNumberProvider<BlockNumber = Self::BlockNumber>;
 
+		/// No allocations are made while the chain is under an emergency shutdown.
+		type EmergencyShutdown: IsShutdown;
+
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
 	}
@@ -219,6 +222,7 @@ pub mod pallet {
 			batch: BoundedVec<(T::AccountId, BalanceOf<T>), T::MaxAllocs>,
 		) -> DispatchResultWithPostInfo {
 			Self::ensure_oracle(origin)?;
+			ensure!(!T::EmergencyShutdown::is_shutdown(), Error::<T>::UnderShutdown);
 			let update_weight = Self::checked_update_session_quota();
 			let rewards_len = batch.len().try_into().unwrap_or_else(|_| T::MaxAllocs::get());
 			Self::allocate(batch)?;
@@ -251,6 +255,8 @@ pub mod pallet {
 		DoesNotSatisfyExistentialDeposit,
 		/// Batch is empty or no issuance is necessary
 		BatchEmpty,
+		/// Allocations are frozen during an emergency shutdown
+		UnderShutdown,
 	}
 
 	#[pallet::event]
@@ -368,6 +374,7 @@ impl<T: Config> Pallet<T> {
 			full_protocol,
 			ExistenceRequirement::AllowDeath,
 		)?;
+		T::ProtocolFeeReceiver::on_protocol_fee(full_protocol);
 
 		Ok(())
 	}
@@ -130,6 +130,17 @@ impl WithAccountId<u64> for Receiver {
 		Receiver::get()
 	}
 }
+impl ProtocolFeeHandler<u64, u64> for Receiver {}
+
+parameter_types! {
+	pub static Shutdown: bool = false;
+}
+pub struct MockShutdown;
+impl IsShutdown for MockShutdown {
+	fn is_shutdown() -> bool {
+		Shutdown::get()
+	}
+}
 
 impl pallet_membership::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
@@ -155,6 +166,7 @@ impl Config for Test {
 	type MaxAllocs = MaxAllocs;
 	type OracleMembers = Membership;
 	type BlockNumberProvider = frame_system::Pallet<Test>;
+	type EmergencyShutdown = MockShutdown;
 	type WeightInfo = ();
 }
 type Errors = Error<Test>;
@@ -539,6 +551,20 @@ fn non_oracle_is_rejected() {
 	})
 }
 
+#[test]
+fn no_allocations_under_shutdown() {
+	new_test_ext().execute_with(|| {
+		let total_issuance = 100000u64;
+		let _issuance = Balances::issue(total_issuance);
+		Shutdown::set(true);
+		assert_noop!(
+			Allocations::batch(RuntimeOrigin::signed(Oracle::get()), bounded_vec![(Grantee::get(), 50)]),
+			Errors::UnderShutdown
+		);
+		Shutdown::set(false);
+	})
+}
+
 #[test]
 fn oracle_does_not_pay_fees() {
 	new_test_ext().execute_with(|| {
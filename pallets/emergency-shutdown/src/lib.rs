@@ -0,0 +1,92 @@
+/*
+ * This file is part of the Nodle Chain distributed at https://github.com/NodleCode/chain
+ * Copyright (C) 2020-2022  Nodle International
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Let governance freeze the Nodle stack in case of emergency. Other pallets, and the runtime
+//! call filter, consult the shutdown state through `support::IsShutdown`.
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod tests;
+
+pub mod weights;
+pub use weights::WeightInfo;
+
+pub use pallet::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// Origin that can enter or leave the emergency shutdown.
+		type ShutdownOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Enter the emergency shutdown if `shutdown` is true, leave it otherwise. Setting
+		/// the current state again has no effect, so racing proposals cannot undo each other.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::set_shutdown())]
+		pub fn set_shutdown(origin: OriginFor<T>, shutdown: bool) -> DispatchResultWithPostInfo {
+			T::ShutdownOrigin::ensure_origin(origin)?;
+
+			Shutdown::<T>::put(shutdown);
+
+			Self::deposit_event(Event::ShutdownSet(shutdown));
+
+			Ok(().into())
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The shutdown state was set \[is shutdown\]
+		ShutdownSet(bool),
+	}
+
+	/// Whether the chain is under an emergency shutdown.
+	#[pallet::storage]
+	#[pallet::getter(fn shutdown)]
+	pub type Shutdown<T: Config> = StorageValue<_, bool, ValueQuery>;
+}
+
+impl<T: Config> support::IsShutdown for Pallet<T> {
+	fn is_shutdown() -> bool {
+		Self::shutdown()
+	}
+}
@@ -0,0 +1,145 @@
+/*
+ * This file is part of the Nodle Chain distributed at https://github.com/NodleCode/chain
+ * Copyright (C) 2020-2022  Nodle International
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+#![cfg(test)]
+
+use super::*;
+use crate::{self as pallet_emergency_shutdown};
+use frame_support::{assert_noop, assert_ok, ord_parameter_types, parameter_types};
+use frame_system::EnsureSignedBy;
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BadOrigin, BlakeTwo256, IdentityLookup},
+};
+use support::IsShutdown;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		EmergencyShutdown: pallet_emergency_shutdown::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+impl frame_system::Config for Test {
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type SS58Prefix = ();
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type DbWeight = ();
+	type BaseCallFilter = frame_support::traits::Everything;
+	type OnSetCode = ();
+	type SystemWeightInfo = ();
+	type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+ord_parameter_types! {
+	pub const Admin: u64 = 1;
+}
+impl Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type ShutdownOrigin = EnsureSignedBy<Admin, u64>;
+	type WeightInfo = ();
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+#[test]
+fn set_shutdown_enters_and_leaves_the_shutdown() {
+	new_test_ext().execute_with(|| {
+		assert!(!EmergencyShutdown::is_shutdown());
+
+		assert_ok!(EmergencyShutdown::set_shutdown(
+			RuntimeOrigin::signed(Admin::get()),
+			true
+		));
+		assert!(EmergencyShutdown::is_shutdown());
+		System::assert_last_event(Event::ShutdownSet(true).into());
+
+		assert_ok!(EmergencyShutdown::set_shutdown(
+			RuntimeOrigin::signed(Admin::get()),
+			false
+		));
+		assert!(!EmergencyShutdown::is_shutdown());
+		System::assert_last_event(Event::ShutdownSet(false).into());
+	})
+}
+
+#[test]
+fn set_shutdown_is_idempotent() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(EmergencyShutdown::set_shutdown(
+			RuntimeOrigin::signed(Admin::get()),
+			true
+		));
+		assert_ok!(EmergencyShutdown::set_shutdown(
+			RuntimeOrigin::signed(Admin::get()),
+			true
+		));
+		assert!(EmergencyShutdown::is_shutdown());
+
+		assert_ok!(EmergencyShutdown::set_shutdown(
+			RuntimeOrigin::signed(Admin::get()),
+			false
+		));
+		assert_ok!(EmergencyShutdown::set_shutdown(
+			RuntimeOrigin::signed(Admin::get()),
+			false
+		));
+		assert!(!EmergencyShutdown::is_shutdown());
+	})
+}
+
+#[test]
+fn non_shutdown_origin_cannot_set_shutdown() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			EmergencyShutdown::set_shutdown(RuntimeOrigin::signed(2), true),
+			BadOrigin
+		);
+	})
+}
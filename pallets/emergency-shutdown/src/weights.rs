@@ -0,0 +1,70 @@
+/*
+ * This file is part of the Nodle Chain distributed at https://github.com/NodleCode/chain
+ * Copyright (C) 2020-2022  Nodle International
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Autogenerated weights for pallet_emergency_shutdown
+//!
+//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
+//! DATE: 2023-02-16, STEPS: `50`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
+//! HOSTNAME: `chain-bench-012bd056`, CPU: `AMD EPYC 7B13`
+//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
+
+// Executed Command:
+// ./target/release/nodle-parachain
+// benchmark
+// pallet
+// --chain=dev
+// --steps=50
+// --repeat=20
+// --pallet=pallet_emergency_shutdown
+// --extrinsic=*
+// --execution=wasm
+// --wasm-execution=compiled
+// --template=./.maintain/internal_pallet_weights.hbs
+// --output=weights
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{constants::RocksDbWeight, Weight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_emergency_shutdown.
+pub trait WeightInfo {
+	fn set_shutdown() -> Weight;
+}
+
+/// Weight functions for `pallet_emergency_shutdown`.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: EmergencyShutdown Shutdown (r:0 w:1)
+	fn set_shutdown() -> Weight {
+		// Minimum execution time: 12_870 nanoseconds.
+		Weight::from_ref_time(13_290_000_u64)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+}
+
+impl WeightInfo for () {
+	// Storage: EmergencyShutdown Shutdown (r:0 w:1)
+	fn set_shutdown() -> Weight {
+		// Minimum execution time: 12_870 nanoseconds.
+		Weight::from_ref_time(13_290_000_u64)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+}
\ No newline at end of file
@@ -36,6 +36,7 @@ struct BenchmarkConfig<T: Config> {
 	grantee_lookup: <T::Lookup as StaticLookup>::Source,
 	collector_lookup: <T::Lookup as StaticLookup>::Source,
 	schedule: VestingSchedule<T::BlockNumber, BalanceOf<T>>,
+	curve: VestingCurveOf<T>,
 }
 
 fn create_shared_config<T: Config>(u: u32) -> BenchmarkConfig<T> {
@@ -54,8 +55,21 @@ fn create_shared_config<T: Config>(u: u32) -> BenchmarkConfig<T> {
 		per_period: T::Currency::minimum_balance(),
 	};
 
+	// Use as many breakpoints as possible so that the locked amount is the most expensive to compute
+	let breakpoints = (1..=T::MaxBreakpoints::get())
+		.map(|i| (i.into(), T::Currency::minimum_balance().saturating_mul(i.into())))
+		.collect::<Vec<_>>()
+		.try_into()
+		.expect("we use exactly the maximum number of breakpoints; qed");
+	let curve = VestingCurve {
+		start: 0u32.into(),
+		breakpoints,
+		interpolation: Interpolation::Linear,
+	};
+
 	BenchmarkConfig {
 		granter,
+		curve,
 		grantee,
 		grantee_lookup,
 		collector_lookup,
@@ -69,17 +83,17 @@ benchmarks! {
 
 		// Add some existing schedules according to b
 		for _x in 1 .. T::MaxSchedule::get() {
-			Pallet::<T>::do_add_vesting_schedule(&config.granter, &config.grantee, config.schedule.clone())?;
+			Pallet::<T>::do_add_vesting_schedule(&config.granter, &config.grantee, config.curve.clone().into())?;
 		}
 	}:  _(RawOrigin::Signed(config.granter.clone()), config.grantee_lookup.clone(), config.schedule.clone())
 
 	claim {
 		let config = create_shared_config::<T>(1);
-		Pallet::<T>::do_add_vesting_schedule(&config.granter, &config.grantee, config.schedule.clone())?;
+		Pallet::<T>::do_add_vesting_schedule(&config.granter, &config.grantee, config.schedule.clone().into())?;
 
 		// Add some existing schedules according to b
 		for _x in 1 .. T::MaxSchedule::get() {
-			Pallet::<T>::do_add_vesting_schedule(&config.granter, &config.grantee, config.schedule.clone())?;
+			Pallet::<T>::do_add_vesting_schedule(&config.granter, &config.grantee, config.curve.clone().into())?;
 		}
 	}: _(RawOrigin::Signed(config.grantee))
 
@@ -88,7 +102,7 @@ benchmarks! {
 
 		// Add some existing schedules according to b
 		for _x in 1 .. T::MaxSchedule::get() {
-			Pallet::<T>::do_add_vesting_schedule(&config.granter, &config.grantee, config.schedule.clone())?;
+			Pallet::<T>::do_add_vesting_schedule(&config.granter, &config.grantee, config.curve.clone().into())?;
 		}
 
 		let call = Call::<T>::cancel_all_vesting_schedules{
@@ -98,6 +112,15 @@ benchmarks! {
 		let origin = T::CancelOrigin::successful_origin();
 	}: { call.dispatch_bypass_filter(origin)? }
 
+	add_vesting_curve {
+		let config = create_shared_config::<T>(1);
+
+		// Add some existing schedules according to b
+		for _x in 1 .. T::MaxSchedule::get() {
+			Pallet::<T>::do_add_vesting_schedule(&config.granter, &config.grantee, config.curve.clone().into())?;
+		}
+	}:  _(RawOrigin::Signed(config.granter.clone()), config.grantee_lookup.clone(), config.curve.clone())
+
 	renounce {
 		let config = create_shared_config::<T>(1);
 		let call = Call::<T>::renounce{
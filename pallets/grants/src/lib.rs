@@ -19,6 +19,7 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 mod benchmarking;
+pub mod migrations;
 
 #[cfg(test)]
 mod mock;
@@ -30,17 +31,19 @@ use codec::{Decode, Encode};
 use frame_support::{
 	ensure,
 	pallet_prelude::{MaxEncodedLen, TypeInfo},
-	traits::{Currency, ExistenceRequirement, LockIdentifier, LockableCurrency, WithdrawReasons},
-	BoundedVec,
+	traits::{Currency, ExistenceRequirement, Get, LockIdentifier, LockableCurrency, StorageVersion, WithdrawReasons},
+	BoundedVec, CloneNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound,
 };
 use sp_runtime::{
 	traits::{AtLeast32Bit, BlockNumberProvider, CheckedAdd, Saturating, StaticLookup, Zero},
-	DispatchResult, RuntimeDebug,
+	DispatchResult, RuntimeDebug, SaturatedConversion,
 };
 use sp_std::{
 	cmp::{Eq, PartialEq},
+	fmt::Debug,
 	vec::Vec,
 };
+use support::IsShutdown;
 
 #[cfg(feature = "std")]
 use frame_support::traits::GenesisBuild;
@@ -50,23 +53,21 @@ pub use weights::WeightInfo;
 
 pub use pallet::*;
 
-// A value placed in storage that represents the current version of the Grants storage.
-// This value is used by the `on_runtime_upgrade` logic to determine whether we run storage
-// migration logic. This should match directly with the semantic versions of the Rust crate.
-#[derive(Encode, MaxEncodedLen, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
-enum Releases {
-	V0, // Legacy version
-	V1, // Adds storage info
-}
+/// The current storage version of the Grants pallet.
+///
+/// Version 1 corresponds to the legacy `Releases::V1`, see [`migrations`] for the way out of the
+/// pallet specific `StorageVersion` item. Version 2 stores [`GrantSchedule`]s instead of plain
+/// linear [`VestingSchedule`]s.
+const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
 
-impl Default for Releases {
-	fn default() -> Self {
-		Releases::V0
-	}
-}
+pub(crate) const LOG_TARGET: &str = "runtime::grants";
 
 pub type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 pub type VestingScheduleOf<T> = VestingSchedule<<T as frame_system::Config>::BlockNumber, BalanceOf<T>>;
+pub type VestingCurveOf<T> =
+	VestingCurve<<T as frame_system::Config>::BlockNumber, BalanceOf<T>, <T as Config>::MaxBreakpoints>;
+pub type GrantScheduleOf<T> =
+	GrantSchedule<<T as frame_system::Config>::BlockNumber, BalanceOf<T>, <T as Config>::MaxBreakpoints>;
 pub type ListVestingScheduleOf<T> = Vec<VestingScheduleOf<T>>;
 pub type ScheduledGrant<T> = (
 	<T as frame_system::Config>::BlockNumber,
@@ -117,6 +118,150 @@ impl<BlockNumber: AtLeast32Bit + Copy, Balance: AtLeast32Bit + Copy> VestingSche
 	}
 }
 
+/// How the unlocked amount evolves between two breakpoints of a [`VestingCurve`].
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, scale_info::TypeInfo)]
+pub enum Interpolation {
+	/// Funds are released gradually, block after block, until the next breakpoint.
+	Linear,
+	/// Funds are released all at once when the next breakpoint is reached.
+	Step,
+}
+
+/// A vesting schedule following an arbitrary curve.
+///
+/// Benefits would be granted from `start` on, following the `(block, cumulative_unlocked)`
+/// breakpoints. The curve starts at `(start, 0)` and everything is unlocked at the last
+/// breakpoint, whose cumulative amount is the total amount of the schedule.
+#[derive(
+	CloneNoBound, Encode, Decode, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound, MaxEncodedLen, scale_info::TypeInfo,
+)]
+#[codec(mel_bound(BlockNumber: MaxEncodedLen, Balance: MaxEncodedLen))]
+#[scale_info(skip_type_params(MaxBreakpoints))]
+pub struct VestingCurve<
+	BlockNumber: Clone + PartialEq + Debug,
+	Balance: Clone + PartialEq + Debug,
+	MaxBreakpoints: Get<u32>,
+> {
+	pub start: BlockNumber,
+	pub breakpoints: BoundedVec<(BlockNumber, Balance), MaxBreakpoints>,
+	pub interpolation: Interpolation,
+}
+
+impl<BlockNumber: AtLeast32Bit + Copy + Debug, Balance: AtLeast32Bit + Copy + Debug, MaxBreakpoints: Get<u32>>
+	VestingCurve<BlockNumber, Balance, MaxBreakpoints>
+{
+	/// Returns `true` if the breakpoints are non-empty, come strictly after `start` and in
+	/// strictly increasing order of blocks, and if the cumulative amounts never decrease.
+	pub fn is_monotonic(&self) -> bool {
+		let mut previous = (self.start, Zero::zero());
+		for &(block, cumulative) in self.breakpoints.iter() {
+			if block <= previous.0 || cumulative < previous.1 {
+				return false;
+			}
+			previous = (block, cumulative);
+		}
+		!self.breakpoints.is_empty()
+	}
+
+	/// Returns the block of the last breakpoint, `None` if there are no breakpoints.
+	pub fn end(&self) -> Option<BlockNumber> {
+		self.breakpoints.last().map(|&(block, _)| block)
+	}
+
+	/// Returns all locked amount, `None` if there are no breakpoints.
+	pub fn total_amount(&self) -> Option<Balance> {
+		self.breakpoints.last().map(|&(_, cumulative)| cumulative)
+	}
+
+	/// Returns locked amount for a given `time`.
+	///
+	/// Note this func assumes the curve is a valid one (monotonic breakpoints), and it should be
+	/// guaranteed by callers.
+	pub fn locked_amount(&self, time: BlockNumber) -> Balance {
+		let total = self.total_amount().unwrap_or_else(Zero::zero);
+		let mut previous = (self.start, Zero::zero());
+		for &(block, cumulative) in self.breakpoints.iter() {
+			if time < block {
+				let unlocked = match self.interpolation {
+					Interpolation::Step => previous.1,
+					Interpolation::Linear => {
+						let elapsed: u128 = time.saturating_sub(previous.0).saturated_into();
+						let span: u128 = block.saturating_sub(previous.0).saturated_into();
+						let delta: u128 = cumulative.saturating_sub(previous.1).saturated_into();
+						let vested = delta.saturating_mul(elapsed) / span.max(1);
+						previous.1.saturating_add(vested.saturated_into())
+					}
+				};
+				return total.saturating_sub(unlocked);
+			}
+			previous = (block, cumulative);
+		}
+		Zero::zero()
+	}
+}
+
+/// A vesting schedule as stored by the pallet: either the compact linear [`VestingSchedule`]
+/// or a [`VestingCurve`] for non-linear vesting.
+#[derive(
+	CloneNoBound, Encode, Decode, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound, MaxEncodedLen, scale_info::TypeInfo,
+)]
+#[codec(mel_bound(BlockNumber: MaxEncodedLen, Balance: MaxEncodedLen))]
+#[scale_info(skip_type_params(MaxBreakpoints))]
+pub enum GrantSchedule<
+	BlockNumber: Clone + PartialEq + Debug,
+	Balance: Clone + PartialEq + Debug,
+	MaxBreakpoints: Get<u32>,
+> {
+	Linear(VestingSchedule<BlockNumber, Balance>),
+	Curve(VestingCurve<BlockNumber, Balance, MaxBreakpoints>),
+}
+
+impl<BlockNumber: AtLeast32Bit + Copy + Debug, Balance: AtLeast32Bit + Copy + Debug, MaxBreakpoints: Get<u32>>
+	GrantSchedule<BlockNumber, Balance, MaxBreakpoints>
+{
+	/// Returns the end of the schedule, `None` if calculation overflows.
+	pub fn end(&self) -> Option<BlockNumber> {
+		match self {
+			GrantSchedule::Linear(schedule) => schedule.end(),
+			GrantSchedule::Curve(curve) => curve.end(),
+		}
+	}
+
+	/// Returns all locked amount, `None` if calculation overflows.
+	pub fn total_amount(&self) -> Option<Balance> {
+		match self {
+			GrantSchedule::Linear(schedule) => schedule.total_amount(),
+			GrantSchedule::Curve(curve) => curve.total_amount(),
+		}
+	}
+
+	/// Returns locked amount for a given `time`.
+	///
+	/// Note this func assumes schedule is a valid one, and it should be guaranteed by callers.
+	pub fn locked_amount(&self, time: BlockNumber) -> Balance {
+		match self {
+			GrantSchedule::Linear(schedule) => schedule.locked_amount(time),
+			GrantSchedule::Curve(curve) => curve.locked_amount(time),
+		}
+	}
+}
+
+impl<BlockNumber: Clone + PartialEq + Debug, Balance: Clone + PartialEq + Debug, MaxBreakpoints: Get<u32>>
+	From<VestingSchedule<BlockNumber, Balance>> for GrantSchedule<BlockNumber, Balance, MaxBreakpoints>
+{
+	fn from(schedule: VestingSchedule<BlockNumber, Balance>) -> Self {
+		GrantSchedule::Linear(schedule)
+	}
+}
+
+impl<BlockNumber: Clone + PartialEq + Debug, Balance: Clone + PartialEq + Debug, MaxBreakpoints: Get<u32>>
+	From<VestingCurve<BlockNumber, Balance, MaxBreakpoints>> for GrantSchedule<BlockNumber, Balance, MaxBreakpoints>
+{
+	fn from(curve: VestingCurve<BlockNumber, Balance, MaxBreakpoints>) -> Self {
+		GrantSchedule::Curve(curve)
+	}
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -131,16 +276,30 @@ pub mod pallet {
 		/// The maximum number of vesting schedule.
 		#[pallet::constant]
 		type MaxSchedule: Get<u32>;
+		/// The maximum number of breakpoints a vesting curve may hold.
+		#[pallet::constant]
+		type MaxBreakpoints: Get<u32>;
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
 		// The block number provider
 		type BlockNumberProvider: BlockNumberProvider<BlockNumber = Self::BlockNumber>;
+		/// No new grants can be created while the chain is under an emergency shutdown.
+		type EmergencyShutdown: IsShutdown;
 	}
 
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(PhantomData<T>);
 
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		#[cfg(feature = "try-runtime")]
+		fn try_state(_n: BlockNumberFor<T>) -> Result<(), &'static str> {
+			Self::do_try_state()
+		}
+	}
+
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Claim funds that have been vested so far
@@ -169,7 +328,7 @@ pub mod pallet {
 		) -> DispatchResultWithPostInfo {
 			let from = ensure_signed(origin)?;
 			let to = T::Lookup::lookup(dest)?;
-			Self::do_add_vesting_schedule(&from, &to, schedule.clone())?;
+			Self::do_add_vesting_schedule(&from, &to, schedule.clone().into())?;
 
 			Self::deposit_event(Event::VestingScheduleAdded(from, to, schedule));
 			Ok(().into())
@@ -226,6 +385,22 @@ pub mod pallet {
 
 			Ok(().into())
 		}
+
+		/// Wire funds to be vested by the receiver following a non-linear curve
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::add_vesting_curve())]
+		pub fn add_vesting_curve(
+			origin: OriginFor<T>,
+			dest: <T::Lookup as StaticLookup>::Source,
+			curve: VestingCurveOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let from = ensure_signed(origin)?;
+			let to = T::Lookup::lookup(dest)?;
+			Self::do_add_vesting_schedule(&from, &to, curve.clone().into())?;
+
+			Self::deposit_event(Event::VestingCurveAdded(from, to, curve));
+			Ok(().into())
+		}
 	}
 
 	#[pallet::event]
@@ -239,6 +414,8 @@ pub mod pallet {
 		VestingSchedulesCanceled(T::AccountId),
 		/// Renounced rights to cancel grant for the given account id \[who\]
 		Renounced(T::AccountId),
+		/// Added new non-linear vesting schedule \[from, to, vesting_curve\]
+		VestingCurveAdded(T::AccountId, T::AccountId, VestingCurveOf<T>),
 	}
 
 	#[pallet::error]
@@ -251,6 +428,10 @@ pub mod pallet {
 		VestingToSelf,
 		MaxScheduleOverflow,
 		Renounced,
+		EmptyVestingCurve,
+		NonMonotonicVestingCurve,
+		ZeroVestingAmount,
+		UnderShutdown,
 	}
 
 	#[pallet::storage]
@@ -259,7 +440,7 @@ pub mod pallet {
 		_,
 		Blake2_128Concat,
 		T::AccountId,
-		BoundedVec<VestingScheduleOf<T>, T::MaxSchedule>,
+		BoundedVec<GrantScheduleOf<T>, T::MaxSchedule>,
 		ValueQuery,
 	>;
 
@@ -267,9 +448,6 @@ pub mod pallet {
 	#[pallet::getter(fn renounced)]
 	pub type Renounced<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, bool, ValueQuery>;
 
-	#[pallet::storage]
-	pub(crate) type StorageVersion<T: Config> = StorageValue<_, Releases, ValueQuery>;
-
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		pub vesting: Vec<ScheduledItem<T>>,
@@ -288,13 +466,15 @@ pub mod pallet {
 	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
 		fn build(&self) {
 			self.vesting.iter().for_each(|(ref who, schedules)| {
-				let vesting_schedule: BoundedVec<VestingScheduleOf<T>, T::MaxSchedule> = schedules
+				let vesting_schedule: BoundedVec<GrantScheduleOf<T>, T::MaxSchedule> = schedules
 					.iter()
-					.map(|&(start, period, period_count, per_period)| VestingSchedule {
-						start,
-						period,
-						period_count,
-						per_period,
+					.map(|&(start, period, period_count, per_period)| {
+						GrantSchedule::Linear(VestingSchedule {
+							start,
+							period,
+							period_count,
+							per_period,
+						})
 					})
 					.collect::<Vec<_>>()
 					.try_into()
@@ -343,7 +523,7 @@ impl<T: Config> Pallet<T> {
 	}
 
 	/// Returns locked balance based on current block number.
-	fn locked_balance(who: &T::AccountId) -> BalanceOf<T> {
+	pub fn locked_balance(who: &T::AccountId) -> BalanceOf<T> {
 		let now = T::BlockNumberProvider::current_block_number();
 		Self::vesting_schedules(who).iter().fold(Zero::zero(), |acc, s| {
 			acc.checked_add(&s.locked_amount(now)).expect(
@@ -352,11 +532,8 @@ impl<T: Config> Pallet<T> {
 		})
 	}
 
-	fn do_add_vesting_schedule(
-		from: &T::AccountId,
-		to: &T::AccountId,
-		schedule: VestingScheduleOf<T>,
-	) -> DispatchResult {
+	fn do_add_vesting_schedule(from: &T::AccountId, to: &T::AccountId, schedule: GrantScheduleOf<T>) -> DispatchResult {
+		ensure!(!T::EmergencyShutdown::is_shutdown(), Error::<T>::UnderShutdown);
 		ensure!(from != to, Error::<T>::VestingToSelf);
 
 		let schedule_amount = Self::ensure_valid_vesting_schedule(&schedule)?;
@@ -378,12 +555,77 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// Check the invariants of the pallet storage:
+	/// - every account in `VestingSchedules` holds at least one valid schedule;
+	/// - the vested but not yet released amount of every grantee is frozen by a lock. The
+	///   `nvesting` lock is only refreshed on `claim`, hence it may be higher than the current
+	///   `locked_balance` but never lower;
+	/// - the counter of `VestingSchedules` matches the number of its entries;
+	/// - `Renounced` only holds `true` values. Renouncing before a schedule is added is allowed,
+	///   so renounced accounts without schedules are only reported in the logs.
+	#[cfg(any(feature = "try-runtime", test))]
+	pub fn do_try_state() -> Result<(), &'static str> {
+		let mut count = 0u32;
+		for (who, schedules) in VestingSchedules::<T>::iter() {
+			count = count.saturating_add(1);
+			ensure!(!schedules.is_empty(), "Grants: empty vesting schedules are stored");
+			for schedule in schedules.iter() {
+				Self::ensure_valid_vesting_schedule(schedule).map_err(|_| "Grants: invalid vesting schedule")?;
+			}
+
+			let locked = Self::locked_balance(&who);
+			if !locked.is_zero() {
+				let free = T::Currency::free_balance(&who);
+				let unlocked = locked.saturating_sub(1u32.into());
+				ensure!(
+					T::Currency::ensure_can_withdraw(
+						&who,
+						free.saturating_sub(unlocked).max(1u32.into()),
+						WithdrawReasons::all(),
+						unlocked
+					)
+					.is_err(),
+					"Grants: the nvesting lock is lower than the locked balance"
+				);
+			}
+		}
+		ensure!(
+			count == VestingSchedules::<T>::count(),
+			"Grants: VestingSchedules counter does not match its entries"
+		);
+
+		for (who, renounced) in Renounced::<T>::iter() {
+			ensure!(renounced, "Grants: Renounced holds a false value");
+			if !VestingSchedules::<T>::contains_key(&who) {
+				log::warn!(
+					target: LOG_TARGET,
+					"Renounced account {:?} does not have any vesting schedule",
+					who
+				);
+			}
+		}
+
+		Ok(())
+	}
+
 	/// Returns `Ok(amount)` if valid schedule, or error.
-	fn ensure_valid_vesting_schedule(schedule: &VestingScheduleOf<T>) -> Result<BalanceOf<T>, Error<T>> {
-		ensure!(!schedule.period.is_zero(), Error::<T>::ZeroVestingPeriod);
-		ensure!(!schedule.period_count.is_zero(), Error::<T>::ZeroVestingPeriodCount);
-		ensure!(schedule.end().is_some(), Error::<T>::NumOverflow);
+	fn ensure_valid_vesting_schedule(schedule: &GrantScheduleOf<T>) -> Result<BalanceOf<T>, Error<T>> {
+		match schedule {
+			GrantSchedule::Linear(schedule) => {
+				ensure!(!schedule.period.is_zero(), Error::<T>::ZeroVestingPeriod);
+				ensure!(!schedule.period_count.is_zero(), Error::<T>::ZeroVestingPeriodCount);
+				ensure!(schedule.end().is_some(), Error::<T>::NumOverflow);
+
+				schedule.total_amount().ok_or(Error::<T>::NumOverflow)
+			}
+			GrantSchedule::Curve(curve) => {
+				ensure!(!curve.breakpoints.is_empty(), Error::<T>::EmptyVestingCurve);
+				ensure!(curve.is_monotonic(), Error::<T>::NonMonotonicVestingCurve);
 
-		schedule.total_amount().ok_or(Error::<T>::NumOverflow)
+				let total = curve.total_amount().ok_or(Error::<T>::EmptyVestingCurve)?;
+				ensure!(!total.is_zero(), Error::<T>::ZeroVestingAmount);
+				Ok(total)
+			}
+		}
 	}
 }
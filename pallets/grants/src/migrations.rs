@@ -0,0 +1,156 @@
+/*
+ * This file is part of the Nodle Chain distributed at https://github.com/NodleCode/chain
+ * Copyright (C) 2020-2022  Nodle International
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Storage migrations for the grants pallet.
+
+use super::*;
+use frame_support::{
+	traits::{Get, GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	weights::Weight,
+};
+use sp_std::marker::PhantomData;
+
+pub mod legacy {
+	use super::*;
+	use frame_support::storage_alias;
+
+	// A value placed in storage that represents the current version of the Grants storage.
+	// This value is used by the `on_runtime_upgrade` logic to determine whether we run storage
+	// migration logic. This should match directly with the semantic versions of the Rust crate.
+	#[derive(Encode, MaxEncodedLen, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+	pub enum Releases {
+		V0, // Legacy version
+		V1, // Adds storage info
+	}
+
+	impl Default for Releases {
+		fn default() -> Self {
+			Releases::V0
+		}
+	}
+
+	/// The pallet specific storage version item that got replaced by FRAME's `StorageVersion`.
+	#[storage_alias]
+	pub type StorageVersion<T: Config> = StorageValue<Pallet<T>, Releases, ValueQuery>;
+}
+
+/// Moves the pallet off the legacy `Releases` based `StorageVersion` item and onto FRAME's
+/// `StorageVersion`. The layout of `VestingSchedules` is left untouched.
+pub struct MigrateToStorageVersion<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for MigrateToStorageVersion<T> {
+	fn on_runtime_upgrade() -> Weight {
+		let on_chain_version = Pallet::<T>::on_chain_storage_version();
+		if on_chain_version < 1 {
+			let legacy_version = legacy::StorageVersion::<T>::take();
+			StorageVersion::new(1).put::<Pallet<T>>();
+			log::info!(
+				target: LOG_TARGET,
+				"Migrated from legacy {:?} to storage version 1",
+				legacy_version
+			);
+			T::DbWeight::get().reads_writes(2, 2)
+		} else {
+			log::info!(
+				target: LOG_TARGET,
+				"Storage version {:?} is already in place, skipping the migration",
+				on_chain_version
+			);
+			T::DbWeight::get().reads(1)
+		}
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+		Ok(VestingSchedules::<T>::count().encode())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+		let count_before =
+			u32::decode(&mut state.as_slice()).map_err(|_| "Grants: failed to decode the pre-upgrade state")?;
+		ensure!(
+			Pallet::<T>::on_chain_storage_version() >= 1,
+			"Grants: storage version was not updated"
+		);
+		ensure!(
+			!legacy::StorageVersion::<T>::exists(),
+			"Grants: legacy storage version was not removed"
+		);
+		ensure!(
+			VestingSchedules::<T>::count() == count_before,
+			"Grants: the number of vesting schedules changed during the migration"
+		);
+		Ok(())
+	}
+}
+
+/// Wraps every stored linear [`VestingSchedule`] into a [`GrantSchedule::Linear`], making room for
+/// non-linear [`VestingCurve`]s.
+pub struct MigrateToGrantSchedules<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for MigrateToGrantSchedules<T> {
+	fn on_runtime_upgrade() -> Weight {
+		let on_chain_version = Pallet::<T>::on_chain_storage_version();
+		if on_chain_version == 1 {
+			let mut translated = 0u64;
+			VestingSchedules::<T>::translate::<BoundedVec<VestingScheduleOf<T>, T::MaxSchedule>, _>(
+				|_who, schedules| {
+					translated = translated.saturating_add(1);
+					let grants: Vec<GrantScheduleOf<T>> = schedules.into_iter().map(Into::into).collect();
+					// Same bound as the legacy vector, this can't fail
+					grants.try_into().ok()
+				},
+			);
+			StorageVersion::new(2).put::<Pallet<T>>();
+			log::info!(
+				target: LOG_TARGET,
+				"Migrated {} accounts to grant schedules, storage version 2",
+				translated
+			);
+			T::DbWeight::get().reads_writes(translated.saturating_add(1), translated.saturating_add(1))
+		} else {
+			log::info!(
+				target: LOG_TARGET,
+				"Storage version {:?} does not need the grant schedules migration",
+				on_chain_version
+			);
+			T::DbWeight::get().reads(1)
+		}
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+		Ok(VestingSchedules::<T>::count().encode())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+		let count_before =
+			u32::decode(&mut state.as_slice()).map_err(|_| "Grants: failed to decode the pre-upgrade state")?;
+		ensure!(
+			Pallet::<T>::on_chain_storage_version() == STORAGE_VERSION,
+			"Grants: storage version was not updated"
+		);
+		ensure!(
+			VestingSchedules::<T>::count() == count_before,
+			"Grants: the number of vesting schedules changed during the migration"
+		);
+		Pallet::<T>::do_try_state()
+	}
+}
@@ -102,6 +102,15 @@ ord_parameter_types! {
 
 parameter_types! {
 	pub static MaxSchedule: u32 = 2;
+	pub const MaxBreakpoints: u32 = 16;
+	pub static Shutdown: bool = false;
+}
+
+pub struct MockShutdown;
+impl IsShutdown for MockShutdown {
+	fn is_shutdown() -> bool {
+		Shutdown::get()
+	}
 }
 
 impl Config for Test {
@@ -109,8 +118,10 @@ impl Config for Test {
 	type Currency = PalletBalances;
 	type CancelOrigin = EnsureSignedBy<CancelOrigin, AccountId>;
 	type MaxSchedule = MaxSchedule;
+	type MaxBreakpoints = MaxBreakpoints;
 	type WeightInfo = ();
 	type BlockNumberProvider = frame_system::Pallet<Test>;
+	type EmergencyShutdown = MockShutdown;
 }
 
 pub(crate) fn balances(who: &AccountId) -> (Balance, Balance) {
@@ -21,10 +21,13 @@
 #![cfg(test)]
 
 use super::*;
-use frame_support::{assert_err, assert_noop, assert_ok, traits::WithdrawReasons};
+use frame_support::{
+	assert_err, assert_noop, assert_ok,
+	traits::{GetStorageVersion, OnRuntimeUpgrade, WithdrawReasons},
+};
 use mock::{
-	context_events, CancelOrigin, ExtBuilder, PalletBalances, RuntimeEvent as TestEvent, RuntimeOrigin, System,
-	Test as Runtime, Vesting, ALICE, BOB,
+	context_events, CancelOrigin, ExtBuilder, PalletBalances, RuntimeEvent as TestEvent, RuntimeOrigin, Shutdown,
+	System, Test as Runtime, Vesting, ALICE, BOB,
 };
 use pallet_balances::{BalanceLock, Reasons};
 use sp_runtime::DispatchError::BadOrigin;
@@ -32,9 +35,62 @@ use sp_runtime::DispatchError::BadOrigin;
 #[test]
 fn check_releases_default_config() {
 	ExtBuilder::default().build().execute_with(|| {
-		let releases = Releases::default();
-		assert_eq!(releases, Releases::V0);
-		assert_ne!(releases, Releases::V1);
+		let releases = migrations::legacy::Releases::default();
+		assert_eq!(releases, migrations::legacy::Releases::V0);
+		assert_ne!(releases, migrations::legacy::Releases::V1);
+	})
+}
+
+#[test]
+fn migrate_to_storage_version_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		StorageVersion::new(0).put::<Vesting>();
+		migrations::legacy::StorageVersion::<Runtime>::put(migrations::legacy::Releases::V1);
+
+		migrations::MigrateToStorageVersion::<Runtime>::on_runtime_upgrade();
+
+		assert_eq!(Vesting::on_chain_storage_version(), StorageVersion::new(1));
+		assert!(!migrations::legacy::StorageVersion::<Runtime>::exists());
+
+		// Running the migration twice is a no-op
+		migrations::MigrateToStorageVersion::<Runtime>::on_runtime_upgrade();
+		assert_eq!(Vesting::on_chain_storage_version(), StorageVersion::new(1));
+	})
+}
+
+#[test]
+fn migrate_to_grant_schedules_works() {
+	ExtBuilder::default().one_hundred_for_alice().build().execute_with(|| {
+		let schedule = VestingSchedule {
+			start: 0u64,
+			period: 10u64,
+			period_count: 2u32,
+			per_period: 10u64,
+		};
+		assert_ok!(Vesting::add_vesting_schedule(
+			RuntimeOrigin::signed(ALICE::get()),
+			BOB::get(),
+			schedule.clone()
+		));
+
+		// Rewrite the storage the way it was laid out in version 1
+		let legacy_schedules: BoundedVec<VestingSchedule<u64, u64>, mock::MaxSchedule> =
+			vec![schedule.clone()].try_into().unwrap();
+		sp_io::storage::set(
+			&VestingSchedules::<Runtime>::hashed_key_for(BOB::get()),
+			&legacy_schedules.encode(),
+		);
+		StorageVersion::new(1).put::<Vesting>();
+
+		migrations::MigrateToGrantSchedules::<Runtime>::on_runtime_upgrade();
+
+		assert_eq!(Vesting::on_chain_storage_version(), STORAGE_VERSION);
+		assert_eq!(
+			Vesting::vesting_schedules(&BOB::get()),
+			vec![GrantSchedule::Linear(schedule)]
+		);
+		assert_eq!(VestingSchedules::<Runtime>::count(), 1);
+		assert_ok!(Vesting::do_try_state());
 	})
 }
 #[test]
@@ -53,13 +109,36 @@ fn add_vesting_schedule_works() {
 			BOB::get(),
 			schedule.clone()
 		));
-		assert_eq!(Vesting::vesting_schedules(&BOB::get()), vec![schedule.clone()]);
+		assert_eq!(
+			Vesting::vesting_schedules(&BOB::get()),
+			vec![GrantSchedule::Linear(schedule.clone())]
+		);
 
 		let vested_event = TestEvent::Vesting(Event::VestingScheduleAdded(ALICE::get(), BOB::get(), schedule));
 		assert!(System::events().iter().any(|record| record.event == vested_event));
 	});
 }
 
+#[test]
+fn cannot_add_vesting_schedule_under_shutdown() {
+	ExtBuilder::default().one_hundred_for_alice().build().execute_with(|| {
+		Shutdown::set(true);
+
+		let schedule = VestingSchedule {
+			start: 0u64,
+			period: 10u64,
+			period_count: 1u32,
+			per_period: 100u64,
+		};
+		assert_noop!(
+			Vesting::add_vesting_schedule(RuntimeOrigin::signed(ALICE::get()), BOB::get(), schedule),
+			Error::<Runtime>::UnderShutdown
+		);
+
+		Shutdown::set(false);
+	});
+}
+
 #[test]
 fn add_new_vesting_schedule_merges_with_current_locked_balance_and_until() {
 	ExtBuilder::default().one_hundred_for_alice().build().execute_with(|| {
@@ -372,8 +451,8 @@ fn cancel_tolerates_corrupted_state() {
 			per_period: 1_000u64, // definitely too much money
 		};
 
-		let ans = <VestingSchedules<Runtime>>::try_mutate(BOB::get(), |s| -> Result<(), VestingSchedule<u64, u64>> {
-			s.try_push(bob_modified_vesting_schedule.clone())
+		let ans = <VestingSchedules<Runtime>>::try_mutate(BOB::get(), |s| -> Result<(), GrantSchedule<u64, u64>> {
+			s.try_push(bob_modified_vesting_schedule.clone().into())
 		});
 
 		assert_ok!(ans);
@@ -383,8 +462,14 @@ fn cancel_tolerates_corrupted_state() {
 		if let Ok(schedule_from_chain) = <VestingSchedules<Runtime>>::try_get(BOB::get()) {
 			assert_eq!(schedule_from_chain.len(), 2);
 
-			assert_eq!(schedule_from_chain[0], allice_vesting_to_bob_schedule);
-			assert_eq!(schedule_from_chain[1], bob_modified_vesting_schedule);
+			assert_eq!(
+				schedule_from_chain[0],
+				GrantSchedule::Linear(allice_vesting_to_bob_schedule)
+			);
+			assert_eq!(
+				schedule_from_chain[1],
+				GrantSchedule::Linear(bob_modified_vesting_schedule)
+			);
 		} else {
 			assert!(false, "Expected Bob to have some grants, Got error instead");
 		}
@@ -629,3 +714,180 @@ fn renounce_privileges() {
 		);
 	});
 }
+
+#[test]
+fn try_state_works() {
+	ExtBuilder::default().one_hundred_for_alice().build().execute_with(|| {
+		let schedule = VestingSchedule {
+			start: 0u64,
+			period: 10u64,
+			period_count: 2u32,
+			per_period: 10u64,
+		};
+		assert_ok!(Vesting::add_vesting_schedule(
+			RuntimeOrigin::signed(ALICE::get()),
+			BOB::get(),
+			schedule
+		));
+		assert_ok!(Vesting::do_try_state());
+
+		// Renouncing ahead of any schedule is allowed
+		assert_ok!(Vesting::renounce(
+			RuntimeOrigin::signed(CancelOrigin::get()),
+			ALICE::get()
+		));
+		assert_ok!(Vesting::do_try_state());
+
+		// The lock is only refreshed on claim, hence it can be higher than the locked balance
+		System::set_block_number(11);
+		assert_ok!(Vesting::do_try_state());
+	});
+}
+
+#[test]
+fn try_state_detects_lock_drift() {
+	ExtBuilder::default().one_hundred_for_alice().build().execute_with(|| {
+		let schedule = VestingSchedule {
+			start: 0u64,
+			period: 10u64,
+			period_count: 2u32,
+			per_period: 10u64,
+		};
+		assert_ok!(Vesting::add_vesting_schedule(
+			RuntimeOrigin::signed(ALICE::get()),
+			BOB::get(),
+			schedule
+		));
+
+		PalletBalances::set_lock(VESTING_LOCK_ID, &BOB::get(), 10, WithdrawReasons::all());
+		assert_err!(
+			Vesting::do_try_state(),
+			"Grants: the nvesting lock is lower than the locked balance"
+		);
+	});
+}
+
+#[test]
+fn try_state_detects_renounced_false_values() {
+	ExtBuilder::default().build().execute_with(|| {
+		Renounced::<Runtime>::insert(BOB::get(), false);
+		assert_err!(Vesting::do_try_state(), "Grants: Renounced holds a false value");
+	});
+}
+
+#[test]
+fn vesting_curve_locked_amount_works() {
+	let linear: VestingCurveOf<Runtime> = VestingCurve {
+		start: 10u64,
+		breakpoints: vec![(20u64, 10u64), (30u64, 10u64), (40u64, 100u64)]
+			.try_into()
+			.unwrap(),
+		interpolation: Interpolation::Linear,
+	};
+	assert_eq!(linear.locked_amount(0), 100);
+	assert_eq!(linear.locked_amount(10), 100);
+	assert_eq!(linear.locked_amount(15), 95);
+	assert_eq!(linear.locked_amount(20), 90);
+	assert_eq!(linear.locked_amount(25), 90);
+	assert_eq!(linear.locked_amount(35), 45);
+	assert_eq!(linear.locked_amount(40), 0);
+	assert_eq!(linear.locked_amount(1_000), 0);
+
+	let step = VestingCurve {
+		interpolation: Interpolation::Step,
+		..linear
+	};
+	assert_eq!(step.locked_amount(15), 100);
+	assert_eq!(step.locked_amount(20), 90);
+	assert_eq!(step.locked_amount(39), 90);
+	assert_eq!(step.locked_amount(40), 0);
+}
+
+#[test]
+fn add_vesting_curve_works() {
+	ExtBuilder::default().one_hundred_for_alice().build().execute_with(|| {
+		let curve = VestingCurve {
+			start: 0u64,
+			breakpoints: vec![(10u64, 10u64), (20u64, 100u64)].try_into().unwrap(),
+			interpolation: Interpolation::Step,
+		};
+		assert_ok!(Vesting::add_vesting_curve(
+			RuntimeOrigin::signed(ALICE::get()),
+			BOB::get(),
+			curve.clone()
+		));
+		assert_eq!(
+			Vesting::vesting_schedules(&BOB::get()),
+			vec![GrantSchedule::Curve(curve.clone())]
+		);
+		assert_eq!(mock::balances(&BOB::get()), (100, 100));
+		assert_eq!(
+			context_events(),
+			vec![Event::VestingCurveAdded(ALICE::get(), BOB::get(), curve)]
+		);
+
+		System::set_block_number(15);
+		assert_ok!(Vesting::claim(RuntimeOrigin::signed(BOB::get())));
+		assert_eq!(mock::balances(&BOB::get()), (100, 90));
+
+		System::set_block_number(20);
+		assert_ok!(Vesting::claim(RuntimeOrigin::signed(BOB::get())));
+		assert_eq!(mock::balances(&BOB::get()), (100, 0));
+		assert!(!<VestingSchedules<Runtime>>::contains_key(BOB::get()));
+	});
+}
+
+#[test]
+fn add_vesting_curve_checks_breakpoints() {
+	ExtBuilder::default().one_hundred_for_alice().build().execute_with(|| {
+		let empty = VestingCurve {
+			start: 0u64,
+			breakpoints: Default::default(),
+			interpolation: Interpolation::Linear,
+		};
+		assert_noop!(
+			Vesting::add_vesting_curve(RuntimeOrigin::signed(ALICE::get()), BOB::get(), empty),
+			Error::<Runtime>::EmptyVestingCurve
+		);
+
+		let decreasing_amounts = VestingCurve {
+			start: 0u64,
+			breakpoints: vec![(10u64, 20u64), (20u64, 10u64)].try_into().unwrap(),
+			interpolation: Interpolation::Linear,
+		};
+		assert_noop!(
+			Vesting::add_vesting_curve(RuntimeOrigin::signed(ALICE::get()), BOB::get(), decreasing_amounts),
+			Error::<Runtime>::NonMonotonicVestingCurve
+		);
+
+		let unordered_blocks = VestingCurve {
+			start: 0u64,
+			breakpoints: vec![(20u64, 10u64), (20u64, 20u64)].try_into().unwrap(),
+			interpolation: Interpolation::Linear,
+		};
+		assert_noop!(
+			Vesting::add_vesting_curve(RuntimeOrigin::signed(ALICE::get()), BOB::get(), unordered_blocks),
+			Error::<Runtime>::NonMonotonicVestingCurve
+		);
+
+		let before_start = VestingCurve {
+			start: 10u64,
+			breakpoints: vec![(10u64, 10u64)].try_into().unwrap(),
+			interpolation: Interpolation::Step,
+		};
+		assert_noop!(
+			Vesting::add_vesting_curve(RuntimeOrigin::signed(ALICE::get()), BOB::get(), before_start),
+			Error::<Runtime>::NonMonotonicVestingCurve
+		);
+
+		let nothing_to_vest = VestingCurve {
+			start: 0u64,
+			breakpoints: vec![(10u64, 0u64)].try_into().unwrap(),
+			interpolation: Interpolation::Step,
+		};
+		assert_noop!(
+			Vesting::add_vesting_curve(RuntimeOrigin::signed(ALICE::get()), BOB::get(), nothing_to_vest),
+			Error::<Runtime>::ZeroVestingAmount
+		);
+	});
+}
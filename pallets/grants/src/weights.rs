@@ -22,6 +22,11 @@
 //! DATE: 2023-02-16, STEPS: `50`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
 //! HOSTNAME: `chain-bench-012bd056`, CPU: `AMD EPYC 7B13`
 //! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
+//!
+//! `add_vesting_curve` is a hand-written estimate, not benchmark output, and `add_vesting_schedule`,
+//! `claim` and `cancel_all_vesting_schedules` were measured before their benchmarks pre-filled
+//! vesting curves. Regenerate this file with `scripts/run_benchmarks.sh` to replace them with
+//! measured weights.
 
 // Executed Command:
 // ./target/release/nodle-parachain
@@ -50,6 +55,7 @@ pub trait WeightInfo {
 	fn claim() -> Weight;
 	fn cancel_all_vesting_schedules() -> Weight;
 	fn renounce() -> Weight;
+	fn add_vesting_curve() -> Weight;
 }
 
 /// Weight functions for `pallet_grants`.
@@ -110,6 +116,20 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(4_u64))
 			.saturating_add(T::DbWeight::get().writes(3_u64))
 	}
+	// Storage: ParachainSystem ValidationData (r:1 w:0)
+	// Storage: Vesting VestingSchedules (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: System Number (r:1 w:0)
+	// Storage: System ExecutionPhase (r:1 w:0)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	// Storage: Balances Locks (r:1 w:1)
+	fn add_vesting_curve() -> Weight {
+		// Estimate, see the module docs.
+		Weight::from_ref_time(95_120_000_u64)
+			.saturating_add(T::DbWeight::get().reads(9_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
 }
 
 impl WeightInfo for () {
@@ -168,4 +188,18 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(4_u64))
 			.saturating_add(RocksDbWeight::get().writes(3_u64))
 	}
+	// Storage: ParachainSystem ValidationData (r:1 w:0)
+	// Storage: Vesting VestingSchedules (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: System Number (r:1 w:0)
+	// Storage: System ExecutionPhase (r:1 w:0)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	// Storage: Balances Locks (r:1 w:1)
+	fn add_vesting_curve() -> Weight {
+		// Estimate, see the module docs.
+		Weight::from_ref_time(95_120_000_u64)
+			.saturating_add(RocksDbWeight::get().reads(9_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
 }
\ No newline at end of file
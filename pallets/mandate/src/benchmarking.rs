@@ -0,0 +1,109 @@
+/*
+ * This file is part of the Nodle Chain distributed at https://github.com/NodleCode/chain
+ * Copyright (C) 2020-2022  Nodle International
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Mandate pallet benchmarks
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::{
+	traits::{EnsureOrigin, UnfilteredDispatchable},
+	weights::Weight,
+};
+use frame_system::RawOrigin;
+use sp_runtime::traits::{Get, Zero};
+use sp_std::{boxed::Box, vec, vec::Vec};
+
+#[cfg(test)]
+use crate::Pallet as Mandate;
+
+fn remark<T: Config>() -> <T as Config>::RuntimeCall
+where
+	<T as Config>::RuntimeCall: From<frame_system::Call<T>>,
+{
+	frame_system::Call::<T>::remark { remark: vec![] }.into()
+}
+
+fn max_call_name() -> Vec<u8> {
+	vec![b'x'; MaxCallNameLength::get() as usize]
+}
+
+benchmarks! {
+	where_clause { where <T as Config>::RuntimeCall: From<frame_system::Call<T>> }
+
+	apply {
+		let call = Call::<T>::apply { call: Box::new(remark::<T>()) };
+		let origin = T::ExternalOrigin::successful_origin();
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		if !T::MandateDelay::get().is_zero() {
+			assert!(Mandates::<T>::contains_key(0));
+		}
+	}
+
+	veto {
+		Mandates::<T>::insert(0, QueuedMandate {
+			calls: vec![remark::<T>()],
+			origin: None,
+			queued_at: Zero::zero(),
+			enacts_at: T::MandateDelay::get(),
+		});
+		let call = Call::<T>::veto { id: 0 };
+		let origin = T::VetoOrigin::successful_origin();
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert!(!Mandates::<T>::contains_key(0));
+	}
+
+	enact {
+		// The queued calls are accounted for through `call_weight_bound`
+		Mandates::<T>::insert(0, QueuedMandate {
+			calls: vec![],
+			origin: None,
+			queued_at: Zero::zero(),
+			enacts_at: Zero::zero(),
+		});
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), 0, Weight::zero())
+	verify {
+		assert!(!Mandates::<T>::contains_key(0));
+	}
+
+	allow_call {
+		let call = Call::<T>::allow_call { pallet_name: max_call_name(), call_name: max_call_name() };
+		let origin = T::AllowListOrigin::successful_origin();
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		let name: CallName = max_call_name().try_into().unwrap();
+		assert!(AllowedCalls::<T>::contains_key(&name, &name));
+	}
+
+	disallow_call {
+		let name: CallName = max_call_name().try_into().unwrap();
+		AllowedCalls::<T>::insert(&name, &name, ());
+		let call = Call::<T>::disallow_call { pallet_name: max_call_name(), call_name: max_call_name() };
+		let origin = T::AllowListOrigin::successful_origin();
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert!(!AllowedCalls::<T>::contains_key(&name, &name));
+	}
+
+	impl_benchmark_test_suite!(Mandate, crate::tests::new_test_ext(), crate::tests::Test,);
+}
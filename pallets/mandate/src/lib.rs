@@ -18,31 +18,113 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-//! Handle the ability to notify other pallets that they should stop all
-//! operations, or resume them
+//! Let a governance origin dispatch calls as root, after a veto-able delay
+//! unless the call is allow-listed. Queued mandates can be enacted by anyone once
+//! `MandateDelay` has passed, and `VetoOrigin` can cancel them in the meantime.
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod tests;
+
+pub mod weights;
+pub use weights::WeightInfo;
 
 pub use pallet::*;
 
 #[frame_support::pallet]
 pub mod pallet {
+	use super::*;
+	use codec::{Decode, Encode};
 	use frame_support::{
-		dispatch::GetDispatchInfo,
+		dispatch::{extract_actual_weight, DispatchClass, GetDispatchInfo, PostDispatchInfo},
 		pallet_prelude::*,
-		traits::{EnsureOrigin, UnfilteredDispatchable},
+		storage::with_storage_layer,
+		traits::{EnsureOrigin, GetCallMetadata, UnfilteredDispatchable},
 		Parameter,
 	};
 	use frame_system::pallet_prelude::*;
-	use sp_runtime::DispatchResult;
-	use sp_std::prelude::Box;
+	use scale_info::TypeInfo;
+	use sp_runtime::{
+		traits::{Dispatchable, Hash, Saturating, Zero},
+		DispatchResult, RuntimeDebug, SaturatedConversion,
+	};
+	use sp_std::{
+		prelude::{Box, Vec},
+		vec,
+	};
+
+	pub type MandateId = u32;
+	pub type MaxCallNameLength = ConstU32<64>;
+	pub type CallName = BoundedVec<u8, MaxCallNameLength>;
+	pub type QueuedMandateOf<T> = QueuedMandate<
+		<T as Config>::RuntimeCall,
+		<T as Config>::PalletsOrigin,
+		<T as frame_system::Config>::BlockNumber,
+	>;
+
+	/// Mandates waiting for their delay to pass. They can be enacted by anyone once `enacts_at`
+	/// is reached unless they got vetoed in the meantime.
+	#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+	pub struct QueuedMandate<Call, PalletsOrigin, BlockNumber> {
+		/// Calls to dispatch atomically, in order
+		pub calls: Vec<Call>,
+		/// Origin to dispatch the calls as, root if `None`
+		pub origin: Option<PalletsOrigin>,
+		pub queued_at: BlockNumber,
+		pub enacts_at: BlockNumber,
+	}
+
+	pub type MandateRecordOf<T> =
+		MandateRecord<<T as frame_system::Config>::BlockNumber, <T as frame_system::Config>::Hash>;
+
+	/// What is kept in the history about a call once it was dispatched by a mandate.
+	#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	pub struct MandateRecord<BlockNumber, Hash> {
+		/// Id of the queued mandate, `None` if it was applied immediately
+		pub id: Option<MandateId>,
+		pub block: BlockNumber,
+		pub call_hash: Hash,
+		pub call_len: u32,
+		pub result: DispatchResult,
+		pub weight: Weight,
+	}
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
-		type RuntimeCall: Parameter + UnfilteredDispatchable<RuntimeOrigin = Self::RuntimeOrigin> + GetDispatchInfo;
+		type RuntimeCall: Parameter
+			+ Dispatchable<RuntimeOrigin = Self::RuntimeOrigin, PostInfo = PostDispatchInfo>
+			+ UnfilteredDispatchable<RuntimeOrigin = Self::RuntimeOrigin, PostInfo = PostDispatchInfo>
+			+ GetDispatchInfo
+			+ GetCallMetadata;
+
+		/// The origins mandates can be dispatched as, typically the runtime's `OriginCaller`.
+		type PalletsOrigin: Parameter + Into<Self::RuntimeOrigin>;
 
 		/// Origin that can call this module and execute sudo actions. Typically
 		/// the `collective` module.
 		type ExternalOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Origin that can cancel a queued mandate before it is enacted.
+		type VetoOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Origin that manages the calls which can be applied without waiting for
+		/// `MandateDelay`.
+		type AllowListOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// How long a mandate stays queued before it can be enacted, a zero delay
+		/// executes every mandate immediately.
+		#[pallet::constant]
+		type MandateDelay: Get<Self::BlockNumber>;
+
+		/// How many dispatched mandates are kept in the history, older records get
+		/// overwritten.
+		#[pallet::constant]
+		type MaxHistoryLength: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::pallet]
@@ -55,30 +137,367 @@ pub mod pallet {
 	#[allow(clippy::boxed_local)]
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		/// Let the configured origin dispatch a call as root
+		/// Let the configured origin dispatch a call as root. Unless the call is
+		/// allow-listed, it is queued for `MandateDelay` blocks first.
 		#[pallet::call_index(0)]
 		#[pallet::weight({
-			let dispatch_info = call.get_dispatch_info();
-			(
-				dispatch_info.weight.saturating_add(Weight::from_ref_time(10_000)),
-				dispatch_info.class,
-			)
+			let (weight, class) = Pallet::<T>::dispatch_weight(sp_std::slice::from_ref(&**call));
+			(weight.saturating_add(T::WeightInfo::apply()), class)
 		})]
 		pub fn apply(origin: OriginFor<T>, call: Box<<T as Config>::RuntimeCall>) -> DispatchResultWithPostInfo {
 			T::ExternalOrigin::ensure_origin(origin)?;
 
-			// Shamelessly stollen from the `sudo` module
-			let res = call.dispatch_bypass_filter(frame_system::RawOrigin::Root.into());
-			Self::deposit_event(Event::RootOp(res.map(|_| ()).map_err(|e| e.error)));
+			let applied = Self::do_apply(None, vec![*call])?;
+
+			Ok(Self::apply_weight(&applied).into())
+		}
+
+		/// Cancel a queued mandate.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::veto())]
+		pub fn veto(origin: OriginFor<T>, id: MandateId) -> DispatchResultWithPostInfo {
+			T::VetoOrigin::ensure_origin(origin)?;
+
+			Mandates::<T>::take(id).ok_or(Error::<T>::UnknownMandate)?;
+
+			Self::deposit_event(Event::MandateVetoed(id));
 
 			Ok(().into())
 		}
+
+		/// Dispatch a queued mandate once its delay has passed. Can be called by
+		/// anyone, `call_weight_bound` has to cover the weight of the queued calls.
+		#[pallet::call_index(2)]
+		#[pallet::weight(call_weight_bound.saturating_add(T::WeightInfo::enact()))]
+		pub fn enact(origin: OriginFor<T>, id: MandateId, call_weight_bound: Weight) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+
+			let mandate = Mandates::<T>::get(id).ok_or(Error::<T>::UnknownMandate)?;
+			ensure!(
+				frame_system::Pallet::<T>::block_number() >= mandate.enacts_at,
+				Error::<T>::MandateNotReady
+			);
+			let (calls_weight, _) = Self::dispatch_weight(&mandate.calls);
+			ensure!(
+				calls_weight.all_lte(call_weight_bound),
+				Error::<T>::WrongCallWeightBound
+			);
+
+			Mandates::<T>::remove(id);
+
+			let (res, weight) = Self::dispatch_all(Some(id), mandate.origin, mandate.calls);
+			Self::deposit_event(Event::MandateEnacted(id, res));
+
+			Ok(Some(weight.saturating_add(T::WeightInfo::enact())).into())
+		}
+
+		/// Let mandates for `call_name` of `pallet_name` be applied immediately.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::allow_call())]
+		pub fn allow_call(
+			origin: OriginFor<T>,
+			pallet_name: Vec<u8>,
+			call_name: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			T::AllowListOrigin::ensure_origin(origin)?;
+
+			let pallet_name: CallName = pallet_name.try_into().map_err(|_| Error::<T>::CallNameTooLong)?;
+			let call_name: CallName = call_name.try_into().map_err(|_| Error::<T>::CallNameTooLong)?;
+			AllowedCalls::<T>::insert(&pallet_name, &call_name, ());
+
+			Self::deposit_event(Event::CallAllowed(pallet_name.into_inner(), call_name.into_inner()));
+
+			Ok(().into())
+		}
+
+		/// Remove a call from the allow-list, mandates for it will be queued again.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::disallow_call())]
+		pub fn disallow_call(
+			origin: OriginFor<T>,
+			pallet_name: Vec<u8>,
+			call_name: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			T::AllowListOrigin::ensure_origin(origin)?;
+
+			let pallet_name: CallName = pallet_name.try_into().map_err(|_| Error::<T>::CallNameTooLong)?;
+			let call_name: CallName = call_name.try_into().map_err(|_| Error::<T>::CallNameTooLong)?;
+			AllowedCalls::<T>::take(&pallet_name, &call_name).ok_or(Error::<T>::CallNotAllowed)?;
+
+			Self::deposit_event(Event::CallDisallowed(pallet_name.into_inner(), call_name.into_inner()));
+
+			Ok(().into())
+		}
+
+		/// Let the configured origin dispatch a call as `as_origin`, for instance a
+		/// signed account. The call is always queued for `MandateDelay` blocks first,
+		/// the allow-list only applies to calls dispatched as root.
+		#[pallet::call_index(5)]
+		#[pallet::weight({
+			let (weight, class) = Pallet::<T>::dispatch_weight(sp_std::slice::from_ref(&**call));
+			(weight.saturating_add(T::WeightInfo::apply()), class)
+		})]
+		pub fn apply_as_origin(
+			origin: OriginFor<T>,
+			as_origin: Box<T::PalletsOrigin>,
+			call: Box<<T as Config>::RuntimeCall>,
+		) -> DispatchResultWithPostInfo {
+			T::ExternalOrigin::ensure_origin(origin)?;
+
+			let applied = Self::do_apply(Some(*as_origin), vec![*call])?;
+
+			Ok(Self::apply_weight(&applied).into())
+		}
+
+		/// Let the configured origin dispatch several calls as root, atomically: if
+		/// one fails none of them is applied. Unless all the calls are allow-listed,
+		/// they are queued for `MandateDelay` blocks first.
+		#[pallet::call_index(6)]
+		#[pallet::weight({
+			let (weight, class) = Pallet::<T>::dispatch_weight(calls);
+			(weight.saturating_add(T::WeightInfo::apply()), class)
+		})]
+		pub fn apply_batch_all(
+			origin: OriginFor<T>,
+			calls: Vec<<T as Config>::RuntimeCall>,
+		) -> DispatchResultWithPostInfo {
+			T::ExternalOrigin::ensure_origin(origin)?;
+			ensure!(!calls.is_empty(), Error::<T>::EmptyBatch);
+
+			// Dispatched right away, a failure reverts the calls but not the records of it
+			let applied = Self::do_apply(None, calls)?;
+			if let Some(Err(error)) = applied {
+				Self::deposit_event(Event::BatchReverted(error));
+			}
+
+			Ok(Self::apply_weight(&applied).into())
+		}
 	}
 
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
-		/// A root operation was executed, show result
-		RootOp(DispatchResult),
+		/// A root operation was executed, show result \[call hash, result, actual weight\]
+		RootOp(T::Hash, DispatchResult, Weight),
+		/// A mandate was queued \[id, calls hash, enacts at\]
+		MandateQueued(MandateId, T::Hash, T::BlockNumber),
+		/// A queued mandate was vetoed \[id\]
+		MandateVetoed(MandateId),
+		/// A queued mandate was executed, show result \[id, result\]
+		MandateEnacted(MandateId, DispatchResult),
+		/// A call can now be applied immediately \[pallet name, call name\]
+		CallAllowed(Vec<u8>, Vec<u8>),
+		/// A call was removed from the allow-list \[pallet name, call name\]
+		CallDisallowed(Vec<u8>, Vec<u8>),
+		/// A call was executed with an origin other than root, show result \[call hash,
+		/// result, actual weight\]
+		DispatchedAs(T::Hash, DispatchResult, Weight),
+		/// A batch applied immediately was reverted as one of its calls failed \[error\]
+		BatchReverted(DispatchError),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// No queued mandate with this id
+		UnknownMandate,
+		/// The mandate delay has not passed yet
+		MandateNotReady,
+		/// The weight bound is lower than the weight of the queued calls
+		WrongCallWeightBound,
+		/// Pallet or call name is longer than `MaxCallNameLength`
+		CallNameTooLong,
+		/// The call is not part of the allow-list
+		CallNotAllowed,
+		/// A batch must contain at least one call
+		EmptyBatch,
+	}
+
+	/// Mandates waiting for their delay to pass.
+	#[pallet::storage]
+	#[pallet::getter(fn mandates)]
+	#[pallet::unbounded]
+	pub type Mandates<T: Config> = StorageMap<_, Twox64Concat, MandateId, QueuedMandateOf<T>, OptionQuery>;
+
+	#[pallet::storage]
+	pub type NextMandateId<T: Config> = StorageValue<_, MandateId, ValueQuery>;
+
+	/// Calls, by pallet and call name, that are applied without delay.
+	#[pallet::storage]
+	pub type AllowedCalls<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, CallName, Blake2_128Concat, CallName, (), OptionQuery>;
+
+	/// Ring buffer of the last `MaxHistoryLength` calls dispatched by mandates, indexed by
+	/// `HistoryCount` modulo `MaxHistoryLength`.
+	#[pallet::storage]
+	pub type History<T: Config> = StorageMap<_, Twox64Concat, u32, MandateRecordOf<T>, OptionQuery>;
+
+	/// Total number of calls ever dispatched by mandates.
+	#[pallet::storage]
+	pub type HistoryCount<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	impl<T: Config> Pallet<T> {
+		/// Weight of dispatching `calls` and recording them in the history, along with the
+		/// class of the mandate: operational only if all the calls are.
+		pub fn dispatch_weight(calls: &[<T as Config>::RuntimeCall]) -> (Weight, DispatchClass) {
+			let mut weight = Weight::zero();
+			let mut all_operational = true;
+			for call in calls {
+				let info = call.get_dispatch_info();
+				weight = weight
+					.saturating_add(info.weight)
+					.saturating_add(T::DbWeight::get().reads_writes(2, 2));
+				all_operational &= info.class == DispatchClass::Operational;
+			}
+			let class = if all_operational && !calls.is_empty() {
+				DispatchClass::Operational
+			} else {
+				DispatchClass::Normal
+			};
+
+			(weight, class)
+		}
+
+		/// Dispatch `calls` right away if there is no delay, or if they are all allow-listed
+		/// and dispatched as root, returning the result. Otherwise queue them and return
+		/// `None`.
+		fn do_apply(
+			as_origin: Option<T::PalletsOrigin>,
+			calls: Vec<<T as Config>::RuntimeCall>,
+		) -> Result<Option<DispatchResult>, DispatchError> {
+			let delay = T::MandateDelay::get();
+			// The allow-list is keyed by call only, it must not let a call run as any origin
+			if delay.is_zero() || (as_origin.is_none() && calls.iter().all(Self::is_allowed)) {
+				let (res, _) = Self::dispatch_all(None, as_origin, calls);
+				return Ok(Some(res));
+			}
+
+			let id = NextMandateId::<T>::get();
+			let now = frame_system::Pallet::<T>::block_number();
+			let enacts_at = now.saturating_add(delay);
+			let calls_hash = T::Hashing::hash_of(&calls);
+
+			Mandates::<T>::insert(
+				id,
+				QueuedMandate {
+					calls,
+					origin: as_origin,
+					queued_at: now,
+					enacts_at,
+				},
+			);
+			NextMandateId::<T>::put(id.wrapping_add(1));
+
+			Self::deposit_event(Event::MandateQueued(id, calls_hash, enacts_at));
+
+			Ok(None)
+		}
+
+		/// Actual weight of an apply: queued calls are only charged once enacted, dispatched
+		/// ones keep the weight declared for them.
+		fn apply_weight(applied: &Option<DispatchResult>) -> Option<Weight> {
+			match applied {
+				Some(_) => None,
+				None => Some(T::WeightInfo::apply()),
+			}
+		}
+
+		/// Dispatch `calls` in order as `as_origin`, or as root bypassing filters if `None`,
+		/// stopping at the first failure in which case all of them are reverted. Each
+		/// dispatched call is recorded in the history and gets its own event.
+		fn dispatch_all(
+			id: Option<MandateId>,
+			as_origin: Option<T::PalletsOrigin>,
+			calls: Vec<<T as Config>::RuntimeCall>,
+		) -> (DispatchResult, Weight) {
+			let mut records = Vec::with_capacity(calls.len());
+			let res = with_storage_layer(|| -> DispatchResult {
+				for call in calls {
+					let record = Self::dispatch_one(id, as_origin.clone(), call);
+					let result = record.result;
+					records.push(record);
+					result?;
+				}
+				Ok(())
+			});
+
+			// The calls preceding a failure were reverted, only the failure remains.
+			if res.is_err() {
+				records = records.pop().into_iter().collect();
+			}
+
+			let mut weight = Weight::zero();
+			for record in records {
+				weight = weight.saturating_add(record.weight);
+				Self::deposit_event(match as_origin {
+					None => Event::RootOp(record.call_hash, record.result, record.weight),
+					Some(_) => Event::DispatchedAs(record.call_hash, record.result, record.weight),
+				});
+				Self::note_history(record);
+			}
+
+			(res, weight)
+		}
+
+		fn dispatch_one(
+			id: Option<MandateId>,
+			as_origin: Option<T::PalletsOrigin>,
+			call: <T as Config>::RuntimeCall,
+		) -> MandateRecordOf<T> {
+			let call_hash = T::Hashing::hash_of(&call);
+			let call_len = call.encoded_size().saturated_into::<u32>();
+			let info = call.get_dispatch_info();
+
+			let res = match as_origin {
+				Some(as_origin) => call.dispatch(as_origin.into()),
+				// Shamelessly stollen from the `sudo` module
+				None => call.dispatch_bypass_filter(frame_system::RawOrigin::Root.into()),
+			};
+			MandateRecord {
+				id,
+				block: frame_system::Pallet::<T>::block_number(),
+				call_hash,
+				call_len,
+				result: res.map(|_| ()).map_err(|e| e.error),
+				weight: extract_actual_weight(&res, &info),
+			}
+		}
+
+		fn note_history(record: MandateRecordOf<T>) {
+			let max_length = T::MaxHistoryLength::get();
+			if !max_length.is_zero() {
+				let count = HistoryCount::<T>::get();
+				History::<T>::insert(count % max_length, record);
+				HistoryCount::<T>::put(count.wrapping_add(1));
+			}
+		}
+
+		/// Up to `count` of the calls most recently dispatched by mandates, newest first.
+		pub fn recent_mandates(count: u32) -> Vec<MandateRecordOf<T>> {
+			let max_length = T::MaxHistoryLength::get();
+			if max_length.is_zero() {
+				return Vec::new();
+			}
+
+			let total = HistoryCount::<T>::get();
+			(1..=count.min(max_length).min(total))
+				.filter_map(|back| History::<T>::get(total.wrapping_sub(back) % max_length))
+				.collect()
+		}
+
+		/// Whether `call` may be applied without waiting for `MandateDelay`.
+		pub fn is_allowed(call: &<T as Config>::RuntimeCall) -> bool {
+			let metadata = call.get_call_metadata();
+			let pallet_name: Result<CallName, _> = metadata.pallet_name.as_bytes().to_vec().try_into();
+			let call_name: Result<CallName, _> = metadata.function_name.as_bytes().to_vec().try_into();
+			match (pallet_name, call_name) {
+				(Ok(pallet_name), Ok(call_name)) => AllowedCalls::<T>::contains_key(pallet_name, call_name),
+				_ => false,
+			}
+		}
+
+		/// All mandates currently queued, by id.
+		pub fn queued_mandates() -> Vec<(MandateId, QueuedMandateOf<T>)> {
+			Mandates::<T>::iter().collect()
+		}
 	}
 }
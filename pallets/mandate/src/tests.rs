@@ -0,0 +1,326 @@
+/*
+ * This file is part of the Nodle Chain distributed at https://github.com/NodleCode/chain
+ * Copyright (C) 2020-2022  Nodle International
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+#![cfg(test)]
+
+use super::*;
+use crate::{self as pallet_mandate};
+use frame_support::{assert_noop, assert_ok, ord_parameter_types, parameter_types, weights::Weight};
+use frame_system::{EnsureRoot, EnsureSignedBy};
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BadOrigin, BlakeTwo256, IdentityLookup},
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Mandate: pallet_mandate::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+impl frame_system::Config for Test {
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type SS58Prefix = ();
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type DbWeight = ();
+	type BaseCallFilter = frame_support::traits::Everything;
+	type OnSetCode = ();
+	type SystemWeightInfo = ();
+	type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+ord_parameter_types! {
+	pub const Admin: u64 = 1;
+	pub const Vetoer: u64 = 2;
+}
+parameter_types! {
+	pub const MandateDelay: u64 = 10;
+	pub const MaxHistoryLength: u32 = 4;
+}
+impl Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type PalletsOrigin = OriginCaller;
+	type ExternalOrigin = EnsureSignedBy<Admin, u64>;
+	type VetoOrigin = EnsureSignedBy<Vetoer, u64>;
+	type AllowListOrigin = EnsureRoot<u64>;
+	type MandateDelay = MandateDelay;
+	type MaxHistoryLength = MaxHistoryLength;
+	type WeightInfo = ();
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// A root only call whose effect is easy to observe
+fn set_storage_call() -> RuntimeCall {
+	RuntimeCall::System(frame_system::Call::set_storage {
+		items: vec![(b"key".to_vec(), b"value".to_vec())],
+	})
+}
+
+fn storage_was_set() -> bool {
+	sp_io::storage::get(b"key").is_some()
+}
+
+fn call_weight() -> Weight {
+	Mandate::dispatch_weight(&[set_storage_call()]).0
+}
+
+#[test]
+fn apply_requires_the_external_origin() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Mandate::apply(RuntimeOrigin::signed(Vetoer::get()), Box::new(set_storage_call())),
+			BadOrigin
+		);
+	})
+}
+
+#[test]
+fn apply_queues_the_call_until_the_delay_passed() {
+	new_test_ext().execute_with(|| {
+		let applied = Mandate::apply(RuntimeOrigin::signed(Admin::get()), Box::new(set_storage_call()));
+		// the queued call is only charged once enacted
+		assert_eq!(
+			applied.map(|info| info.actual_weight),
+			Ok(Some(<() as WeightInfo>::apply()))
+		);
+		assert!(!storage_was_set());
+		assert_eq!(Mandate::mandates(0).map(|m| m.enacts_at), Some(11));
+
+		assert_noop!(
+			Mandate::enact(RuntimeOrigin::signed(3), 0, call_weight()),
+			Error::<Test>::MandateNotReady
+		);
+
+		System::set_block_number(11);
+		assert_ok!(Mandate::enact(RuntimeOrigin::signed(3), 0, call_weight()));
+		assert!(storage_was_set());
+		assert_eq!(Mandate::mandates(0), None);
+
+		let history = Mandate::recent_mandates(1);
+		assert_eq!(history.len(), 1);
+		assert_eq!(history[0].id, Some(0));
+		assert_eq!(history[0].result, Ok(()));
+	})
+}
+
+#[test]
+fn vetoed_mandates_cannot_be_enacted() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Mandate::apply(
+			RuntimeOrigin::signed(Admin::get()),
+			Box::new(set_storage_call())
+		));
+
+		assert_noop!(Mandate::veto(RuntimeOrigin::signed(Admin::get()), 0), BadOrigin);
+		assert_ok!(Mandate::veto(RuntimeOrigin::signed(Vetoer::get()), 0));
+		assert_noop!(
+			Mandate::veto(RuntimeOrigin::signed(Vetoer::get()), 0),
+			Error::<Test>::UnknownMandate
+		);
+
+		System::set_block_number(11);
+		assert_noop!(
+			Mandate::enact(RuntimeOrigin::signed(3), 0, call_weight()),
+			Error::<Test>::UnknownMandate
+		);
+		assert!(!storage_was_set());
+	})
+}
+
+#[test]
+fn enact_requires_a_sufficient_weight_bound() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Mandate::apply(
+			RuntimeOrigin::signed(Admin::get()),
+			Box::new(set_storage_call())
+		));
+		System::set_block_number(11);
+
+		assert!(call_weight().any_gt(Weight::zero()));
+		assert_noop!(
+			Mandate::enact(RuntimeOrigin::signed(3), 0, Weight::zero()),
+			Error::<Test>::WrongCallWeightBound
+		);
+		assert_ok!(Mandate::enact(RuntimeOrigin::signed(3), 0, call_weight()));
+	})
+}
+
+#[test]
+fn allow_listed_calls_are_applied_immediately() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Mandate::allow_call(
+				RuntimeOrigin::signed(Admin::get()),
+				b"System".to_vec(),
+				b"set_storage".to_vec()
+			),
+			BadOrigin
+		);
+		assert_ok!(Mandate::allow_call(
+			RuntimeOrigin::root(),
+			b"System".to_vec(),
+			b"set_storage".to_vec()
+		));
+
+		assert_ok!(Mandate::apply(
+			RuntimeOrigin::signed(Admin::get()),
+			Box::new(set_storage_call())
+		));
+		assert!(storage_was_set());
+		assert!(Mandate::queued_mandates().is_empty());
+		assert_eq!(Mandate::recent_mandates(1)[0].id, None);
+	})
+}
+
+#[test]
+fn disallowed_calls_are_queued_again() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Mandate::disallow_call(RuntimeOrigin::root(), b"System".to_vec(), b"set_storage".to_vec()),
+			Error::<Test>::CallNotAllowed
+		);
+		assert_ok!(Mandate::allow_call(
+			RuntimeOrigin::root(),
+			b"System".to_vec(),
+			b"set_storage".to_vec()
+		));
+		assert_ok!(Mandate::disallow_call(
+			RuntimeOrigin::root(),
+			b"System".to_vec(),
+			b"set_storage".to_vec()
+		));
+
+		assert_ok!(Mandate::apply(
+			RuntimeOrigin::signed(Admin::get()),
+			Box::new(set_storage_call())
+		));
+		assert!(!storage_was_set());
+		assert!(Mandate::mandates(0).is_some());
+	})
+}
+
+#[test]
+fn call_names_are_bounded() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Mandate::allow_call(RuntimeOrigin::root(), vec![b'x'; 65], b"set_storage".to_vec()),
+			Error::<Test>::CallNameTooLong
+		);
+	})
+}
+
+#[test]
+fn apply_as_origin_is_queued_even_when_allow_listed() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Mandate::allow_call(
+			RuntimeOrigin::root(),
+			b"System".to_vec(),
+			b"remark".to_vec()
+		));
+
+		let remark = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+		assert_ok!(Mandate::apply_as_origin(
+			RuntimeOrigin::signed(Admin::get()),
+			Box::new(frame_system::RawOrigin::Signed(5).into()),
+			Box::new(remark.clone())
+		));
+		assert_eq!(
+			Mandate::mandates(0).and_then(|m| m.origin),
+			Some(frame_system::RawOrigin::Signed(5).into())
+		);
+		assert!(Mandate::recent_mandates(1).is_empty());
+
+		System::set_block_number(11);
+		assert_ok!(Mandate::enact(
+			RuntimeOrigin::signed(3),
+			0,
+			Mandate::dispatch_weight(&[remark]).0
+		));
+		assert_eq!(Mandate::recent_mandates(1)[0].result, Ok(()));
+	})
+}
+
+#[test]
+fn failed_batches_are_reverted_but_recorded() {
+	new_test_ext().execute_with(|| {
+		for (pallet_name, call_name) in [(&b"System"[..], &b"set_storage"[..]), (&b"Mandate"[..], &b"veto"[..])] {
+			assert_ok!(Mandate::allow_call(
+				RuntimeOrigin::root(),
+				pallet_name.to_vec(),
+				call_name.to_vec()
+			));
+		}
+
+		// Root is not the veto origin, the second call fails
+		let veto = RuntimeCall::Mandate(Call::veto { id: 42 });
+		assert_ok!(Mandate::apply_batch_all(
+			RuntimeOrigin::signed(Admin::get()),
+			vec![set_storage_call(), veto]
+		));
+		assert!(!storage_was_set());
+		System::assert_last_event(Event::BatchReverted(BadOrigin.into()).into());
+
+		let history = Mandate::recent_mandates(MaxHistoryLength::get());
+		assert_eq!(history.len(), 1);
+		assert_eq!(history[0].result, Err(BadOrigin.into()));
+	})
+}
+
+#[test]
+fn empty_batches_are_rejected() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Mandate::apply_batch_all(RuntimeOrigin::signed(Admin::get()), vec![]),
+			Error::<Test>::EmptyBatch
+		);
+	})
+}
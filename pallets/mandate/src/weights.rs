@@ -0,0 +1,132 @@
+/*
+ * This file is part of the Nodle Chain distributed at https://github.com/NodleCode/chain
+ * Copyright (C) 2020-2022  Nodle International
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Weights for pallet_mandate
+//!
+//! These weights are hand-written estimates, not benchmark output. Regenerate this file with
+//! `scripts/run_benchmarks.sh` to replace them with measured weights.
+
+// Regenerate with:
+// ./target/release/nodle-parachain
+// benchmark
+// pallet
+// --chain=dev
+// --steps=50
+// --repeat=20
+// --pallet=pallet_mandate
+// --extrinsic=*
+// --execution=wasm
+// --wasm-execution=compiled
+// --template=./.maintain/internal_pallet_weights.hbs
+// --output=weights
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{constants::RocksDbWeight, Weight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_mandate.
+pub trait WeightInfo {
+	fn apply() -> Weight;
+	fn veto() -> Weight;
+	fn enact() -> Weight;
+	fn allow_call() -> Weight;
+	fn disallow_call() -> Weight;
+}
+
+/// Weight functions for `pallet_mandate`.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: Mandate AllowedCalls (r:1 w:0)
+	// Storage: Mandate NextMandateId (r:1 w:1)
+	// Storage: Mandate Mandates (r:0 w:1)
+	fn apply() -> Weight {
+		// Estimate, see the module docs.
+		Weight::from_ref_time(22_010_000_u64)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	// Storage: Mandate Mandates (r:1 w:1)
+	fn veto() -> Weight {
+		// Estimate, see the module docs.
+		Weight::from_ref_time(19_250_000_u64)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Storage: Mandate Mandates (r:1 w:1)
+	fn enact() -> Weight {
+		// Estimate, see the module docs.
+		Weight::from_ref_time(20_680_000_u64)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Storage: Mandate AllowedCalls (r:0 w:1)
+	fn allow_call() -> Weight {
+		// Estimate, see the module docs.
+		Weight::from_ref_time(16_400_000_u64)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Storage: Mandate AllowedCalls (r:1 w:1)
+	fn disallow_call() -> Weight {
+		// Estimate, see the module docs.
+		Weight::from_ref_time(19_560_000_u64)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+}
+
+impl WeightInfo for () {
+	// Storage: Mandate AllowedCalls (r:1 w:0)
+	// Storage: Mandate NextMandateId (r:1 w:1)
+	// Storage: Mandate Mandates (r:0 w:1)
+	fn apply() -> Weight {
+		// Estimate, see the module docs.
+		Weight::from_ref_time(22_010_000_u64)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	// Storage: Mandate Mandates (r:1 w:1)
+	fn veto() -> Weight {
+		// Estimate, see the module docs.
+		Weight::from_ref_time(19_250_000_u64)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	// Storage: Mandate Mandates (r:1 w:1)
+	fn enact() -> Weight {
+		// Estimate, see the module docs.
+		Weight::from_ref_time(20_680_000_u64)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	// Storage: Mandate AllowedCalls (r:0 w:1)
+	fn allow_call() -> Weight {
+		// Estimate, see the module docs.
+		Weight::from_ref_time(16_400_000_u64)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	// Storage: Mandate AllowedCalls (r:1 w:1)
+	fn disallow_call() -> Weight {
+		// Estimate, see the module docs.
+		Weight::from_ref_time(19_560_000_u64)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+}
\ No newline at end of file
@@ -0,0 +1,47 @@
+/*
+ * This file is part of the Nodle Chain distributed at https://github.com/NodleCode/chain
+ * Copyright (C) 2020-2022  Nodle International
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Runtime API definition for the reserve pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	pub trait ReserveApi<AccountId, Balance, BlockNumber, CategoryReport, InflowSource>
+	where
+		AccountId: Codec,
+		Balance: Codec,
+		BlockNumber: Codec,
+		CategoryReport: Codec,
+		InflowSource: Codec,
+	{
+		/// Returns the index of the current spend period of the reserve owning the account
+		/// `reserve`, or `None` if there is no such reserve.
+		fn current_period(reserve: AccountId) -> Option<BlockNumber>;
+
+		/// Returns what was spent and what remains of every budget category of the reserve owning
+		/// the account `reserve` during the spend period `period`, the current one if `None`.
+		fn budget_report(reserve: AccountId, period: Option<BlockNumber>) -> Option<Vec<CategoryReport>>;
+
+		/// Returns the total amount that flowed into the reserve owning the account `reserve`
+		/// during the spend period `period`, the current one if `None`, for each source.
+		fn inflow_report(reserve: AccountId, period: Option<BlockNumber>) -> Option<Vec<(InflowSource, Balance)>>;
+	}
+}
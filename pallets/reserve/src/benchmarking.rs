@@ -24,17 +24,35 @@
 use super::*;
 
 use frame_benchmarking::{account, benchmarks_instance_pallet, impl_benchmark_test_suite};
-use frame_support::traits::{EnsureOrigin, UnfilteredDispatchable};
+use frame_support::traits::{fungibles, EnsureOrigin, Hooks, UnfilteredDispatchable};
 use frame_system::RawOrigin;
 use sp_runtime::traits::Saturating;
 use sp_std::prelude::*;
+use xcm::latest::{Junction, Junctions::X1, MultiLocation, NetworkId};
 
 #[cfg(test)]
 use crate::Pallet as Reserve;
 
 const SEED: u32 = 0;
 
+fn create_asset<T: Config<I>, I: 'static>() -> AssetIdOf<T, I>
+where
+	T::Assets: fungibles::Create<T::AccountId> + fungibles::Mutate<T::AccountId>,
+	AssetIdOf<T, I>: From<u32>,
+{
+	let asset: AssetIdOf<T, I> = 0u32.into();
+	let admin = account("admin", 0, SEED);
+	assert!(<T::Assets as fungibles::Create<T::AccountId>>::create(asset, admin, true, 1u32.into()).is_ok());
+	asset
+}
+
 benchmarks_instance_pallet! {
+	where_clause {
+		where
+			T::Assets: fungibles::Create<T::AccountId> + fungibles::Mutate<T::AccountId>,
+			AssetIdOf<T, I>: From<u32>,
+	}
+
 	tip {
 		let tipper = account("caller", 0, SEED);
 		let value = 100u32.into();
@@ -44,6 +62,7 @@ benchmarks_instance_pallet! {
 	spend {
 		let dest = account("dest", 0, SEED);
 		let value = T::Currency::minimum_balance().saturating_mul(100u32.into());
+		let _ = T::Currency::make_free_balance_be(&Pallet::<T, I>::account_id(), value.saturating_mul(2u32.into()));
 
 		let call = Call::<T, I>::spend{
 			to: dest,
@@ -52,5 +71,173 @@ benchmarks_instance_pallet! {
 		let origin = T::ExternalOrigin::successful_origin();
 	}: { call.dispatch_bypass_filter(origin)? }
 
+	spend_in_category {
+		let dest = account("dest", 0, SEED);
+		let value = T::Currency::minimum_balance().saturating_mul(100u32.into());
+		let _ = T::Currency::make_free_balance_be(&Pallet::<T, I>::account_id(), value.saturating_mul(2u32.into()));
+		Pallet::<T, I>::create_category(RawOrigin::Root.into(), vec![0; 32], value)?;
+
+		let call = Call::<T, I>::spend_in_category{
+			to: dest,
+			amount: value,
+			category: 0
+		};
+		let origin = T::ExternalOrigin::successful_origin();
+	}: { call.dispatch_bypass_filter(origin)? }
+
+	set_spend_limit {
+		let limit = Some(T::Currency::minimum_balance().saturating_mul(100u32.into()));
+	}: _(RawOrigin::Root, limit)
+
+	request_spend {
+		let dest = account("dest", 0, SEED);
+		let value = T::Currency::minimum_balance().saturating_mul(100u32.into());
+
+		let call = Call::<T, I>::request_spend{
+			to: dest,
+			amount: value
+		};
+		let origin = T::ExternalOrigin::successful_origin();
+	}: { call.dispatch_bypass_filter(origin)? }
+
+	veto_spend {
+		let dest = account("dest", 0, SEED);
+		let value = T::Currency::minimum_balance().saturating_mul(100u32.into());
+		Pallet::<T, I>::request_spend(RawOrigin::Root.into(), dest, value)?;
+
+		let call = Call::<T, I>::veto_spend{ id: 0 };
+		let origin = T::VetoOrigin::successful_origin();
+	}: { call.dispatch_bypass_filter(origin)? }
+
+	execute_spend {
+		let caller = account("caller", 0, SEED);
+		let dest = account("dest", 0, SEED);
+		let value = T::Currency::minimum_balance().saturating_mul(100u32.into());
+		let _ = T::Currency::make_free_balance_be(&Pallet::<T, I>::account_id(), value.saturating_mul(2u32.into()));
+		Pallet::<T, I>::request_spend(RawOrigin::Root.into(), dest, value)?;
+		frame_system::Pallet::<T>::set_block_number(T::SpendDelay::get().saturating_add(1u32.into()));
+	}: _(RawOrigin::Signed(caller), 0)
+
+	create_stream {
+		let beneficiary = account("beneficiary", 0, SEED);
+		let value = T::Currency::minimum_balance().saturating_mul(100u32.into());
+
+		let call = Call::<T, I>::create_stream{
+			beneficiary,
+			amount: value,
+			period: 10u32.into(),
+			start: 1u32.into(),
+			limit: Some(StreamLimit::Payments(12)),
+		};
+		let origin = T::ExternalOrigin::successful_origin();
+	}: { call.dispatch_bypass_filter(origin)? }
+
+	cancel_stream {
+		let beneficiary = account("beneficiary", 0, SEED);
+		let value = T::Currency::minimum_balance().saturating_mul(100u32.into());
+		Pallet::<T, I>::create_stream(RawOrigin::Root.into(), beneficiary, value, 10u32.into(), 1u32.into(), None)?;
+
+		let call = Call::<T, I>::cancel_stream{ id: 0 };
+		let origin = T::ExternalOrigin::successful_origin();
+	}: { call.dispatch_bypass_filter(origin)? }
+
+	pay_streams {
+		let n in 0 .. T::MaxStreamsPerBlock::get();
+
+		frame_system::Pallet::<T>::set_block_number(0u32.into());
+		let value = T::Currency::minimum_balance().saturating_mul(100u32.into());
+		let _ = T::Currency::make_free_balance_be(
+			&Pallet::<T, I>::account_id(),
+			value.saturating_mul((n + 1).into()),
+		);
+		for i in 0 .. n {
+			let beneficiary = account("beneficiary", i, SEED);
+			Pallet::<T, I>::create_stream(RawOrigin::Root.into(), beneficiary, value, 10u32.into(), 1u32.into(), None)?;
+		}
+	}: { Pallet::<T, I>::on_initialize(1u32.into()); }
+	verify {
+		assert_eq!(StreamAgenda::<T, I>::get(T::BlockNumber::from(1u32)).len(), 0);
+	}
+
+	spend_asset {
+		let asset = create_asset::<T, I>();
+		let dest = account("dest", 0, SEED);
+		let value: AssetBalanceOf<T, I> = 100u32.into();
+		<T::Assets as fungibles::Mutate<T::AccountId>>::mint_into(
+			asset,
+			&Pallet::<T, I>::account_id(),
+			value.saturating_mul(2u32.into()),
+		)?;
+		Pallet::<T, I>::set_asset_spend_limit(RawOrigin::Root.into(), asset, Some(value))?;
+
+		let call = Call::<T, I>::spend_asset{
+			asset,
+			to: dest,
+			amount: value
+		};
+		let origin = T::ExternalOrigin::successful_origin();
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert_eq!(Pallet::<T, I>::asset_balance(asset), value);
+	}
+
+	tip_asset {
+		let asset = create_asset::<T, I>();
+		let tipper: T::AccountId = account("caller", 0, SEED);
+		let value: AssetBalanceOf<T, I> = 100u32.into();
+		<T::Assets as fungibles::Mutate<T::AccountId>>::mint_into(asset, &tipper, value)?;
+	}: _(RawOrigin::Signed(tipper), asset, value)
+	verify {
+		assert_eq!(Pallet::<T, I>::asset_balance(asset), value);
+	}
+
+	spend_xcm {
+		let value = T::Currency::minimum_balance().saturating_mul(100u32.into());
+		let _ = T::Currency::make_free_balance_be(&Pallet::<T, I>::account_id(), value.saturating_mul(1_000u32.into()));
+		let dest = MultiLocation::parent();
+		let beneficiary = MultiLocation::new(0, X1(Junction::AccountId32 { network: NetworkId::Any, id: [0u8; 32] }));
+
+		let call = Call::<T, I>::spend_xcm{
+			dest: Box::new(dest.into()),
+			beneficiary: Box::new(beneficiary.into()),
+			amount: value
+		};
+		let origin = T::ExternalOrigin::successful_origin();
+	}: { call.dispatch_bypass_filter(origin)? }
+
+	create_category {
+		let allowance = T::Currency::minimum_balance().saturating_mul(100u32.into());
+
+		let call = Call::<T, I>::create_category{
+			name: vec![0; 32],
+			allowance
+		};
+		let origin = T::ExternalOrigin::successful_origin();
+	}: { call.dispatch_bypass_filter(origin)? }
+
+	set_category_allowance {
+		let allowance = T::Currency::minimum_balance().saturating_mul(100u32.into());
+		Pallet::<T, I>::create_category(RawOrigin::Root.into(), vec![0; 32], allowance)?;
+
+		let call = Call::<T, I>::set_category_allowance{
+			id: 0,
+			allowance: allowance.saturating_mul(2u32.into())
+		};
+		let origin = T::ExternalOrigin::successful_origin();
+	}: { call.dispatch_bypass_filter(origin)? }
+
+	remove_category {
+		let allowance = T::Currency::minimum_balance().saturating_mul(100u32.into());
+		Pallet::<T, I>::create_category(RawOrigin::Root.into(), vec![0; 32], allowance)?;
+
+		let call = Call::<T, I>::remove_category{ id: 0 };
+		let origin = T::ExternalOrigin::successful_origin();
+	}: { call.dispatch_bypass_filter(origin)? }
+
+	set_asset_spend_limit {
+		let asset = create_asset::<T, I>();
+		let limit = Some(100u32.into());
+	}: _(RawOrigin::Root, asset, limit)
+
 	impl_benchmark_test_suite!(Reserve, crate::tests::new_test_ext(), crate::tests::Test,);
 }
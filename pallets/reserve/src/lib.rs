@@ -25,14 +25,33 @@ mod benchmarking;
 #[cfg(test)]
 mod tests;
 
+use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::{
-	dispatch::GetDispatchInfo,
-	traits::{Currency, ExistenceRequirement, Get, Imbalance, OnUnbalanced},
-	PalletId,
+	dispatch::{extract_actual_weight, GetDispatchInfo, PostDispatchInfo},
+	ensure,
+	storage::with_storage_layer,
+	traits::{
+		fungibles, ConstU32, Contains, Currency, ExistenceRequirement, Get, Imbalance, OnUnbalanced, OriginTrait,
+		WithdrawReasons,
+	},
+	weights::Weight,
+	BoundedVec, PalletId,
 };
-use sp_runtime::traits::{AccountIdConversion, Dispatchable};
-use sp_std::prelude::Box;
-use support::WithAccountId;
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{AccountIdConversion, AtLeast32BitUnsigned, Convert, Dispatchable, Hash, One, Saturating, Zero},
+	DispatchError, DispatchResult, RuntimeDebug, SaturatedConversion,
+};
+use sp_std::{marker::PhantomData, prelude::Box, vec, vec::Vec};
+use support::{IsShutdown, ProtocolFeeHandler, WithAccountId};
+use xcm::{
+	latest::{
+		AssetId::Concrete, ExecuteXcm, Fungibility::Fungible, Instruction, MultiAsset, MultiAssetFilter, MultiLocation,
+		WeightLimit, WildMultiAsset, Xcm,
+	},
+	VersionedMultiLocation,
+};
+use xcm_executor::traits::{InvertLocation, WeightBounds, WeightTrader};
 
 #[cfg(feature = "std")]
 use frame_support::traits::GenesisBuild;
@@ -42,9 +61,127 @@ pub use weights::WeightInfo;
 
 pub use pallet::*;
 
-type BalanceOf<T, I> = <<T as Config<I>>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+pub type BalanceOf<T, I> = <<T as Config<I>>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 type NegativeImbalanceOf<T, I> =
 	<<T as Config<I>>::Currency as Currency<<T as frame_system::Config>::AccountId>>::NegativeImbalance;
+pub type AssetIdOf<T, I> =
+	<<T as Config<I>>::Assets as fungibles::Inspect<<T as frame_system::Config>::AccountId>>::AssetId;
+pub type AssetBalanceOf<T, I> =
+	<<T as Config<I>>::Assets as fungibles::Inspect<<T as frame_system::Config>::AccountId>>::Balance;
+pub type SpendRequestId = u32;
+pub type SpendRequestOf<T, I> =
+	SpendRequest<<T as frame_system::Config>::AccountId, BalanceOf<T, I>, <T as frame_system::Config>::BlockNumber>;
+
+/// A spending queued by the `ExternalOrigin`, which can be executed by anyone once `execute_at`
+/// is reached unless it got vetoed in the meantime.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct SpendRequest<AccountId, Balance, BlockNumber> {
+	pub to: AccountId,
+	pub amount: Balance,
+	pub execute_at: BlockNumber,
+}
+
+pub type CategoryId = u32;
+pub type MaxCategoryNameLength = ConstU32<32>;
+pub type BudgetCategoryOf<T, I> = BudgetCategory<BalanceOf<T, I>>;
+pub type CategoryReportOf<T, I> = CategoryReport<BalanceOf<T, I>>;
+
+/// A budget line defined by governance, spendings tagged with it can't go over `allowance`
+/// during a `SpendPeriod`.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct BudgetCategory<Balance> {
+	pub name: BoundedVec<u8, MaxCategoryNameLength>,
+	pub allowance: Balance,
+}
+
+/// What was spent out of a budget category during a given spend period.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct CategoryReport<Balance> {
+	pub id: CategoryId,
+	pub name: Vec<u8>,
+	pub allowance: Balance,
+	pub spent: Balance,
+	pub remaining: Balance,
+}
+
+/// Where the funds flowing into a reserve come from.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, PartialOrd, Ord, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub enum InflowSource {
+	/// Share of the transaction fees.
+	Fees,
+	/// Share of the transaction tips.
+	Tips,
+	/// Dust left by reaped accounts.
+	Dust,
+	/// Protocol fees taken on allocations.
+	ProtocolFees,
+	/// Funds sent with the `tip` call.
+	DirectTip,
+	/// Imbalances handed to the reserve without any source.
+	Other,
+}
+
+frame_support::parameter_types! {
+	pub const FeesSource: InflowSource = InflowSource::Fees;
+	pub const TipsSource: InflowSource = InflowSource::Tips;
+	pub const DustSource: InflowSource = InflowSource::Dust;
+}
+
+/// Credits the reserve with the imbalances it is handed, recording them as coming from `Source`.
+/// The reserve pallet itself records them as `InflowSource::Other`.
+pub struct InflowFrom<T, I, Source>(PhantomData<(T, I, Source)>);
+
+impl<T: Config<I>, I: 'static, Source: Get<InflowSource>> OnUnbalanced<NegativeImbalanceOf<T, I>>
+	for InflowFrom<T, I, Source>
+{
+	fn on_nonzero_unbalanced(amount: NegativeImbalanceOf<T, I>) {
+		Pallet::<T, I>::deposit_inflow(Source::get(), amount);
+	}
+}
+
+pub type StreamId = u32;
+pub type PaymentStreamOf<T, I> =
+	PaymentStream<<T as frame_system::Config>::AccountId, BalanceOf<T, I>, <T as frame_system::Config>::BlockNumber>;
+
+/// When a payment stream should stop paying its beneficiary.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub enum StreamLimit<BlockNumber> {
+	/// Stop after this many payments.
+	Payments(u32),
+	/// Do not pay anymore from this block on.
+	Until(BlockNumber),
+}
+
+/// A recurring payment of `amount` to `beneficiary` every `period` blocks, paid out of the
+/// reserve by the `on_initialize` hook.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct PaymentStream<AccountId, Balance, BlockNumber> {
+	pub beneficiary: AccountId,
+	pub amount: Balance,
+	pub period: BlockNumber,
+	/// Block at which the next payment is due.
+	pub next_payment: BlockNumber,
+	pub limit: Option<StreamLimit<BlockNumber>>,
+}
+
+impl<AccountId, Balance, BlockNumber: AtLeast32BitUnsigned + Copy> PaymentStream<AccountId, Balance, BlockNumber> {
+	/// Returns `true` if the stream should not pay its beneficiary anymore.
+	pub fn is_over(&self) -> bool {
+		match self.limit {
+			Some(StreamLimit::Payments(left)) => left.is_zero(),
+			Some(StreamLimit::Until(end)) => self.next_payment >= end,
+			None => false,
+		}
+	}
+
+	/// Account for a successful payment and move on to the next one.
+	fn advance(&mut self) {
+		self.next_payment = self.next_payment.saturating_add(self.period);
+		if let Some(StreamLimit::Payments(left)) = self.limit.as_mut() {
+			*left = left.saturating_sub(1);
+		}
+	}
+}
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -57,8 +194,50 @@ pub mod pallet {
 		type RuntimeEvent: From<Event<Self, I>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 		type ExternalOrigin: EnsureOrigin<Self::RuntimeOrigin>;
 		type Currency: Currency<Self::AccountId>;
-		type RuntimeCall: Parameter + Dispatchable<RuntimeOrigin = Self::RuntimeOrigin> + GetDispatchInfo;
+		/// Fungible assets, other than `Currency`, that the reserve can hold and spend. Typically
+		/// foreign assets received over XCM.
+		type Assets: fungibles::Inspect<Self::AccountId> + fungibles::Transfer<Self::AccountId>;
+		type RuntimeCall: Parameter
+			+ Dispatchable<RuntimeOrigin = Self::RuntimeOrigin, PostInfo = PostDispatchInfo>
+			+ GetDispatchInfo
+			+ IsType<<Self as frame_system::Config>::RuntimeCall>;
+		/// Calls the reserve account is allowed to dispatch through `apply_as`, this applies to
+		/// the calls nested in batches too.
+		type CallFilter: Contains<<Self as Config<I>>::RuntimeCall>;
 		type PalletId: Get<PalletId>;
+		/// Length, in blocks, of the periods over which the spend limit applies.
+		#[pallet::constant]
+		type SpendPeriod: Get<Self::BlockNumber>;
+		/// Number of blocks a spend request has to wait before it can be executed.
+		#[pallet::constant]
+		type SpendDelay: Get<Self::BlockNumber>;
+		/// Origin that can veto a pending spend request.
+		type VetoOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// Maximum number of stream payments processed in a single block.
+		#[pallet::constant]
+		type MaxStreamsPerBlock: Get<u32>;
+		/// Number of blocks to wait before retrying a failed stream payment.
+		#[pallet::constant]
+		type StreamRetryDelay: Get<Self::BlockNumber>;
+		/// Executes the XCM messages built by `spend_xcm`.
+		type XcmExecutor: ExecuteXcm<<Self as Config<I>>::RuntimeCall>;
+		/// Weighs the XCM messages before their execution.
+		type Weigher: WeightBounds<<Self as Config<I>>::RuntimeCall>;
+		/// Prices the local execution of the XCM messages sent by `spend_xcm`, this should be the
+		/// runtime's XCM `Trader`. The fees it is paid are handed over to it, as the XCM executor
+		/// would do, and dealt with once it is dropped.
+		type Trader: WeightTrader;
+		/// Location of `Currency` as seen by this chain.
+		type NativeAssetLocation: Get<MultiLocation>;
+		/// Location of the asset `Trader` is paid in, the fees withdrawn in `Currency` are handed
+		/// over to it under this location.
+		type FeeAssetLocation: Get<MultiLocation>;
+		/// Used to reanchor the assets sent to another chain.
+		type LocationInverter: InvertLocation;
+		/// Converts the reserve account into the origin of the XCM messages.
+		type AccountIdToMultiLocation: Convert<Self::AccountId, MultiLocation>;
+		/// Nothing can leave the reserve while the chain is under an emergency shutdown.
+		type EmergencyShutdown: IsShutdown;
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
 	}
@@ -68,7 +247,19 @@ pub mod pallet {
 	pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
 
 	#[pallet::hooks]
-	impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {}
+	impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let due = StreamAgenda::<T, I>::take(now);
+			let processed = due.len() as u32;
+
+			for id in due {
+				Self::pay_stream(id, now);
+			}
+
+			T::WeightInfo::pay_streams(processed)
+				.saturating_add(Self::schedule_stream_weight().saturating_mul(processed.into()))
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config<I>, I: 'static> Pallet<T, I> {
@@ -78,7 +269,7 @@ pub mod pallet {
 		pub fn spend(origin: OriginFor<T>, to: T::AccountId, amount: BalanceOf<T, I>) -> DispatchResultWithPostInfo {
 			T::ExternalOrigin::try_origin(origin).map(|_| ()).or_else(ensure_root)?;
 
-			let _ = T::Currency::transfer(&Self::account_id(), &to, amount, ExistenceRequirement::KeepAlive);
+			Self::do_spend(&to, amount)?;
 
 			Self::deposit_event(Event::SpentFunds(to, amount));
 
@@ -91,7 +282,8 @@ pub mod pallet {
 		pub fn tip(origin: OriginFor<T>, amount: BalanceOf<T, I>) -> DispatchResultWithPostInfo {
 			let tipper = ensure_signed(origin)?;
 
-			let _ = T::Currency::transfer(&tipper, &Self::account_id(), amount, ExistenceRequirement::AllowDeath);
+			T::Currency::transfer(&tipper, &Self::account_id(), amount, ExistenceRequirement::AllowDeath)?;
+			Self::note_inflow(InflowSource::DirectTip, amount);
 
 			Self::deposit_event(Event::TipReceived(tipper, amount));
 
@@ -104,16 +296,341 @@ pub mod pallet {
 		#[pallet::weight({
             let dispatch_info = call.get_dispatch_info();
             (
-                dispatch_info.weight.saturating_add(Weight::from_ref_time(10_000)),
+                dispatch_info.weight
+                    .saturating_add(Weight::from_ref_time(10_000))
+                    .saturating_add(T::DbWeight::get().reads_writes(3, 1)),
                 dispatch_info.class,
             )
         })]
 		pub fn apply_as(origin: OriginFor<T>, call: Box<<T as Config<I>>::RuntimeCall>) -> DispatchResultWithPostInfo {
 			T::ExternalOrigin::try_origin(origin).map(|_| ()).or_else(ensure_root)?;
 
-			let res = call.dispatch(frame_system::RawOrigin::Signed(Self::account_id()).into());
+			Self::do_apply_as(*call, None)?;
+
+			Ok(().into())
+		}
+
+		/// Set the maximum amount that can leave the reserve per `SpendPeriod`, `None` removes
+		/// the limit.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::set_spend_limit())]
+		pub fn set_spend_limit(origin: OriginFor<T>, limit: Option<BalanceOf<T, I>>) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+
+			SpendLimit::<T, I>::set(limit);
+
+			Self::deposit_event(Event::SpendLimitSet(limit));
+
+			Ok(().into())
+		}
+
+		/// Queue a spending of `amount` funds to `to`, executable once `SpendDelay` blocks passed.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::request_spend())]
+		pub fn request_spend(
+			origin: OriginFor<T>,
+			to: T::AccountId,
+			amount: BalanceOf<T, I>,
+		) -> DispatchResultWithPostInfo {
+			T::ExternalOrigin::try_origin(origin).map(|_| ()).or_else(ensure_root)?;
+
+			let id = NextSpendRequestId::<T, I>::get();
+			let execute_at = frame_system::Pallet::<T>::block_number().saturating_add(T::SpendDelay::get());
+			SpendRequests::<T, I>::insert(
+				id,
+				SpendRequest {
+					to: to.clone(),
+					amount,
+					execute_at,
+				},
+			);
+			NextSpendRequestId::<T, I>::put(id.wrapping_add(1));
+
+			Self::deposit_event(Event::SpendRequested(id, to, amount, execute_at));
+
+			Ok(().into())
+		}
+
+		/// Veto a pending spend request.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::veto_spend())]
+		pub fn veto_spend(origin: OriginFor<T>, id: SpendRequestId) -> DispatchResultWithPostInfo {
+			T::VetoOrigin::try_origin(origin).map(|_| ()).or_else(ensure_root)?;
+
+			SpendRequests::<T, I>::take(id).ok_or(Error::<T, I>::UnknownSpendRequest)?;
+
+			Self::deposit_event(Event::SpendVetoed(id));
+
+			Ok(().into())
+		}
+
+		/// Execute a spend request whose delay has passed. Can be called by anyone.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::execute_spend())]
+		pub fn execute_spend(origin: OriginFor<T>, id: SpendRequestId) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+
+			let request = SpendRequests::<T, I>::get(id).ok_or(Error::<T, I>::UnknownSpendRequest)?;
+			ensure!(
+				frame_system::Pallet::<T>::block_number() >= request.execute_at,
+				Error::<T, I>::SpendRequestNotReady
+			);
+
+			Self::do_spend(&request.to, request.amount)?;
+			SpendRequests::<T, I>::remove(id);
+
+			Self::deposit_event(Event::SpentFunds(request.to, request.amount));
+
+			Ok(().into())
+		}
+
+		/// Pay `amount` to `beneficiary` every `period` blocks, starting at block `start`, until
+		/// the optional `limit` is reached or the stream is cancelled.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::create_stream())]
+		pub fn create_stream(
+			origin: OriginFor<T>,
+			beneficiary: T::AccountId,
+			amount: BalanceOf<T, I>,
+			period: T::BlockNumber,
+			start: T::BlockNumber,
+			limit: Option<StreamLimit<T::BlockNumber>>,
+		) -> DispatchResultWithPostInfo {
+			T::ExternalOrigin::try_origin(origin).map(|_| ()).or_else(ensure_root)?;
+
+			ensure!(!amount.is_zero(), Error::<T, I>::ZeroStreamAmount);
+			ensure!(!period.is_zero(), Error::<T, I>::ZeroStreamPeriod);
+
+			let next_block = frame_system::Pallet::<T>::block_number().saturating_add(One::one());
+			let stream = PaymentStream {
+				beneficiary: beneficiary.clone(),
+				amount,
+				period,
+				next_payment: start.max(next_block),
+				limit,
+			};
+			ensure!(!stream.is_over(), Error::<T, I>::StreamAlreadyOver);
+
+			let id = NextStreamId::<T, I>::get();
+			Self::schedule_stream(id, stream.next_payment)?;
+			Streams::<T, I>::insert(id, stream);
+			NextStreamId::<T, I>::put(id.wrapping_add(1));
+
+			Self::deposit_event(Event::StreamCreated(id, beneficiary, amount, period));
+
+			Ok(().into())
+		}
+
+		/// Stop a payment stream, no further payments will be made.
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::cancel_stream())]
+		pub fn cancel_stream(origin: OriginFor<T>, id: StreamId) -> DispatchResultWithPostInfo {
+			T::ExternalOrigin::try_origin(origin).map(|_| ()).or_else(ensure_root)?;
+
+			// Any agenda entry left behind is skipped once it comes up
+			Streams::<T, I>::take(id).ok_or(Error::<T, I>::UnknownStream)?;
+
+			Self::deposit_event(Event::StreamCancelled(id));
+
+			Ok(().into())
+		}
+
+		/// Spend `amount` of the fungible `asset` from the reserve account to `to`, accounting
+		/// for it in the spend limit of `asset`.
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::spend_asset())]
+		pub fn spend_asset(
+			origin: OriginFor<T>,
+			asset: AssetIdOf<T, I>,
+			to: T::AccountId,
+			amount: AssetBalanceOf<T, I>,
+		) -> DispatchResultWithPostInfo {
+			T::ExternalOrigin::try_origin(origin).map(|_| ()).or_else(ensure_root)?;
+
+			Self::note_asset_spending(asset, amount)?;
+			<T::Assets as fungibles::Transfer<T::AccountId>>::transfer(asset, &Self::account_id(), &to, amount, true)?;
+
+			Self::deposit_event(Event::AssetSpent(asset, to, amount));
+
+			Ok(().into())
+		}
+
+		/// Deposit `amount` of the fungible `asset` in the reserve account
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::tip_asset())]
+		pub fn tip_asset(
+			origin: OriginFor<T>,
+			asset: AssetIdOf<T, I>,
+			amount: AssetBalanceOf<T, I>,
+		) -> DispatchResultWithPostInfo {
+			let tipper = ensure_signed(origin)?;
+
+			<T::Assets as fungibles::Transfer<T::AccountId>>::transfer(
+				asset,
+				&tipper,
+				&Self::account_id(),
+				amount,
+				false,
+			)?;
+
+			Self::deposit_event(Event::AssetTipReceived(asset, tipper, amount));
+
+			Ok(().into())
+		}
+
+		/// Send `amount` funds from the reserve account to `beneficiary` on the `dest` chain
+		/// through a reserve transfer. The local execution of the message is priced by the
+		/// runtime's XCM `Trader` and paid by the reserve on top of `amount`, remote fees are
+		/// deducted from `amount` on arrival.
+		#[pallet::call_index(11)]
+		#[pallet::weight(
+			T::WeightInfo::spend_xcm().saturating_add(Pallet::<T, I>::xcm_spend_weight(dest, beneficiary, *amount))
+		)]
+		pub fn spend_xcm(
+			origin: OriginFor<T>,
+			dest: Box<VersionedMultiLocation>,
+			beneficiary: Box<VersionedMultiLocation>,
+			amount: BalanceOf<T, I>,
+		) -> DispatchResultWithPostInfo {
+			T::ExternalOrigin::try_origin(origin).map(|_| ()).or_else(ensure_root)?;
+
+			let dest: MultiLocation = (*dest).try_into().map_err(|()| Error::<T, I>::BadXcmVersion)?;
+			let beneficiary: MultiLocation = (*beneficiary).try_into().map_err(|()| Error::<T, I>::BadXcmVersion)?;
+			ensure!(!amount.is_zero(), Error::<T, I>::ZeroXcmAmount);
+
+			let mut message = Self::build_xcm_spend(&dest, &beneficiary, amount)?;
+			let weight = T::Weigher::weight(&mut message).map_err(|()| Error::<T, I>::XcmWeighingFailed)?;
+			let fee = Self::estimate_xcm_fee(weight)?;
+			let message_hash = message.using_encoded(sp_io::hashing::blake2_256);
+
+			Self::note_spending(amount.saturating_add(fee))?;
+			Self::pay_xcm_fee(weight, fee)?;
+
+			// The message is executed in credit, its local execution was paid for above
+			let origin_location = T::AccountIdToMultiLocation::convert(Self::account_id());
+			T::XcmExecutor::execute_xcm_in_credit(origin_location, message, weight, weight)
+				.ensure_complete()
+				.map_err(|_| Error::<T, I>::XcmExecutionFailed)?;
+
+			Self::deposit_event(Event::SpentXcm(dest, beneficiary, amount, fee, message_hash));
+
+			Ok(().into())
+		}
+
+		/// Define a new budget category allowing to spend up to `allowance` per `SpendPeriod`.
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::create_category())]
+		pub fn create_category(
+			origin: OriginFor<T>,
+			name: Vec<u8>,
+			allowance: BalanceOf<T, I>,
+		) -> DispatchResultWithPostInfo {
+			T::ExternalOrigin::try_origin(origin).map(|_| ()).or_else(ensure_root)?;
+
+			let name: BoundedVec<u8, MaxCategoryNameLength> =
+				name.try_into().map_err(|_| Error::<T, I>::CategoryNameTooLong)?;
+			let id = NextCategoryId::<T, I>::get();
+			Categories::<T, I>::insert(id, BudgetCategory { name, allowance });
+			NextCategoryId::<T, I>::put(id.wrapping_add(1));
+
+			Self::deposit_event(Event::CategoryCreated(id, allowance));
+
+			Ok(().into())
+		}
+
+		/// Change the allowance of a budget category, this applies to the current period too.
+		#[pallet::call_index(13)]
+		#[pallet::weight(T::WeightInfo::set_category_allowance())]
+		pub fn set_category_allowance(
+			origin: OriginFor<T>,
+			id: CategoryId,
+			allowance: BalanceOf<T, I>,
+		) -> DispatchResultWithPostInfo {
+			T::ExternalOrigin::try_origin(origin).map(|_| ()).or_else(ensure_root)?;
+
+			Categories::<T, I>::try_mutate(id, |maybe_category| {
+				let category = maybe_category.as_mut().ok_or(Error::<T, I>::UnknownCategory)?;
+				category.allowance = allowance;
+				Ok::<_, DispatchError>(())
+			})?;
+
+			Self::deposit_event(Event::CategoryAllowanceSet(id, allowance));
+
+			Ok(().into())
+		}
+
+		/// Remove a budget category, what was spent out of it stays on record.
+		#[pallet::call_index(14)]
+		#[pallet::weight(T::WeightInfo::remove_category())]
+		pub fn remove_category(origin: OriginFor<T>, id: CategoryId) -> DispatchResultWithPostInfo {
+			T::ExternalOrigin::try_origin(origin).map(|_| ()).or_else(ensure_root)?;
+
+			Categories::<T, I>::take(id).ok_or(Error::<T, I>::UnknownCategory)?;
+
+			Self::deposit_event(Event::CategoryRemoved(id));
+
+			Ok(().into())
+		}
+
+		/// Set the maximum amount of the fungible `asset` that can leave the reserve per
+		/// `SpendPeriod`, `None` removes the limit.
+		#[pallet::call_index(15)]
+		#[pallet::weight(T::WeightInfo::set_asset_spend_limit())]
+		pub fn set_asset_spend_limit(
+			origin: OriginFor<T>,
+			asset: AssetIdOf<T, I>,
+			limit: Option<AssetBalanceOf<T, I>>,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+
+			AssetSpendLimit::<T, I>::set(asset, limit);
+
+			Self::deposit_event(Event::AssetSpendLimitSet(asset, limit));
+
+			Ok(().into())
+		}
+
+		/// Spend `amount` funds from the reserve account to `to`, accounting them under
+		/// `category`.
+		#[pallet::call_index(16)]
+		#[pallet::weight(T::WeightInfo::spend_in_category())]
+		pub fn spend_in_category(
+			origin: OriginFor<T>,
+			to: T::AccountId,
+			amount: BalanceOf<T, I>,
+			category: CategoryId,
+		) -> DispatchResultWithPostInfo {
+			T::ExternalOrigin::try_origin(origin).map(|_| ()).or_else(ensure_root)?;
+
+			Self::note_category_spending(category, amount)?;
+			Self::do_spend(&to, amount)?;
+
+			Self::deposit_event(Event::SpentFunds(to, amount));
+
+			Ok(().into())
+		}
+
+		#[allow(clippy::boxed_local)]
+		/// Dispatch a call as coming from the reserve account, what leaves the reserve is
+		/// accounted under `category`.
+		#[pallet::call_index(17)]
+		#[pallet::weight({
+            let dispatch_info = call.get_dispatch_info();
+            (
+                dispatch_info.weight
+                    .saturating_add(Weight::from_ref_time(10_000))
+                    .saturating_add(T::DbWeight::get().reads_writes(5, 2)),
+                dispatch_info.class,
+            )
+        })]
+		pub fn apply_as_in_category(
+			origin: OriginFor<T>,
+			call: Box<<T as Config<I>>::RuntimeCall>,
+			category: CategoryId,
+		) -> DispatchResultWithPostInfo {
+			T::ExternalOrigin::try_origin(origin).map(|_| ()).or_else(ensure_root)?;
 
-			Self::deposit_event(Event::ReserveOp(res.map(|_| ()).map_err(|e| e.error)));
+			Self::do_apply_as(*call, Some(category))?;
 
 			Ok(().into())
 		}
@@ -122,16 +639,159 @@ pub mod pallet {
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config<I>, I: 'static = ()> {
-		/// Some amount was deposited (e.g. for transaction fees).
-		Deposit(BalanceOf<T, I>),
+		/// Some amount was deposited (e.g. for transaction fees) \[source, amount\]
+		Deposit(InflowSource, BalanceOf<T, I>),
 		/// Some funds were spent from the reserve.
 		SpentFunds(T::AccountId, BalanceOf<T, I>),
 		/// Someone tipped the company reserve
 		TipReceived(T::AccountId, BalanceOf<T, I>),
-		/// We executed a call coming from the company reserve account
-		ReserveOp(DispatchResult),
+		/// We executed a call coming from the company reserve account \[call_hash, result,
+		/// actual_weight\]
+		ReserveOp(T::Hash, DispatchResult, Weight),
+		/// The spend limit per period was changed
+		SpendLimitSet(Option<BalanceOf<T, I>>),
+		/// A spending was queued \[id, to, amount, execute_at\]
+		SpendRequested(SpendRequestId, T::AccountId, BalanceOf<T, I>, T::BlockNumber),
+		/// A pending spend request was vetoed
+		SpendVetoed(SpendRequestId),
+		/// A payment stream was created \[id, beneficiary, amount, period\]
+		StreamCreated(StreamId, T::AccountId, BalanceOf<T, I>, T::BlockNumber),
+		/// A payment stream was cancelled
+		StreamCancelled(StreamId),
+		/// A payment stream paid its beneficiary \[id, beneficiary, amount\]
+		StreamPaid(StreamId, T::AccountId, BalanceOf<T, I>),
+		/// A payment stream failed to pay its beneficiary, it will be retried \[id, error, retry_at\]
+		StreamPaymentFailed(StreamId, DispatchError, T::BlockNumber),
+		/// A payment stream made its last payment
+		StreamCompleted(StreamId),
+		/// Some amount of a fungible asset was spent from the reserve \[asset, to, amount\]
+		AssetSpent(AssetIdOf<T, I>, T::AccountId, AssetBalanceOf<T, I>),
+		/// Someone tipped the reserve with a fungible asset \[asset, who, amount\]
+		AssetTipReceived(AssetIdOf<T, I>, T::AccountId, AssetBalanceOf<T, I>),
+		/// Some funds were sent to another chain, the fee paid for the local execution of the
+		/// message comes on top of the amount \[dest, beneficiary, amount, fee, message_hash\]
+		SpentXcm(MultiLocation, MultiLocation, BalanceOf<T, I>, BalanceOf<T, I>, [u8; 32]),
+		/// A budget category was defined \[id, allowance\]
+		CategoryCreated(CategoryId, BalanceOf<T, I>),
+		/// The allowance of a budget category changed \[id, allowance\]
+		CategoryAllowanceSet(CategoryId, BalanceOf<T, I>),
+		/// A budget category was removed
+		CategoryRemoved(CategoryId),
+		/// Some funds were accounted under a budget category \[id, amount\]
+		CategorySpending(CategoryId, BalanceOf<T, I>),
+		/// The spend limit per period of a fungible asset was changed \[asset, limit\]
+		AssetSpendLimitSet(AssetIdOf<T, I>, Option<AssetBalanceOf<T, I>>),
+	}
+
+	#[pallet::error]
+	pub enum Error<T, I = ()> {
+		/// The spending would go over the limit of the current period
+		SpendLimitExceeded,
+		/// There is no pending spend request with this id
+		UnknownSpendRequest,
+		/// The spend request delay has not passed yet
+		SpendRequestNotReady,
+		/// Payment streams must pay a non-zero amount
+		ZeroStreamAmount,
+		/// Payment streams must have a non-zero period
+		ZeroStreamPeriod,
+		/// The payment stream would not make any payment
+		StreamAlreadyOver,
+		/// There is no payment stream with this id
+		UnknownStream,
+		/// Too many payments are already scheduled around the requested block
+		StreamAgendaFull,
+		/// The given XCM location is in a version we can't convert
+		BadXcmVersion,
+		/// Cross chain spends must send a non-zero amount
+		ZeroXcmAmount,
+		/// The XCM message could not be weighed
+		XcmWeighingFailed,
+		/// The local execution fee of the XCM message could not be estimated or paid
+		XcmFeeEstimationFailed,
+		/// The XCM message failed to execute
+		XcmExecutionFailed,
+		/// The reserve is not allowed to dispatch this call
+		CallFiltered,
+		/// There is no budget category with this id
+		UnknownCategory,
+		/// The name of the budget category is too long
+		CategoryNameTooLong,
+		/// The spending would go over the allowance of its category for the current period
+		CategoryAllowanceExceeded,
+		/// Funds are frozen during an emergency shutdown
+		UnderShutdown,
 	}
 
+	/// Maximum amount that can leave the reserve per `SpendPeriod`, unlimited if unset.
+	#[pallet::storage]
+	#[pallet::getter(fn spend_limit)]
+	pub type SpendLimit<T: Config<I>, I: 'static = ()> = StorageValue<_, BalanceOf<T, I>, OptionQuery>;
+
+	/// Index of the current spend period and the amount spent so far during it.
+	#[pallet::storage]
+	#[pallet::getter(fn spent)]
+	pub type Spent<T: Config<I>, I: 'static = ()> = StorageValue<_, (T::BlockNumber, BalanceOf<T, I>), ValueQuery>;
+
+	/// Spend requests waiting for their delay to pass.
+	#[pallet::storage]
+	#[pallet::getter(fn spend_requests)]
+	pub type SpendRequests<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, SpendRequestId, SpendRequestOf<T, I>, OptionQuery>;
+
+	#[pallet::storage]
+	pub type NextSpendRequestId<T: Config<I>, I: 'static = ()> = StorageValue<_, SpendRequestId, ValueQuery>;
+
+	/// Active payment streams.
+	#[pallet::storage]
+	#[pallet::getter(fn streams)]
+	pub type Streams<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, StreamId, PaymentStreamOf<T, I>, OptionQuery>;
+
+	/// Payment streams due at a given block.
+	#[pallet::storage]
+	#[pallet::getter(fn stream_agenda)]
+	pub type StreamAgenda<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, T::BlockNumber, BoundedVec<StreamId, T::MaxStreamsPerBlock>, ValueQuery>;
+
+	#[pallet::storage]
+	pub type NextStreamId<T: Config<I>, I: 'static = ()> = StorageValue<_, StreamId, ValueQuery>;
+
+	/// Budget categories defined by governance.
+	#[pallet::storage]
+	#[pallet::getter(fn categories)]
+	pub type Categories<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, CategoryId, BudgetCategoryOf<T, I>, OptionQuery>;
+
+	#[pallet::storage]
+	pub type NextCategoryId<T: Config<I>, I: 'static = ()> = StorageValue<_, CategoryId, ValueQuery>;
+
+	/// Funds that flowed into the reserve, per spend period index and source.
+	#[pallet::storage]
+	#[pallet::getter(fn inflows)]
+	pub type Inflows<T: Config<I>, I: 'static = ()> =
+		StorageDoubleMap<_, Twox64Concat, T::BlockNumber, Twox64Concat, InflowSource, BalanceOf<T, I>, ValueQuery>;
+
+	/// Amount spent out of each budget category, per spend period index.
+	#[pallet::storage]
+	#[pallet::getter(fn category_spent)]
+	pub type CategorySpent<T: Config<I>, I: 'static = ()> =
+		StorageDoubleMap<_, Twox64Concat, CategoryId, Twox64Concat, T::BlockNumber, BalanceOf<T, I>, ValueQuery>;
+
+	/// Maximum amount of each fungible asset that can leave the reserve per `SpendPeriod`,
+	/// unlimited if unset.
+	#[pallet::storage]
+	#[pallet::getter(fn asset_spend_limit)]
+	pub type AssetSpendLimit<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, AssetIdOf<T, I>, AssetBalanceOf<T, I>, OptionQuery>;
+
+	/// Index of the spend period each fungible asset was last spent in and the amount spent
+	/// so far during it.
+	#[pallet::storage]
+	#[pallet::getter(fn asset_spent)]
+	pub type AssetSpent<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, AssetIdOf<T, I>, (T::BlockNumber, AssetBalanceOf<T, I>), ValueQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config<I>, I: 'static = ()> {
 		pub phantom: sp_std::marker::PhantomData<(T, I)>,
@@ -175,6 +835,351 @@ impl<T: Config<I>, I: 'static> GenesisConfig<T, I> {
 	}
 }
 
+/// How many blocks after the requested one we look at when the agenda is full.
+const STREAM_AGENDA_LOOKAHEAD: u32 = 10;
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// Index of the current spend period, used to account for the spend limit and the budget
+	/// categories allowances.
+	pub fn current_period() -> T::BlockNumber {
+		frame_system::Pallet::<T>::block_number() / T::SpendPeriod::get().max(One::one())
+	}
+
+	/// Spent and remaining amounts of every budget category during the spend period `period`.
+	pub fn budget_report(period: T::BlockNumber) -> Vec<CategoryReportOf<T, I>> {
+		let mut report: Vec<_> = Categories::<T, I>::iter()
+			.map(|(id, category)| {
+				let spent = CategorySpent::<T, I>::get(id, period);
+				CategoryReport {
+					id,
+					name: category.name.into_inner(),
+					allowance: category.allowance,
+					spent,
+					remaining: category.allowance.saturating_sub(spent),
+				}
+			})
+			.collect();
+		report.sort_by_key(|category| category.id);
+		report
+	}
+
+	/// Funds that flowed into the reserve during the spend period `period`, per source.
+	pub fn inflow_report(period: T::BlockNumber) -> Vec<(InflowSource, BalanceOf<T, I>)> {
+		let mut report: Vec<_> = Inflows::<T, I>::iter_prefix(period).collect();
+		report.sort_by_key(|(source, _)| *source);
+		report
+	}
+
+	/// Balance of the fungible `asset` held by the reserve.
+	pub fn asset_balance(asset: AssetIdOf<T, I>) -> AssetBalanceOf<T, I> {
+		<T::Assets as fungibles::Inspect<T::AccountId>>::balance(asset, &Self::account_id())
+	}
+
+	/// Upper bound of the agenda accesses to reschedule a stream, on top of the benchmarked
+	/// weights since how many agendas are visited depends on how full the next blocks are.
+	fn schedule_stream_weight() -> Weight {
+		T::DbWeight::get().reads_writes(STREAM_AGENDA_LOOKAHEAD.into(), STREAM_AGENDA_LOOKAHEAD.into())
+	}
+
+	/// Put the stream `id` on the agenda of the first block from `when` on with some room left.
+	fn schedule_stream(id: StreamId, when: T::BlockNumber) -> Result<T::BlockNumber, DispatchError> {
+		let mut when = when;
+		for _ in 0..STREAM_AGENDA_LOOKAHEAD {
+			if StreamAgenda::<T, I>::mutate(when, |agenda| agenda.try_push(id).is_ok()) {
+				return Ok(when);
+			}
+			when = when.saturating_add(One::one());
+		}
+		Err(Error::<T, I>::StreamAgendaFull.into())
+	}
+
+	/// Pay the stream `id` if it still exists, then schedule its next payment or a retry.
+	fn pay_stream(id: StreamId, now: T::BlockNumber) {
+		let mut stream = match Streams::<T, I>::get(id) {
+			Some(stream) => stream,
+			None => return,
+		};
+
+		let next_attempt = match with_storage_layer(|| Self::do_spend(&stream.beneficiary, stream.amount)) {
+			Ok(()) => {
+				Self::deposit_event(Event::StreamPaid(id, stream.beneficiary.clone(), stream.amount));
+				stream.advance();
+				if stream.is_over() {
+					Streams::<T, I>::remove(id);
+					Self::deposit_event(Event::StreamCompleted(id));
+					return;
+				}
+				// A late retry should not shift the following payments
+				stream.next_payment.max(now.saturating_add(One::one()))
+			}
+			Err(error) => {
+				let retry_at = now.saturating_add(T::StreamRetryDelay::get().max(One::one()));
+				Self::deposit_event(Event::StreamPaymentFailed(id, error, retry_at));
+				retry_at
+			}
+		};
+
+		if Self::schedule_stream(id, next_attempt).is_ok() {
+			Streams::<T, I>::insert(id, stream);
+		} else {
+			// Nowhere to put the stream, drop it rather than silently never paying again
+			Streams::<T, I>::remove(id);
+			Self::deposit_event(Event::StreamCancelled(id));
+		}
+	}
+
+	/// Build the message sending `amount` to `beneficiary` on `dest`.
+	fn build_xcm_spend(
+		dest: &MultiLocation,
+		beneficiary: &MultiLocation,
+		amount: BalanceOf<T, I>,
+	) -> Result<Xcm<<T as Config<I>>::RuntimeCall>, DispatchError> {
+		let assets = Self::native_asset(amount);
+		let remote_fees = assets
+			.clone()
+			.reanchored(dest, &T::LocationInverter::ancestry())
+			.map_err(|()| Error::<T, I>::BadXcmVersion)?;
+
+		Ok(Xcm(vec![
+			Instruction::WithdrawAsset(assets.into()),
+			Instruction::DepositReserveAsset {
+				assets: MultiAssetFilter::Wild(WildMultiAsset::All),
+				max_assets: 1,
+				dest: dest.clone(),
+				xcm: Xcm(vec![
+					Instruction::BuyExecution {
+						fees: remote_fees,
+						weight_limit: WeightLimit::Unlimited,
+					},
+					Instruction::DepositAsset {
+						assets: MultiAssetFilter::Wild(WildMultiAsset::All),
+						max_assets: 1,
+						beneficiary: beneficiary.clone(),
+					},
+				]),
+			},
+		]))
+	}
+
+	/// Weight of the local execution of the message `spend_xcm` would send, zero if it can't be
+	/// built.
+	fn xcm_spend_weight(
+		dest: &VersionedMultiLocation,
+		beneficiary: &VersionedMultiLocation,
+		amount: BalanceOf<T, I>,
+	) -> Weight {
+		let weigh = || -> Option<u64> {
+			let dest = MultiLocation::try_from(dest.clone()).ok()?;
+			let beneficiary = MultiLocation::try_from(beneficiary.clone()).ok()?;
+			let mut message = Self::build_xcm_spend(&dest, &beneficiary, amount).ok()?;
+			T::Weigher::weight(&mut message).ok()
+		};
+		Weight::from_ref_time(weigh().unwrap_or_default())
+	}
+
+	/// `amount` of `Currency` as an XCM asset.
+	fn native_asset(amount: BalanceOf<T, I>) -> MultiAsset {
+		MultiAsset {
+			id: Concrete(T::NativeAssetLocation::get()),
+			fun: Fungible(amount.saturated_into()),
+		}
+	}
+
+	/// Price `weight` with the `Trader` without actually charging anything.
+	fn estimate_xcm_fee(weight: u64) -> Result<BalanceOf<T, I>, DispatchError> {
+		let location = T::FeeAssetLocation::get();
+		let available = u128::MAX;
+
+		let mut trader = T::Trader::new();
+		let unused = trader
+			.buy_weight(
+				weight,
+				MultiAsset {
+					id: Concrete(location.clone()),
+					fun: Fungible(available),
+				}
+				.into(),
+			)
+			.map_err(|_| Error::<T, I>::XcmFeeEstimationFailed)?;
+		// Hand back everything so that the trader has nothing to account for once dropped
+		let _ = trader.refund_weight(weight);
+
+		let left = unused.fungible.get(&Concrete(location)).copied().unwrap_or_default();
+		Ok(available.saturating_sub(left).saturated_into())
+	}
+
+	/// Withdraw `fee` from the reserve and buy `weight` with it from the `Trader`, which takes
+	/// care of the fee once dropped.
+	fn pay_xcm_fee(weight: u64, fee: BalanceOf<T, I>) -> DispatchResult {
+		if fee.is_zero() {
+			return Ok(());
+		}
+
+		let withdrawn = T::Currency::withdraw(
+			&Self::account_id(),
+			fee,
+			WithdrawReasons::FEE,
+			ExistenceRequirement::KeepAlive,
+		)?;
+		let mut trader = T::Trader::new();
+		trader
+			.buy_weight(
+				weight,
+				MultiAsset {
+					id: Concrete(T::FeeAssetLocation::get()),
+					fun: Fungible(withdrawn.peek().saturated_into()),
+				}
+				.into(),
+			)
+			.map_err(|_| Error::<T, I>::XcmFeeEstimationFailed)?;
+
+		Ok(())
+	}
+
+	/// Dispatch `call` as coming from the reserve account, accounting what left the reserve under
+	/// `category` if any.
+	fn do_apply_as(call: <T as Config<I>>::RuntimeCall, category: Option<CategoryId>) -> DispatchResult {
+		if let Some(category) = category {
+			ensure!(
+				Categories::<T, I>::contains_key(category),
+				Error::<T, I>::UnknownCategory
+			);
+		}
+
+		ensure!(T::CallFilter::contains(&call), Error::<T, I>::CallFiltered);
+		ensure!(!T::EmergencyShutdown::is_shutdown(), Error::<T, I>::UnderShutdown);
+
+		let reserve = Self::account_id();
+		let balance_before = T::Currency::free_balance(&reserve);
+
+		let mut reserve_origin: T::RuntimeOrigin = frame_system::RawOrigin::Signed(reserve.clone()).into();
+		reserve_origin.add_filter(|call: &<T as frame_system::Config>::RuntimeCall| {
+			T::CallFilter::contains(<T as Config<I>>::RuntimeCall::from_ref(call))
+		});
+		let call_hash = T::Hashing::hash_of(&call);
+		let info = call.get_dispatch_info();
+		let res = call.dispatch(reserve_origin);
+		let actual_weight = extract_actual_weight(&res, &info);
+
+		// Whatever left the reserve during the call counts against the spend limit, if the
+		// limit is exceeded the whole call is reverted.
+		let balance_after = T::Currency::free_balance(&reserve);
+		let spent = balance_before.saturating_sub(balance_after);
+		Self::note_spending(spent)?;
+		if let Some(category) = category {
+			Self::note_category_spending(category, spent)?;
+		}
+
+		Self::deposit_event(Event::ReserveOp(
+			call_hash,
+			res.map(|_| ()).map_err(|e| e.error),
+			actual_weight,
+		));
+
+		Ok(())
+	}
+
+	/// Transfer `amount` out of the reserve, accounting for it in the spend limit.
+	fn do_spend(to: &T::AccountId, amount: BalanceOf<T, I>) -> DispatchResult {
+		Self::note_spending(amount)?;
+		T::Currency::transfer(&Self::account_id(), to, amount, ExistenceRequirement::KeepAlive)
+	}
+
+	/// Add `amount` to what was spent during the current period, erroring if this goes over the
+	/// spend limit.
+	fn note_spending(amount: BalanceOf<T, I>) -> DispatchResult {
+		ensure!(!T::EmergencyShutdown::is_shutdown(), Error::<T, I>::UnderShutdown);
+		if amount.is_zero() {
+			return Ok(());
+		}
+
+		let period = Self::current_period();
+		Spent::<T, I>::try_mutate(|(current_period, spent)| {
+			if *current_period != period {
+				*current_period = period;
+				*spent = Zero::zero();
+			}
+
+			let new_spent = spent.saturating_add(amount);
+			if let Some(limit) = Self::spend_limit() {
+				ensure!(new_spent <= limit, Error::<T, I>::SpendLimitExceeded);
+			}
+			*spent = new_spent;
+
+			Ok(())
+		})
+	}
+
+	/// Add `amount` of `asset` to what was spent of it during the current period, erroring if
+	/// this goes over its spend limit.
+	fn note_asset_spending(asset: AssetIdOf<T, I>, amount: AssetBalanceOf<T, I>) -> DispatchResult {
+		ensure!(!T::EmergencyShutdown::is_shutdown(), Error::<T, I>::UnderShutdown);
+		if amount.is_zero() {
+			return Ok(());
+		}
+
+		let period = Self::current_period();
+		AssetSpent::<T, I>::try_mutate(asset, |(current_period, spent)| {
+			if *current_period != period {
+				*current_period = period;
+				*spent = Zero::zero();
+			}
+
+			let new_spent = spent.saturating_add(amount);
+			if let Some(limit) = Self::asset_spend_limit(asset) {
+				ensure!(new_spent <= limit, Error::<T, I>::SpendLimitExceeded);
+			}
+			*spent = new_spent;
+
+			Ok(())
+		})
+	}
+
+	/// Account `amount` under `category` for the current period, erroring if this goes over its
+	/// allowance.
+	fn note_category_spending(category: CategoryId, amount: BalanceOf<T, I>) -> DispatchResult {
+		let allowance = Self::categories(category)
+			.ok_or(Error::<T, I>::UnknownCategory)?
+			.allowance;
+		if amount.is_zero() {
+			return Ok(());
+		}
+
+		CategorySpent::<T, I>::try_mutate(category, Self::current_period(), |spent| {
+			let new_spent = spent.saturating_add(amount);
+			ensure!(new_spent <= allowance, Error::<T, I>::CategoryAllowanceExceeded);
+			*spent = new_spent;
+			Ok::<_, DispatchError>(())
+		})?;
+
+		Self::deposit_event(Event::CategorySpending(category, amount));
+
+		Ok(())
+	}
+
+	/// Credit the reserve with `amount` coming from `source`.
+	fn deposit_inflow(source: InflowSource, amount: NegativeImbalanceOf<T, I>) {
+		let numeric_amount = amount.peek();
+
+		// Must resolve into existing but better to be safe.
+		T::Currency::resolve_creating(&Self::account_id(), amount);
+		Self::note_inflow(source, numeric_amount);
+
+		Self::deposit_event(Event::Deposit(source, numeric_amount));
+	}
+
+	/// Add `amount` to what came from `source` during the current period.
+	fn note_inflow(source: InflowSource, amount: BalanceOf<T, I>) {
+		if amount.is_zero() {
+			return;
+		}
+
+		Inflows::<T, I>::mutate(Self::current_period(), source, |total| {
+			*total = total.saturating_add(amount)
+		});
+	}
+}
+
 impl<T: Config<I>, I: 'static> WithAccountId<T::AccountId> for Pallet<T, I> {
 	fn account_id() -> T::AccountId {
 		T::PalletId::get().into_account_truncating()
@@ -183,11 +1188,13 @@ impl<T: Config<I>, I: 'static> WithAccountId<T::AccountId> for Pallet<T, I> {
 
 impl<T: Config<I>, I: 'static> OnUnbalanced<NegativeImbalanceOf<T, I>> for Pallet<T, I> {
 	fn on_nonzero_unbalanced(amount: NegativeImbalanceOf<T, I>) {
-		let numeric_amount = amount.peek();
-
-		// Must resolve into existing but better to be safe.
-		T::Currency::resolve_creating(&Self::account_id(), amount);
+		Self::deposit_inflow(InflowSource::Other, amount);
+	}
+}
 
-		Self::deposit_event(Event::Deposit(numeric_amount));
+impl<T: Config<I>, I: 'static> ProtocolFeeHandler<T::AccountId, BalanceOf<T, I>> for Pallet<T, I> {
+	fn on_protocol_fee(amount: BalanceOf<T, I>) {
+		Self::note_inflow(InflowSource::ProtocolFees, amount);
+		Self::deposit_event(Event::Deposit(InflowSource::ProtocolFees, amount));
 	}
 }
@@ -20,15 +20,23 @@
 
 use super::*;
 use crate::{self as pallet_reserve};
-use frame_support::{assert_noop, assert_ok, ord_parameter_types, parameter_types, traits::Currency};
-use frame_system::{EnsureSignedBy, RawOrigin};
+use frame_support::{
+	assert_err, assert_noop, assert_ok, ord_parameter_types, parameter_types,
+	traits::{
+		fungibles, AsEnsureOriginWithArg, ConstU32, ConstU64, Contains, Currency, ExistenceRequirement, Hooks,
+		OnUnbalanced, WithdrawReasons,
+	},
+};
+use frame_system::{EnsureRoot, EnsureSigned, EnsureSignedBy, RawOrigin};
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
-	traits::{BlakeTwo256, IdentityLookup},
+	traits::{BlakeTwo256, Convert, IdentityLookup},
 	DispatchError::BadOrigin,
 };
-use sp_std::prelude::Box;
+use sp_std::{cell::RefCell, prelude::Box};
+use xcm::latest::prelude::*;
+use xcm_builder::{FixedRateOfFungible, FixedWeightBounds, LocationInverter};
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -41,6 +49,7 @@ frame_support::construct_runtime!(
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
 		Balances: pallet_balances::{Pallet, Call, Config<T>, Storage, Event<T>},
+		Assets: pallet_assets::{Pallet, Call, Storage, Event<T>},
 		TestModule: pallet_reserve::{Pallet, Call, Storage, Event<T>},
 	}
 );
@@ -89,8 +98,110 @@ impl pallet_balances::Config for Test {
 	type WeightInfo = ();
 }
 
+impl pallet_assets::Config for Test {
+	type RuntimeEvent = ();
+	type Balance = u64;
+	type AssetId = u32;
+	type AssetIdParameter = codec::Compact<u32>;
+	type Currency = Balances;
+	type CreateOrigin = AsEnsureOriginWithArg<EnsureSigned<u64>>;
+	type ForceOrigin = EnsureRoot<u64>;
+	type AssetDeposit = ConstU64<0>;
+	type AssetAccountDeposit = ConstU64<0>;
+	type MetadataDepositBase = ConstU64<0>;
+	type MetadataDepositPerByte = ConstU64<0>;
+	type ApprovalDeposit = ConstU64<0>;
+	type StringLimit = ConstU32<50>;
+	type Freezer = ();
+	type Extra = ();
+	type RemoveItemsLimit = ConstU32<5>;
+	type WeightInfo = ();
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
+}
+
+thread_local! {
+	static EXECUTED_XCM: RefCell<Vec<(MultiLocation, Xcm<RuntimeCall>, u64)>> = RefCell::new(Vec::new());
+}
+
+fn executed_xcm() -> Vec<(MultiLocation, Xcm<RuntimeCall>, u64)> {
+	EXECUTED_XCM.with(|q| q.borrow().clone())
+}
+
+/// Only withdraws the assets of the first instruction from the origin account and records the
+/// message.
+pub struct MockXcmExecutor;
+impl ExecuteXcm<RuntimeCall> for MockXcmExecutor {
+	fn execute_xcm_in_credit(
+		origin: impl Into<MultiLocation>,
+		message: Xcm<RuntimeCall>,
+		weight_limit: u64,
+		_weight_credit: u64,
+	) -> Outcome {
+		let origin = origin.into();
+		let who = match &origin {
+			MultiLocation {
+				parents: 0,
+				interior: X1(GeneralIndex(index)),
+			} => *index as u64,
+			_ => return Outcome::Error(XcmError::BadOrigin),
+		};
+		if let Some(WithdrawAsset(assets)) = message.0.first() {
+			for asset in assets.inner() {
+				if let Fungible(amount) = asset.fun {
+					if TestCurrency::withdraw(
+						&who,
+						amount as u64,
+						WithdrawReasons::TRANSFER,
+						ExistenceRequirement::KeepAlive,
+					)
+					.is_err()
+					{
+						return Outcome::Error(XcmError::NotWithdrawable);
+					}
+				}
+			}
+		}
+		EXECUTED_XCM.with(|q| q.borrow_mut().push((origin, message, weight_limit)));
+		Outcome::Complete(weight_limit)
+	}
+}
+
+pub struct AccountIdToMultiLocation;
+impl Convert<u64, MultiLocation> for AccountIdToMultiLocation {
+	fn convert(account: u64) -> MultiLocation {
+		MultiLocation::new(0, X1(GeneralIndex(account.into())))
+	}
+}
+
+parameter_types! {
+	pub NativeLocation: MultiLocation = MultiLocation::here();
+	// One unit of fee per unit of weight
+	pub NativePerSecond: (xcm::latest::AssetId, u128) = (Concrete(MultiLocation::here()), 1_000_000_000_000);
+	pub Ancestry: MultiLocation = Parachain(2000).into();
+}
+
+parameter_types! {
+	pub static Shutdown: bool = false;
+}
+pub struct MockShutdown;
+impl IsShutdown for MockShutdown {
+	fn is_shutdown() -> bool {
+		Shutdown::get()
+	}
+}
+
+/// Lets the reserve dispatch anything but calls to the assets pallet.
+pub struct NotAssets;
+impl Contains<RuntimeCall> for NotAssets {
+	fn contains(call: &RuntimeCall) -> bool {
+		!matches!(call, RuntimeCall::Assets(_))
+	}
+}
+
 ord_parameter_types! {
 	pub const Admin: u64 = 1;
+	pub const Vetoer: u64 = 2;
 }
 parameter_types! {
 	pub const ReserveModuleId: PalletId = PalletId(*b"py/resrv");
@@ -98,9 +209,24 @@ parameter_types! {
 impl Config for Test {
 	type RuntimeEvent = ();
 	type Currency = pallet_balances::Pallet<Self>;
+	type Assets = Assets;
 	type ExternalOrigin = EnsureSignedBy<Admin, u64>;
 	type RuntimeCall = RuntimeCall;
+	type CallFilter = NotAssets;
 	type PalletId = ReserveModuleId;
+	type SpendPeriod = ConstU64<10>;
+	type SpendDelay = ConstU64<5>;
+	type VetoOrigin = EnsureSignedBy<Vetoer, u64>;
+	type MaxStreamsPerBlock = ConstU32<2>;
+	type StreamRetryDelay = ConstU64<3>;
+	type XcmExecutor = MockXcmExecutor;
+	type Weigher = FixedWeightBounds<ConstU64<10>, RuntimeCall, ConstU32<100>>;
+	type Trader = FixedRateOfFungible<NativePerSecond, ()>;
+	type NativeAssetLocation = NativeLocation;
+	type FeeAssetLocation = NativeLocation;
+	type LocationInverter = LocationInverter<Ancestry>;
+	type AccountIdToMultiLocation = AccountIdToMultiLocation;
+	type EmergencyShutdown = MockShutdown;
 	type WeightInfo = ();
 }
 type TestCurrency = <Test as Config>::Currency;
@@ -134,6 +260,32 @@ fn spend_funds_to_target() {
 	})
 }
 
+#[test]
+fn spend_is_frozen_under_shutdown() {
+	new_test_ext().execute_with(|| {
+		TestCurrency::make_free_balance_be(&TestModule::account_id(), 100);
+		Shutdown::set(true);
+
+		assert_noop!(
+			TestModule::spend(RuntimeOrigin::signed(Admin::get()), 3, 100),
+			Error::<Test>::UnderShutdown
+		);
+		assert_noop!(
+			TestModule::apply_as(
+				RuntimeOrigin::signed(Admin::get()),
+				Box::new(RuntimeCall::Balances(pallet_balances::Call::<Test>::transfer {
+					dest: 3,
+					value: 100
+				}))
+			),
+			Error::<Test>::UnderShutdown
+		);
+
+		Shutdown::set(false);
+		assert_ok!(TestModule::spend(RuntimeOrigin::signed(Admin::get()), 3, 100));
+	})
+}
+
 #[test]
 fn tip() {
 	new_test_ext().execute_with(|| {
@@ -145,6 +297,40 @@ fn tip() {
 	})
 }
 
+#[test]
+fn inflows_are_accounted_per_source() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		TestCurrency::make_free_balance_be(&999, 100);
+
+		InflowFrom::<Test, (), FeesSource>::on_unbalanced(TestCurrency::issue(10));
+		InflowFrom::<Test, (), FeesSource>::on_unbalanced(TestCurrency::issue(5));
+		InflowFrom::<Test, (), DustSource>::on_unbalanced(TestCurrency::issue(1));
+		TestModule::on_unbalanced(TestCurrency::issue(2));
+		TestModule::on_protocol_fee(7);
+		assert_ok!(TestModule::tip(RuntimeOrigin::signed(999), 50));
+
+		assert_eq!(Balances::free_balance(TestModule::account_id()), 68);
+		assert_eq!(TestModule::inflows(0, InflowSource::Fees), 15);
+		assert_eq!(
+			TestModule::inflow_report(0),
+			vec![
+				(InflowSource::Fees, 15),
+				(InflowSource::Dust, 1),
+				(InflowSource::ProtocolFees, 7),
+				(InflowSource::DirectTip, 50),
+				(InflowSource::Other, 2),
+			]
+		);
+
+		// Totals start over with each period
+		System::set_block_number(10);
+		InflowFrom::<Test, (), TipsSource>::on_unbalanced(TestCurrency::issue(3));
+		assert_eq!(TestModule::inflow_report(1), vec![(InflowSource::Tips, 3)]);
+		assert_eq!(TestModule::inflows(0, InflowSource::Fees), 15);
+	})
+}
+
 fn make_call(value: u8) -> Box<RuntimeCall> {
 	Box::new(RuntimeCall::System(frame_system::Call::<Test>::remark {
 		remark: vec![value],
@@ -165,6 +351,20 @@ fn apply_as_works() {
 	})
 }
 
+#[test]
+fn apply_as_rejects_filtered_calls() {
+	new_test_ext().execute_with(|| {
+		let call = Box::new(RuntimeCall::Assets(pallet_assets::Call::<Test>::freeze_asset {
+			id: ASSET.into(),
+		}));
+
+		assert_noop!(
+			TestModule::apply_as(RuntimeOrigin::signed(Admin::get()), call),
+			Error::<Test>::CallFiltered
+		);
+	})
+}
+
 #[test]
 fn try_root_if_not_admin() {
 	new_test_ext().execute_with(|| {
@@ -174,3 +374,773 @@ fn try_root_if_not_admin() {
 		assert_ok!(TestModule::apply_as(RawOrigin::Root.into(), make_call(1)));
 	})
 }
+
+#[test]
+fn spend_fails_if_transfer_fails() {
+	new_test_ext().execute_with(|| {
+		TestCurrency::make_free_balance_be(&TestModule::account_id(), 100);
+
+		assert_noop!(
+			TestModule::spend(RuntimeOrigin::signed(Admin::get()), 3, 101),
+			pallet_balances::Error::<Test>::InsufficientBalance
+		);
+		assert_eq!(Balances::free_balance(TestModule::account_id()), 100);
+	})
+}
+
+#[test]
+fn set_spend_limit_requires_root() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TestModule::set_spend_limit(RuntimeOrigin::signed(Admin::get()), Some(10)),
+			BadOrigin
+		);
+		assert_ok!(TestModule::set_spend_limit(RawOrigin::Root.into(), Some(10)));
+		assert_eq!(TestModule::spend_limit(), Some(10));
+	})
+}
+
+#[test]
+fn spend_limit_is_enforced_per_period() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		TestCurrency::make_free_balance_be(&TestModule::account_id(), 100);
+		assert_ok!(TestModule::set_spend_limit(RawOrigin::Root.into(), Some(30)));
+
+		assert_ok!(TestModule::spend(RuntimeOrigin::signed(Admin::get()), 3, 20));
+		assert_noop!(
+			TestModule::spend(RuntimeOrigin::signed(Admin::get()), 3, 11),
+			Error::<Test>::SpendLimitExceeded
+		);
+		assert_ok!(TestModule::spend(RuntimeOrigin::signed(Admin::get()), 3, 10));
+		assert_eq!(TestModule::spent(), (0, 30));
+
+		// A new period starts at block 10
+		System::set_block_number(10);
+		assert_ok!(TestModule::spend(RuntimeOrigin::signed(Admin::get()), 3, 30));
+		assert_eq!(TestModule::spent(), (1, 30));
+		assert_eq!(Balances::free_balance(3), 60);
+	})
+}
+
+#[test]
+fn spend_limit_applies_to_apply_as() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		TestCurrency::make_free_balance_be(&TestModule::account_id(), 100);
+		assert_ok!(TestModule::set_spend_limit(RawOrigin::Root.into(), Some(30)));
+
+		let transfer = |value| {
+			Box::new(RuntimeCall::Balances(pallet_balances::Call::<Test>::transfer {
+				dest: 3,
+				value,
+			}))
+		};
+
+		assert_noop!(
+			TestModule::apply_as(RuntimeOrigin::signed(Admin::get()), transfer(31)),
+			Error::<Test>::SpendLimitExceeded
+		);
+		assert_ok!(TestModule::apply_as(RuntimeOrigin::signed(Admin::get()), transfer(30)));
+		assert_eq!(Balances::free_balance(3), 30);
+		assert_eq!(TestModule::spent(), (0, 30));
+	})
+}
+
+#[test]
+fn create_category_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TestModule::create_category(RuntimeOrigin::signed(0), b"grants".to_vec(), 50),
+			BadOrigin
+		);
+		assert_noop!(
+			TestModule::create_category(RuntimeOrigin::signed(Admin::get()), vec![0; 33], 50),
+			Error::<Test>::CategoryNameTooLong
+		);
+
+		assert_ok!(TestModule::create_category(
+			RuntimeOrigin::signed(Admin::get()),
+			b"grants".to_vec(),
+			50
+		));
+		assert_ok!(TestModule::create_category(
+			RawOrigin::Root.into(),
+			b"audits".to_vec(),
+			20
+		));
+
+		assert_eq!(TestModule::categories(0).unwrap().allowance, 50);
+		assert_eq!(TestModule::categories(1).unwrap().name.into_inner(), b"audits".to_vec());
+	})
+}
+
+#[test]
+fn spend_is_accounted_under_its_category() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		TestCurrency::make_free_balance_be(&TestModule::account_id(), 100);
+		assert_ok!(TestModule::create_category(
+			RawOrigin::Root.into(),
+			b"grants".to_vec(),
+			50
+		));
+
+		assert_noop!(
+			TestModule::spend_in_category(RuntimeOrigin::signed(Admin::get()), 3, 10, 1),
+			Error::<Test>::UnknownCategory
+		);
+
+		assert_ok!(TestModule::spend_in_category(
+			RuntimeOrigin::signed(Admin::get()),
+			3,
+			30,
+			0
+		));
+		assert_noop!(
+			TestModule::spend_in_category(RuntimeOrigin::signed(Admin::get()), 3, 21, 0),
+			Error::<Test>::CategoryAllowanceExceeded
+		);
+		// Uncategorized spendings do not use the allowance
+		assert_ok!(TestModule::spend(RuntimeOrigin::signed(Admin::get()), 3, 21));
+		assert_ok!(TestModule::spend_in_category(
+			RuntimeOrigin::signed(Admin::get()),
+			3,
+			20,
+			0
+		));
+		assert_eq!(TestModule::category_spent(0, 0), 50);
+
+		// New period, new allowance
+		System::set_block_number(10);
+		assert_ok!(TestModule::spend_in_category(
+			RuntimeOrigin::signed(Admin::get()),
+			3,
+			15,
+			0
+		));
+		assert_eq!(TestModule::category_spent(0, 0), 50);
+		assert_eq!(TestModule::category_spent(0, 1), 15);
+	})
+}
+
+#[test]
+fn apply_as_is_accounted_under_its_category() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		TestCurrency::make_free_balance_be(&TestModule::account_id(), 100);
+		assert_ok!(TestModule::create_category(
+			RawOrigin::Root.into(),
+			b"grants".to_vec(),
+			30
+		));
+
+		let transfer = |value| {
+			Box::new(RuntimeCall::Balances(pallet_balances::Call::<Test>::transfer {
+				dest: 3,
+				value,
+			}))
+		};
+
+		assert_noop!(
+			TestModule::apply_as_in_category(RuntimeOrigin::signed(Admin::get()), transfer(10), 1),
+			Error::<Test>::UnknownCategory
+		);
+		assert_noop!(
+			TestModule::apply_as_in_category(RuntimeOrigin::signed(Admin::get()), transfer(31), 0),
+			Error::<Test>::CategoryAllowanceExceeded
+		);
+		assert_ok!(TestModule::apply_as_in_category(
+			RuntimeOrigin::signed(Admin::get()),
+			transfer(30),
+			0
+		));
+		assert_eq!(TestModule::category_spent(0, 0), 30);
+	})
+}
+
+#[test]
+fn set_category_allowance_and_remove_category_work() {
+	new_test_ext().execute_with(|| {
+		TestCurrency::make_free_balance_be(&TestModule::account_id(), 100);
+		assert_ok!(TestModule::create_category(
+			RawOrigin::Root.into(),
+			b"grants".to_vec(),
+			10
+		));
+
+		assert_noop!(
+			TestModule::set_category_allowance(RuntimeOrigin::signed(0), 0, 20),
+			BadOrigin
+		);
+		assert_noop!(
+			TestModule::set_category_allowance(RawOrigin::Root.into(), 1, 20),
+			Error::<Test>::UnknownCategory
+		);
+		assert_ok!(TestModule::set_category_allowance(
+			RuntimeOrigin::signed(Admin::get()),
+			0,
+			20
+		));
+		assert_ok!(TestModule::spend_in_category(
+			RuntimeOrigin::signed(Admin::get()),
+			3,
+			20,
+			0
+		));
+
+		assert_noop!(TestModule::remove_category(RuntimeOrigin::signed(0), 0), BadOrigin);
+		assert_ok!(TestModule::remove_category(RuntimeOrigin::signed(Admin::get()), 0));
+		assert_noop!(
+			TestModule::remove_category(RawOrigin::Root.into(), 0),
+			Error::<Test>::UnknownCategory
+		);
+		assert_noop!(
+			TestModule::spend_in_category(RuntimeOrigin::signed(Admin::get()), 3, 1, 0),
+			Error::<Test>::UnknownCategory
+		);
+		// The record of what was spent is kept
+		assert_eq!(TestModule::category_spent(0, 0), 20);
+	})
+}
+
+#[test]
+fn budget_report_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		TestCurrency::make_free_balance_be(&TestModule::account_id(), 100);
+		assert_ok!(TestModule::create_category(
+			RawOrigin::Root.into(),
+			b"grants".to_vec(),
+			50
+		));
+		assert_ok!(TestModule::create_category(
+			RawOrigin::Root.into(),
+			b"audits".to_vec(),
+			20
+		));
+		assert_ok!(TestModule::spend_in_category(
+			RuntimeOrigin::signed(Admin::get()),
+			3,
+			30,
+			0
+		));
+
+		assert_eq!(
+			TestModule::budget_report(0),
+			vec![
+				CategoryReport {
+					id: 0,
+					name: b"grants".to_vec(),
+					allowance: 50,
+					spent: 30,
+					remaining: 20,
+				},
+				CategoryReport {
+					id: 1,
+					name: b"audits".to_vec(),
+					allowance: 20,
+					spent: 0,
+					remaining: 20,
+				},
+			]
+		);
+		assert_eq!(TestModule::budget_report(1)[0].spent, 0);
+	})
+}
+
+#[test]
+fn spend_request_can_be_executed_after_delay() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		TestCurrency::make_free_balance_be(&TestModule::account_id(), 100);
+
+		assert_noop!(TestModule::request_spend(RuntimeOrigin::signed(0), 3, 50), BadOrigin);
+		assert_ok!(TestModule::request_spend(RuntimeOrigin::signed(Admin::get()), 3, 50));
+		assert_eq!(
+			TestModule::spend_requests(0),
+			Some(SpendRequest {
+				to: 3,
+				amount: 50,
+				execute_at: 6
+			})
+		);
+
+		assert_noop!(
+			TestModule::execute_spend(RuntimeOrigin::signed(999), 0),
+			Error::<Test>::SpendRequestNotReady
+		);
+
+		System::set_block_number(6);
+		assert_ok!(TestModule::execute_spend(RuntimeOrigin::signed(999), 0));
+		assert_eq!(Balances::free_balance(3), 50);
+		assert_eq!(TestModule::spend_requests(0), None);
+		assert_noop!(
+			TestModule::execute_spend(RuntimeOrigin::signed(999), 0),
+			Error::<Test>::UnknownSpendRequest
+		);
+	})
+}
+
+#[test]
+fn spend_request_can_be_vetoed() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		TestCurrency::make_free_balance_be(&TestModule::account_id(), 100);
+
+		assert_ok!(TestModule::request_spend(RuntimeOrigin::signed(Admin::get()), 3, 50));
+		assert_noop!(
+			TestModule::veto_spend(RuntimeOrigin::signed(Admin::get()), 0),
+			BadOrigin
+		);
+		assert_ok!(TestModule::veto_spend(RuntimeOrigin::signed(Vetoer::get()), 0));
+		assert_eq!(TestModule::spend_requests(0), None);
+
+		System::set_block_number(6);
+		assert_noop!(
+			TestModule::execute_spend(RuntimeOrigin::signed(999), 0),
+			Error::<Test>::UnknownSpendRequest
+		);
+		assert_eq!(Balances::free_balance(TestModule::account_id()), 100);
+	})
+}
+
+fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		System::set_block_number(System::block_number() + 1);
+		TestModule::on_initialize(System::block_number());
+	}
+}
+
+#[test]
+fn create_stream_checks_parameters() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_noop!(
+			TestModule::create_stream(RuntimeOrigin::signed(0), 3, 10, 5, 1, None),
+			BadOrigin
+		);
+		assert_noop!(
+			TestModule::create_stream(RuntimeOrigin::signed(Admin::get()), 3, 0, 5, 1, None),
+			Error::<Test>::ZeroStreamAmount
+		);
+		assert_noop!(
+			TestModule::create_stream(RuntimeOrigin::signed(Admin::get()), 3, 10, 0, 1, None),
+			Error::<Test>::ZeroStreamPeriod
+		);
+		assert_noop!(
+			TestModule::create_stream(
+				RuntimeOrigin::signed(Admin::get()),
+				3,
+				10,
+				5,
+				1,
+				Some(StreamLimit::Payments(0))
+			),
+			Error::<Test>::StreamAlreadyOver
+		);
+	})
+}
+
+#[test]
+fn stream_pays_every_period_until_limit() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		TestCurrency::make_free_balance_be(&TestModule::account_id(), 100);
+
+		assert_ok!(TestModule::create_stream(
+			RuntimeOrigin::signed(Admin::get()),
+			3,
+			10,
+			5,
+			2,
+			Some(StreamLimit::Payments(3))
+		));
+		assert_eq!(TestModule::stream_agenda(2).to_vec(), vec![0]);
+
+		run_to_block(2);
+		assert_eq!(Balances::free_balance(3), 10);
+		run_to_block(6);
+		assert_eq!(Balances::free_balance(3), 10);
+		run_to_block(7);
+		assert_eq!(Balances::free_balance(3), 20);
+		assert!(TestModule::streams(0).is_some());
+		run_to_block(12);
+		assert_eq!(Balances::free_balance(3), 30);
+		assert!(TestModule::streams(0).is_none());
+
+		run_to_block(30);
+		assert_eq!(Balances::free_balance(3), 30);
+	})
+}
+
+#[test]
+fn stream_stops_at_end_block() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		TestCurrency::make_free_balance_be(&TestModule::account_id(), 100);
+
+		assert_ok!(TestModule::create_stream(
+			RuntimeOrigin::signed(Admin::get()),
+			3,
+			10,
+			5,
+			2,
+			Some(StreamLimit::Until(12))
+		));
+
+		run_to_block(30);
+		assert_eq!(Balances::free_balance(3), 20);
+		assert!(TestModule::streams(0).is_none());
+	})
+}
+
+#[test]
+fn failed_stream_payment_is_retried() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!(TestModule::create_stream(
+			RuntimeOrigin::signed(Admin::get()),
+			3,
+			10,
+			5,
+			2,
+			None
+		));
+
+		// The reserve is empty, the payment is retried after `StreamRetryDelay`
+		run_to_block(2);
+		assert_eq!(Balances::free_balance(3), 0);
+		assert_eq!(TestModule::stream_agenda(5).to_vec(), vec![0]);
+
+		TestCurrency::make_free_balance_be(&TestModule::account_id(), 100);
+		run_to_block(5);
+		assert_eq!(Balances::free_balance(3), 10);
+
+		// The following payment is still due at block 7
+		run_to_block(7);
+		assert_eq!(Balances::free_balance(3), 20);
+	})
+}
+
+#[test]
+fn cancelled_stream_is_not_paid() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		TestCurrency::make_free_balance_be(&TestModule::account_id(), 100);
+
+		assert_ok!(TestModule::create_stream(
+			RuntimeOrigin::signed(Admin::get()),
+			3,
+			10,
+			5,
+			2,
+			None
+		));
+		assert_noop!(TestModule::cancel_stream(RuntimeOrigin::signed(0), 0), BadOrigin);
+		assert_ok!(TestModule::cancel_stream(RuntimeOrigin::signed(Admin::get()), 0));
+		assert_noop!(
+			TestModule::cancel_stream(RuntimeOrigin::signed(Admin::get()), 0),
+			Error::<Test>::UnknownStream
+		);
+
+		run_to_block(10);
+		assert_eq!(Balances::free_balance(3), 0);
+	})
+}
+
+#[test]
+fn full_agenda_pushes_streams_to_next_block() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		for _ in 0..3 {
+			assert_ok!(TestModule::create_stream(
+				RuntimeOrigin::signed(Admin::get()),
+				3,
+				10,
+				5,
+				2,
+				None
+			));
+		}
+		assert_eq!(TestModule::stream_agenda(2).to_vec(), vec![0, 1]);
+		assert_eq!(TestModule::stream_agenda(3).to_vec(), vec![2]);
+	})
+}
+
+const ASSET: u32 = 42;
+
+fn create_asset() {
+	assert_ok!(<Assets as fungibles::Create<u64>>::create(ASSET, 0, true, 1));
+}
+
+#[test]
+fn spend_asset_error_if_bad_origin() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TestModule::spend_asset(RuntimeOrigin::signed(0), ASSET, 1, 1),
+			BadOrigin
+		);
+	})
+}
+
+#[test]
+fn spend_asset_to_target() {
+	new_test_ext().execute_with(|| {
+		create_asset();
+		assert_ok!(<Assets as fungibles::Mutate<u64>>::mint_into(
+			ASSET,
+			&TestModule::account_id(),
+			100
+		));
+
+		assert_eq!(TestModule::asset_balance(ASSET), 100);
+		assert_ok!(TestModule::spend_asset(
+			RuntimeOrigin::signed(Admin::get()),
+			ASSET,
+			3,
+			40
+		));
+		assert_eq!(TestModule::asset_balance(ASSET), 60);
+		assert_eq!(<Assets as fungibles::Inspect<u64>>::balance(ASSET, &3), 40);
+
+		// Does not touch the native currency
+		assert_eq!(Balances::free_balance(TestModule::account_id()), 0);
+	})
+}
+
+#[test]
+fn spend_asset_fails_if_not_enough_funds() {
+	new_test_ext().execute_with(|| {
+		create_asset();
+		assert_ok!(<Assets as fungibles::Mutate<u64>>::mint_into(
+			ASSET,
+			&TestModule::account_id(),
+			100
+		));
+
+		assert!(TestModule::spend_asset(RuntimeOrigin::signed(Admin::get()), ASSET, 3, 101).is_err());
+		assert_eq!(TestModule::asset_balance(ASSET), 100);
+	})
+}
+
+#[test]
+fn set_asset_spend_limit_requires_root() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TestModule::set_asset_spend_limit(RuntimeOrigin::signed(Admin::get()), ASSET, Some(10)),
+			BadOrigin
+		);
+		assert_ok!(TestModule::set_asset_spend_limit(
+			RawOrigin::Root.into(),
+			ASSET,
+			Some(10)
+		));
+		assert_eq!(TestModule::asset_spend_limit(ASSET), Some(10));
+		assert_eq!(TestModule::asset_spend_limit(ASSET + 1), None);
+	})
+}
+
+#[test]
+fn asset_spend_limit_is_enforced_per_period() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		create_asset();
+		assert_ok!(<Assets as fungibles::Mutate<u64>>::mint_into(
+			ASSET,
+			&TestModule::account_id(),
+			100
+		));
+		assert_ok!(TestModule::set_asset_spend_limit(
+			RawOrigin::Root.into(),
+			ASSET,
+			Some(30)
+		));
+
+		assert_ok!(TestModule::spend_asset(
+			RuntimeOrigin::signed(Admin::get()),
+			ASSET,
+			3,
+			20
+		));
+		assert_noop!(
+			TestModule::spend_asset(RuntimeOrigin::signed(Admin::get()), ASSET, 3, 11),
+			Error::<Test>::SpendLimitExceeded
+		);
+		assert_ok!(TestModule::spend_asset(
+			RuntimeOrigin::signed(Admin::get()),
+			ASSET,
+			3,
+			10
+		));
+		assert_eq!(TestModule::asset_spent(ASSET), (0, 30));
+		// The native spend limit is left untouched
+		assert_eq!(TestModule::spent(), (0, 0));
+
+		// A new period starts at block 10
+		System::set_block_number(10);
+		assert_ok!(TestModule::spend_asset(
+			RuntimeOrigin::signed(Admin::get()),
+			ASSET,
+			3,
+			30
+		));
+		assert_eq!(TestModule::asset_spent(ASSET), (1, 30));
+		assert_eq!(<Assets as fungibles::Inspect<u64>>::balance(ASSET, &3), 60);
+	})
+}
+
+#[test]
+fn tip_asset_works() {
+	new_test_ext().execute_with(|| {
+		create_asset();
+		assert_ok!(<Assets as fungibles::Mutate<u64>>::mint_into(ASSET, &3, 100));
+
+		assert_ok!(TestModule::tip_asset(RuntimeOrigin::signed(3), ASSET, 100));
+		assert_eq!(TestModule::asset_balance(ASSET), 100);
+		assert_eq!(<Assets as fungibles::Inspect<u64>>::balance(ASSET, &3), 0);
+	})
+}
+
+fn xcm_beneficiary() -> MultiLocation {
+	MultiLocation::new(
+		1,
+		X2(
+			Parachain(1000),
+			AccountId32 {
+				network: NetworkId::Any,
+				id: [3u8; 32],
+			},
+		),
+	)
+}
+
+#[test]
+fn spend_xcm_error_if_bad_origin() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TestModule::spend_xcm(
+				RuntimeOrigin::signed(0),
+				Box::new(MultiLocation::parent().into()),
+				Box::new(xcm_beneficiary().into()),
+				100
+			),
+			BadOrigin
+		);
+	})
+}
+
+#[test]
+fn spend_xcm_sends_funds_and_pays_local_fee() {
+	new_test_ext().execute_with(|| {
+		TestCurrency::make_free_balance_be(&TestModule::account_id(), 1000);
+
+		assert_ok!(TestModule::spend_xcm(
+			RuntimeOrigin::signed(Admin::get()),
+			Box::new(MultiLocation::parent().into()),
+			Box::new(xcm_beneficiary().into()),
+			100
+		));
+
+		// 4 instructions of 10 weight each, paid one unit per weight
+		let fee = 40;
+		assert_eq!(Balances::free_balance(TestModule::account_id()), 1000 - 100 - fee);
+		assert_eq!(TestModule::spent().1, 100 + fee);
+
+		let executed = executed_xcm();
+		assert_eq!(executed.len(), 1);
+		let (origin, message, weight) = &executed[0];
+		assert_eq!(origin, &AccountIdToMultiLocation::convert(TestModule::account_id()));
+		assert_eq!(*weight, 40);
+		assert_eq!(
+			message,
+			&Xcm(vec![
+				WithdrawAsset(MultiAsset::from((Here, 100u128)).into()),
+				DepositReserveAsset {
+					assets: Wild(All),
+					max_assets: 1,
+					dest: MultiLocation::parent(),
+					xcm: Xcm(vec![
+						BuyExecution {
+							fees: (MultiLocation::new(0, X1(Parachain(2000))), 100u128).into(),
+							weight_limit: Unlimited,
+						},
+						DepositAsset {
+							assets: Wild(All),
+							max_assets: 1,
+							beneficiary: xcm_beneficiary(),
+						},
+					]),
+				},
+			])
+		);
+	})
+}
+
+#[test]
+fn spend_xcm_weight_accounts_for_the_message() {
+	let call = Call::<Test>::spend_xcm {
+		dest: Box::new(MultiLocation::parent().into()),
+		beneficiary: Box::new(xcm_beneficiary().into()),
+		amount: 100,
+	};
+	assert_eq!(
+		call.get_dispatch_info().weight,
+		<() as WeightInfo>::spend_xcm().saturating_add(Weight::from_ref_time(40))
+	);
+}
+
+#[test]
+fn spend_xcm_fee_counts_towards_spend_limit() {
+	new_test_ext().execute_with(|| {
+		TestCurrency::make_free_balance_be(&TestModule::account_id(), 1000);
+		assert_ok!(TestModule::set_spend_limit(RawOrigin::Root.into(), Some(120)));
+
+		assert_noop!(
+			TestModule::spend_xcm(
+				RuntimeOrigin::signed(Admin::get()),
+				Box::new(MultiLocation::parent().into()),
+				Box::new(xcm_beneficiary().into()),
+				100
+			),
+			Error::<Test>::SpendLimitExceeded
+		);
+		assert!(executed_xcm().is_empty());
+	})
+}
+
+#[test]
+fn spend_xcm_fails_if_execution_fails() {
+	new_test_ext().execute_with(|| {
+		TestCurrency::make_free_balance_be(&TestModule::account_id(), 100);
+
+		assert_err!(
+			TestModule::spend_xcm(
+				RuntimeOrigin::signed(Admin::get()),
+				Box::new(MultiLocation::parent().into()),
+				Box::new(xcm_beneficiary().into()),
+				100
+			),
+			Error::<Test>::XcmExecutionFailed
+		);
+		assert_eq!(Balances::free_balance(TestModule::account_id()), 100);
+	})
+}
+
+#[test]
+fn spend_xcm_rejects_zero_amount() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TestModule::spend_xcm(
+				RuntimeOrigin::signed(Admin::get()),
+				Box::new(MultiLocation::parent().into()),
+				Box::new(xcm_beneficiary().into()),
+				0
+			),
+			Error::<Test>::ZeroXcmAmount
+		);
+	})
+}
@@ -16,14 +16,14 @@
  * along with this program.  If not, see <http://www.gnu.org/licenses/>.
  */
 
-//! Autogenerated weights for pallet_reserve
+//! Weights for pallet_reserve
 //!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
-//! DATE: 2023-02-16, STEPS: `50`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
-//! HOSTNAME: `chain-bench-012bd056`, CPU: `AMD EPYC 7B13`
-//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
+//! These weights are hand-written estimates, not benchmark output. `tip` and `spend` were last
+//! measured on 2023-02-16 and every entry has since been adjusted or added by hand for the
+//! storage the pallet now touches. Regenerate this file with `scripts/run_benchmarks.sh` to
+//! replace them with measured weights.
 
-// Executed Command:
+// Regenerate with:
 // ./target/release/nodle-parachain
 // benchmark
 // pallet
@@ -48,6 +48,21 @@ use sp_std::marker::PhantomData;
 pub trait WeightInfo {
 	fn tip() -> Weight;
 	fn spend() -> Weight;
+	fn set_spend_limit() -> Weight;
+	fn request_spend() -> Weight;
+	fn veto_spend() -> Weight;
+	fn execute_spend() -> Weight;
+	fn create_stream() -> Weight;
+	fn cancel_stream() -> Weight;
+	fn pay_streams(n: u32, ) -> Weight;
+	fn spend_asset() -> Weight;
+	fn tip_asset() -> Weight;
+	fn spend_xcm() -> Weight;
+	fn create_category() -> Weight;
+	fn set_category_allowance() -> Weight;
+	fn remove_category() -> Weight;
+	fn set_asset_spend_limit() -> Weight;
+	fn spend_in_category() -> Weight;
 }
 
 /// Weight functions for `pallet_reserve`.
@@ -55,49 +70,445 @@ pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	// Storage: System Account (r:2 w:0)
 	// Storage: System Number (r:1 w:0)
+	// Storage: CompanyReserve Inflows (r:1 w:1)
 	// Storage: System ExecutionPhase (r:1 w:0)
 	// Storage: System EventCount (r:1 w:1)
 	// Storage: System Events (r:1 w:1)
 	fn tip() -> Weight {
-		// Minimum execution time: 27_300 nanoseconds.
-		Weight::from_ref_time(27_930_000_u64)
-			.saturating_add(T::DbWeight::get().reads(6_u64))
-			.saturating_add(T::DbWeight::get().writes(2_u64))
+		// Estimate, see the module docs.
+		Weight::from_ref_time(31_620_000_u64)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
 	}
-	// Storage: System Account (r:2 w:0)
 	// Storage: System Number (r:1 w:0)
+	// Storage: CompanyReserve Spent (r:1 w:1)
+	// Storage: CompanyReserve SpendLimit (r:1 w:0)
+	// Storage: System Account (r:2 w:0)
 	// Storage: System ExecutionPhase (r:1 w:0)
 	// Storage: System EventCount (r:1 w:1)
 	// Storage: System Events (r:1 w:1)
 	fn spend() -> Weight {
-		// Minimum execution time: 27_400 nanoseconds.
-		Weight::from_ref_time(28_290_000_u64)
+		// Estimate, see the module docs.
+		Weight::from_ref_time(32_480_000_u64)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	// Storage: System Number (r:1 w:0)
+	// Storage: System ExecutionPhase (r:1 w:0)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	// Storage: CompanyReserve SpendLimit (r:0 w:1)
+	fn set_spend_limit() -> Weight {
+		// Estimate, see the module docs.
+		Weight::from_ref_time(15_730_000_u64)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	// Storage: CompanyReserve NextSpendRequestId (r:1 w:1)
+	// Storage: System Number (r:1 w:0)
+	// Storage: System ExecutionPhase (r:1 w:0)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	// Storage: CompanyReserve SpendRequests (r:0 w:1)
+	fn request_spend() -> Weight {
+		// Estimate, see the module docs.
+		Weight::from_ref_time(20_410_000_u64)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	// Storage: CompanyReserve SpendRequests (r:1 w:1)
+	// Storage: System Number (r:1 w:0)
+	// Storage: System ExecutionPhase (r:1 w:0)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	fn veto_spend() -> Weight {
+		// Estimate, see the module docs.
+		Weight::from_ref_time(19_120_000_u64)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	// Storage: CompanyReserve SpendRequests (r:1 w:1)
+	// Storage: System Number (r:1 w:0)
+	// Storage: CompanyReserve Spent (r:1 w:1)
+	// Storage: CompanyReserve SpendLimit (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: System ExecutionPhase (r:1 w:0)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	fn execute_spend() -> Weight {
+		// Estimate, see the module docs.
+		Weight::from_ref_time(42_590_000_u64)
+			.saturating_add(T::DbWeight::get().reads(9_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	// Storage: System Number (r:1 w:0)
+	// Storage: CompanyReserve NextStreamId (r:1 w:1)
+	// Storage: CompanyReserve StreamAgenda (r:1 w:1)
+	// Storage: System ExecutionPhase (r:1 w:0)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	// Storage: CompanyReserve Streams (r:0 w:1)
+	fn create_stream() -> Weight {
+		// Estimate, see the module docs.
+		Weight::from_ref_time(25_640_000_u64)
 			.saturating_add(T::DbWeight::get().reads(6_u64))
-			.saturating_add(T::DbWeight::get().writes(2_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	// Storage: CompanyReserve Streams (r:1 w:1)
+	// Storage: System Number (r:1 w:0)
+	// Storage: System ExecutionPhase (r:1 w:0)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	fn cancel_stream() -> Weight {
+		// Estimate, see the module docs.
+		Weight::from_ref_time(19_050_000_u64)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	// Storage: CompanyReserve StreamAgenda (r:2 w:2)
+	// Storage: CompanyReserve Streams (r:1 w:1)
+	// Storage: CompanyReserve Spent (r:1 w:1)
+	// Storage: CompanyReserve SpendLimit (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	/// The range of component `n` is `[0, 16]`.
+	fn pay_streams(n: u32, ) -> Weight {
+		// Estimate, see the module docs.
+		Weight::from_ref_time(3_570_000_u64)
+			.saturating_add(Weight::from_ref_time(36_842_000_u64).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().reads((8_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((7_u64).saturating_mul(n.into())))
+	}
+	// Storage: CompanyReserve AssetSpent (r:1 w:1)
+	// Storage: CompanyReserve AssetSpendLimit (r:1 w:0)
+	// Storage: ForeignAssets Asset (r:1 w:1)
+	// Storage: ForeignAssets Account (r:2 w:2)
+	// Storage: System Account (r:1 w:1)
+	// Storage: System Number (r:1 w:0)
+	// Storage: System ExecutionPhase (r:1 w:0)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	fn spend_asset() -> Weight {
+		// Estimate, see the module docs.
+		Weight::from_ref_time(62_870_000_u64)
+			.saturating_add(T::DbWeight::get().reads(10_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	// Storage: ForeignAssets Asset (r:1 w:1)
+	// Storage: ForeignAssets Account (r:2 w:2)
+	// Storage: System Account (r:1 w:1)
+	// Storage: System Number (r:1 w:0)
+	// Storage: System ExecutionPhase (r:1 w:0)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	fn tip_asset() -> Weight {
+		// Estimate, see the module docs.
+		Weight::from_ref_time(62_150_000_u64)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	// Storage: CompanyReserve SpendLimit (r:1 w:0)
+	// Storage: CompanyReserve Spent (r:1 w:1)
+	// Storage: ParachainInfo ParachainId (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: PolkadotXcm SupportedVersion (r:1 w:0)
+	// Storage: PolkadotXcm VersionDiscoveryQueue (r:1 w:1)
+	// Storage: PolkadotXcm SafeXcmVersion (r:1 w:0)
+	// Storage: ParachainSystem HostConfiguration (r:1 w:0)
+	// Storage: ParachainSystem PendingUpwardMessages (r:1 w:1)
+	// Storage: System Number (r:1 w:0)
+	// Storage: System ExecutionPhase (r:1 w:0)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	fn spend_xcm() -> Weight {
+		// Estimate, see the module docs.
+		Weight::from_ref_time(134_420_000_u64)
+			.saturating_add(T::DbWeight::get().reads(13_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	// Storage: CompanyReserve NextCategoryId (r:1 w:1)
+	// Storage: System Number (r:1 w:0)
+	// Storage: System ExecutionPhase (r:1 w:0)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	// Storage: CompanyReserve Categories (r:0 w:1)
+	fn create_category() -> Weight {
+		// Estimate, see the module docs.
+		Weight::from_ref_time(25_110_000_u64)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	// Storage: CompanyReserve Categories (r:1 w:1)
+	// Storage: System Number (r:1 w:0)
+	// Storage: System ExecutionPhase (r:1 w:0)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	fn set_category_allowance() -> Weight {
+		// Estimate, see the module docs.
+		Weight::from_ref_time(24_520_000_u64)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	// Storage: CompanyReserve Categories (r:1 w:1)
+	// Storage: System Number (r:1 w:0)
+	// Storage: System ExecutionPhase (r:1 w:0)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	fn remove_category() -> Weight {
+		// Estimate, see the module docs.
+		Weight::from_ref_time(24_030_000_u64)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	// Storage: System Number (r:1 w:0)
+	// Storage: System ExecutionPhase (r:1 w:0)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	// Storage: CompanyReserve AssetSpendLimit (r:0 w:1)
+	fn set_asset_spend_limit() -> Weight {
+		Weight::from_ref_time(15_730_000_u64)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	// Storage: CompanyReserve Categories (r:1 w:0)
+	// Storage: System Number (r:1 w:0)
+	// Storage: CompanyReserve CategorySpent (r:1 w:1)
+	// Storage: CompanyReserve Spent (r:1 w:1)
+	// Storage: CompanyReserve SpendLimit (r:1 w:0)
+	// Storage: System Account (r:2 w:0)
+	// Storage: System ExecutionPhase (r:1 w:0)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	fn spend_in_category() -> Weight {
+		// Estimate, see the module docs.
+		Weight::from_ref_time(40_270_000_u64)
+			.saturating_add(T::DbWeight::get().reads(10_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
 	}
 }
 
 impl WeightInfo for () {
 	// Storage: System Account (r:2 w:0)
 	// Storage: System Number (r:1 w:0)
+	// Storage: CompanyReserve Inflows (r:1 w:1)
 	// Storage: System ExecutionPhase (r:1 w:0)
 	// Storage: System EventCount (r:1 w:1)
 	// Storage: System Events (r:1 w:1)
 	fn tip() -> Weight {
-		// Minimum execution time: 27_300 nanoseconds.
-		Weight::from_ref_time(27_930_000_u64)
-			.saturating_add(RocksDbWeight::get().reads(6_u64))
-			.saturating_add(RocksDbWeight::get().writes(2_u64))
+		// Estimate, see the module docs.
+		Weight::from_ref_time(31_620_000_u64)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
 	}
-	// Storage: System Account (r:2 w:0)
 	// Storage: System Number (r:1 w:0)
+	// Storage: CompanyReserve Spent (r:1 w:1)
+	// Storage: CompanyReserve SpendLimit (r:1 w:0)
+	// Storage: System Account (r:2 w:0)
 	// Storage: System ExecutionPhase (r:1 w:0)
 	// Storage: System EventCount (r:1 w:1)
 	// Storage: System Events (r:1 w:1)
 	fn spend() -> Weight {
-		// Minimum execution time: 27_400 nanoseconds.
-		Weight::from_ref_time(28_290_000_u64)
+		// Estimate, see the module docs.
+		Weight::from_ref_time(32_480_000_u64)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	// Storage: System Number (r:1 w:0)
+	// Storage: System ExecutionPhase (r:1 w:0)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	// Storage: CompanyReserve SpendLimit (r:0 w:1)
+	fn set_spend_limit() -> Weight {
+		// Estimate, see the module docs.
+		Weight::from_ref_time(15_730_000_u64)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	// Storage: CompanyReserve NextSpendRequestId (r:1 w:1)
+	// Storage: System Number (r:1 w:0)
+	// Storage: System ExecutionPhase (r:1 w:0)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	// Storage: CompanyReserve SpendRequests (r:0 w:1)
+	fn request_spend() -> Weight {
+		// Estimate, see the module docs.
+		Weight::from_ref_time(20_410_000_u64)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	// Storage: CompanyReserve SpendRequests (r:1 w:1)
+	// Storage: System Number (r:1 w:0)
+	// Storage: System ExecutionPhase (r:1 w:0)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	fn veto_spend() -> Weight {
+		// Estimate, see the module docs.
+		Weight::from_ref_time(19_120_000_u64)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	// Storage: CompanyReserve SpendRequests (r:1 w:1)
+	// Storage: System Number (r:1 w:0)
+	// Storage: CompanyReserve Spent (r:1 w:1)
+	// Storage: CompanyReserve SpendLimit (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: System ExecutionPhase (r:1 w:0)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	fn execute_spend() -> Weight {
+		// Estimate, see the module docs.
+		Weight::from_ref_time(42_590_000_u64)
+			.saturating_add(RocksDbWeight::get().reads(9_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	// Storage: System Number (r:1 w:0)
+	// Storage: CompanyReserve NextStreamId (r:1 w:1)
+	// Storage: CompanyReserve StreamAgenda (r:1 w:1)
+	// Storage: System ExecutionPhase (r:1 w:0)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	// Storage: CompanyReserve Streams (r:0 w:1)
+	fn create_stream() -> Weight {
+		// Estimate, see the module docs.
+		Weight::from_ref_time(25_640_000_u64)
 			.saturating_add(RocksDbWeight::get().reads(6_u64))
-			.saturating_add(RocksDbWeight::get().writes(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	// Storage: CompanyReserve Streams (r:1 w:1)
+	// Storage: System Number (r:1 w:0)
+	// Storage: System ExecutionPhase (r:1 w:0)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	fn cancel_stream() -> Weight {
+		// Estimate, see the module docs.
+		Weight::from_ref_time(19_050_000_u64)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	// Storage: CompanyReserve StreamAgenda (r:2 w:2)
+	// Storage: CompanyReserve Streams (r:1 w:1)
+	// Storage: CompanyReserve Spent (r:1 w:1)
+	// Storage: CompanyReserve SpendLimit (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	/// The range of component `n` is `[0, 16]`.
+	fn pay_streams(n: u32, ) -> Weight {
+		// Estimate, see the module docs.
+		Weight::from_ref_time(3_570_000_u64)
+			.saturating_add(Weight::from_ref_time(36_842_000_u64).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((8_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((7_u64).saturating_mul(n.into())))
+	}
+	// Storage: CompanyReserve AssetSpent (r:1 w:1)
+	// Storage: CompanyReserve AssetSpendLimit (r:1 w:0)
+	// Storage: ForeignAssets Asset (r:1 w:1)
+	// Storage: ForeignAssets Account (r:2 w:2)
+	// Storage: System Account (r:1 w:1)
+	// Storage: System Number (r:1 w:0)
+	// Storage: System ExecutionPhase (r:1 w:0)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	fn spend_asset() -> Weight {
+		// Estimate, see the module docs.
+		Weight::from_ref_time(62_870_000_u64)
+			.saturating_add(RocksDbWeight::get().reads(10_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	// Storage: ForeignAssets Asset (r:1 w:1)
+	// Storage: ForeignAssets Account (r:2 w:2)
+	// Storage: System Account (r:1 w:1)
+	// Storage: System Number (r:1 w:0)
+	// Storage: System ExecutionPhase (r:1 w:0)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	fn tip_asset() -> Weight {
+		// Estimate, see the module docs.
+		Weight::from_ref_time(62_150_000_u64)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	// Storage: CompanyReserve SpendLimit (r:1 w:0)
+	// Storage: CompanyReserve Spent (r:1 w:1)
+	// Storage: ParachainInfo ParachainId (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: PolkadotXcm SupportedVersion (r:1 w:0)
+	// Storage: PolkadotXcm VersionDiscoveryQueue (r:1 w:1)
+	// Storage: PolkadotXcm SafeXcmVersion (r:1 w:0)
+	// Storage: ParachainSystem HostConfiguration (r:1 w:0)
+	// Storage: ParachainSystem PendingUpwardMessages (r:1 w:1)
+	// Storage: System Number (r:1 w:0)
+	// Storage: System ExecutionPhase (r:1 w:0)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	fn spend_xcm() -> Weight {
+		// Estimate, see the module docs.
+		Weight::from_ref_time(134_420_000_u64)
+			.saturating_add(RocksDbWeight::get().reads(13_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	// Storage: CompanyReserve NextCategoryId (r:1 w:1)
+	// Storage: System Number (r:1 w:0)
+	// Storage: System ExecutionPhase (r:1 w:0)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	// Storage: CompanyReserve Categories (r:0 w:1)
+	fn create_category() -> Weight {
+		// Estimate, see the module docs.
+		Weight::from_ref_time(25_110_000_u64)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	// Storage: CompanyReserve Categories (r:1 w:1)
+	// Storage: System Number (r:1 w:0)
+	// Storage: System ExecutionPhase (r:1 w:0)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	fn set_category_allowance() -> Weight {
+		// Estimate, see the module docs.
+		Weight::from_ref_time(24_520_000_u64)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	// Storage: CompanyReserve Categories (r:1 w:1)
+	// Storage: System Number (r:1 w:0)
+	// Storage: System ExecutionPhase (r:1 w:0)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	fn remove_category() -> Weight {
+		// Estimate, see the module docs.
+		Weight::from_ref_time(24_030_000_u64)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	// Storage: System Number (r:1 w:0)
+	// Storage: System ExecutionPhase (r:1 w:0)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	// Storage: CompanyReserve AssetSpendLimit (r:0 w:1)
+	fn set_asset_spend_limit() -> Weight {
+		Weight::from_ref_time(15_730_000_u64)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	// Storage: CompanyReserve Categories (r:1 w:0)
+	// Storage: System Number (r:1 w:0)
+	// Storage: CompanyReserve CategorySpent (r:1 w:1)
+	// Storage: CompanyReserve Spent (r:1 w:1)
+	// Storage: CompanyReserve SpendLimit (r:1 w:0)
+	// Storage: System Account (r:2 w:0)
+	// Storage: System ExecutionPhase (r:1 w:0)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	fn spend_in_category() -> Weight {
+		// Estimate, see the module docs.
+		Weight::from_ref_time(40_270_000_u64)
+			.saturating_add(RocksDbWeight::get().reads(10_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
 	}
 }
\ No newline at end of file
@@ -0,0 +1,164 @@
+/*
+ * This file is part of the Nodle Chain distributed at https://github.com/NodleCode/chain
+ * Copyright (C) 2020-2022  Nodle International
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! RPC interface for the staking pallet, served from the `StakingApi` runtime API.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+pub use pallet_staking_runtime_api::StakingApi as StakingRuntimeApi;
+
+#[rpc(client, server)]
+pub trait StakingApi<BlockHash, AccountId, Balance, ValidatorInfo, NominatorInfo, RewardRate, PendingSlash> {
+	/// Validators selected for the active session.
+	#[method(name = "staking_selectedValidators")]
+	fn selected_validators(&self, at: Option<BlockHash>) -> RpcResult<Vec<AccountId>>;
+
+	/// Bond, commission, nominators and exposure in the active session of a validator.
+	#[method(name = "staking_validatorInfo")]
+	fn validator_info(&self, validator: AccountId, at: Option<BlockHash>) -> RpcResult<Option<ValidatorInfo>>;
+
+	/// Nominations, frozen bond and unlocking chunks of a nominator.
+	#[method(name = "staking_nominatorInfo")]
+	fn nominator_info(&self, nominator: AccountId, at: Option<BlockHash>) -> RpcResult<Option<NominatorInfo>>;
+
+	/// Rewards paid to an account that can be withdrawn.
+	#[method(name = "staking_claimableRewards")]
+	fn claimable_rewards(&self, who: AccountId, at: Option<BlockHash>) -> RpcResult<Balance>;
+
+	/// Past sessions in which a validator has rewards left to pay out, with the pages left.
+	#[method(name = "staking_unclaimedPayouts")]
+	fn unclaimed_payouts(&self, validator: AccountId, at: Option<BlockHash>) -> RpcResult<Vec<(u32, Vec<u32>)>>;
+
+	/// Estimated rewards per session per unit of stake of a validator and its nominators.
+	#[method(name = "staking_rewardRate")]
+	fn reward_rate(&self, validator: AccountId, at: Option<BlockHash>) -> RpcResult<Option<RewardRate>>;
+
+	/// Slashes deferred until a later session, with the session they are applied at.
+	#[method(name = "staking_pendingSlashes")]
+	fn pending_slashes(&self, at: Option<BlockHash>) -> RpcResult<Vec<PendingSlash>>;
+}
+
+/// Error code of a failed runtime call.
+const RUNTIME_ERROR: i32 = 1;
+
+fn runtime_error_into_rpc_err(err: impl std::fmt::Debug) -> jsonrpsee::core::Error {
+	CallError::Custom(ErrorObject::owned(
+		RUNTIME_ERROR,
+		"Runtime error",
+		Some(format!("{:?}", err)),
+	))
+	.into()
+}
+
+/// Provides RPC methods to query the staking state.
+pub struct Staking<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> Staking<C, Block> {
+	/// Creates a new instance of the Staking RPC helper.
+	pub fn new(client: Arc<C>) -> Self {
+		Self {
+			client,
+			_marker: Default::default(),
+		}
+	}
+}
+
+impl<C, Block, AccountId, Balance, ValidatorInfo, NominatorInfo, RewardRate, PendingSlash>
+	StakingApiServer<
+		<Block as BlockT>::Hash,
+		AccountId,
+		Balance,
+		ValidatorInfo,
+		NominatorInfo,
+		RewardRate,
+		PendingSlash,
+	> for Staking<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: StakingRuntimeApi<Block, AccountId, Balance, ValidatorInfo, NominatorInfo, RewardRate, PendingSlash>,
+	AccountId: Codec,
+	Balance: Codec,
+	ValidatorInfo: Codec,
+	NominatorInfo: Codec,
+	RewardRate: Codec,
+	PendingSlash: Codec,
+{
+	fn selected_validators(&self, at: Option<Block::Hash>) -> RpcResult<Vec<AccountId>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.selected_validators(&at).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn validator_info(&self, validator: AccountId, at: Option<Block::Hash>) -> RpcResult<Option<ValidatorInfo>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.validator_info(&at, validator).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn nominator_info(&self, nominator: AccountId, at: Option<Block::Hash>) -> RpcResult<Option<NominatorInfo>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.nominator_info(&at, nominator).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn claimable_rewards(&self, who: AccountId, at: Option<Block::Hash>) -> RpcResult<Balance> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.claimable_rewards(&at, who).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn unclaimed_payouts(&self, validator: AccountId, at: Option<Block::Hash>) -> RpcResult<Vec<(u32, Vec<u32>)>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.unclaimed_payouts(&at, validator)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn reward_rate(&self, validator: AccountId, at: Option<Block::Hash>) -> RpcResult<Option<RewardRate>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.reward_rate(&at, validator).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn pending_slashes(&self, at: Option<Block::Hash>) -> RpcResult<Vec<PendingSlash>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.pending_slashes(&at).map_err(runtime_error_into_rpc_err)
+	}
+}
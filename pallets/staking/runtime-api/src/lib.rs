@@ -0,0 +1,72 @@
+/*
+ * This file is part of the Nodle Chain distributed at https://github.com/NodleCode/chain
+ * Copyright (C) 2020-2022  Nodle International
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Runtime API definition for the staking pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	pub trait StakingApi<AccountId, Balance, ValidatorInfo, NominatorInfo, RewardRate, PendingSlash>
+	where
+		AccountId: Codec,
+		Balance: Codec,
+		ValidatorInfo: Codec,
+		NominatorInfo: Codec,
+		RewardRate: Codec,
+		PendingSlash: Codec,
+	{
+		/// Returns the validators selected for the active session.
+		fn selected_validators() -> Vec<AccountId>;
+
+		/// Returns the bond, commission, nominators and exposure in the active session of the
+		/// validator `validator`, or `None` if there is no such validator.
+		fn validator_info(validator: AccountId) -> Option<ValidatorInfo>;
+
+		/// Returns the nominations, frozen bond and unlocking chunks of the nominator
+		/// `nominator`, or `None` if there is no such nominator.
+		fn nominator_info(nominator: AccountId) -> Option<NominatorInfo>;
+
+		/// Returns the rewards paid to `who` that can be withdrawn.
+		fn claimable_rewards(who: AccountId) -> Balance;
+
+		/// Returns the past sessions in which `validator` has rewards left to pay out with
+		/// `payout_stakers`, along with the pages left in each of them.
+		fn unclaimed_payouts(validator: AccountId) -> Vec<(u32, Vec<u32>)>;
+
+		/// Returns the rewards per session per unit of stake of `validator` and its nominators,
+		/// estimated from the past sessions still bonded, or `None` if it wasn't at stake in any.
+		fn reward_rate(validator: AccountId) -> Option<RewardRate>;
+
+		/// Returns the slashes deferred until a later session, along with the session they are
+		/// applied at, the soonest first.
+		fn pending_slashes() -> Vec<PendingSlash>;
+	}
+
+	pub trait EquivocationApi<AuthorityId>
+	where
+		AuthorityId: Codec,
+	{
+		/// Returns the encoded proof of `authority_id` being part of the keys of the active
+		/// session, to report its equivocations with `report_equivocation`, or `None` if it
+		/// isn't.
+		fn generate_key_ownership_proof(authority_id: AuthorityId) -> Option<Vec<u8>>;
+	}
+}
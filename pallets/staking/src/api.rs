@@ -0,0 +1,252 @@
+/*
+ * This file is part of the Nodle Chain distributed at https://github.com/NodleCode/chain
+ * Copyright (C) 2020-2022  Nodle International
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Staking state as served by the `StakingApi` runtime API, so that wallets don't have to
+//! decode the storage of the pallet by hand.
+
+use super::{
+	AtStake, AwardedPts, BalanceOf, ClaimedRewards, Config, NominatorBonds, Pallet, Points, SessionValidatorReward,
+	StakeRewards, UnappliedSlashes,
+};
+use crate::types::{Bond, UnlockChunk, ValidatorStatus};
+use codec::{Decode, Encode};
+use frame_support::traits::Currency;
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{Saturating, Zero},
+	Perbill, RuntimeDebug,
+};
+use sp_staking::SessionIndex;
+use sp_std::prelude::*;
+
+/// Bonds backing a validator in a session.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct Exposure<AccountId, Balance> {
+	/// Own bond of the validator.
+	pub bond: Balance,
+	/// Own bond of the validator along with the ones of its nominators.
+	pub total: Balance,
+	pub nominators: Vec<Bond<AccountId, Balance>>,
+}
+
+/// State of a validator.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct ValidatorInfo<AccountId, Balance> {
+	pub bond: Balance,
+	/// Own bond of the validator along with the ones of its nominators.
+	pub total: Balance,
+	/// Commission charged on the rewards, within the current governance bounds.
+	pub commission: Perbill,
+	pub status: ValidatorStatus,
+	/// Whether the validator is selected for the active session.
+	pub selected: bool,
+	/// Current nominators of the validator.
+	pub nominators: Vec<Bond<AccountId, Balance>>,
+	pub unlocking: Vec<UnlockChunk<Balance>>,
+	/// Bonds at stake in the active session, if the validator is part of it.
+	pub exposure: Option<Exposure<AccountId, Balance>>,
+}
+
+pub type ValidatorInfoOf<T> = ValidatorInfo<<T as frame_system::Config>::AccountId, BalanceOf<T>>;
+
+/// State of a nominator.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct NominatorInfo<AccountId, Balance> {
+	/// Validators nominated along with the bond on each of them.
+	pub nominations: Vec<Bond<AccountId, Balance>>,
+	pub total: Balance,
+	pub active_bond: Balance,
+	pub frozen_bond: Balance,
+	pub unlocking: Vec<UnlockChunk<Balance>>,
+}
+
+pub type NominatorInfoOf<T> = NominatorInfo<<T as frame_system::Config>::AccountId, BalanceOf<T>>;
+
+/// A slash deferred until a later session.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct PendingSlash<AccountId, Balance> {
+	/// The offending validator.
+	pub validator: AccountId,
+	/// Slash of the own bond of the validator.
+	pub own: Balance,
+	/// Slashes of the nominators of the validator.
+	pub others: Vec<(AccountId, Balance)>,
+	/// Reporters of the offence, sharing `payout`.
+	pub reporters: Vec<AccountId>,
+	pub payout: Balance,
+	/// Session the offence occurred in.
+	pub slash_session: SessionIndex,
+	/// Session at the start of which the slash is applied.
+	pub apply_at: SessionIndex,
+}
+
+pub type PendingSlashOf<T> = PendingSlash<<T as frame_system::Config>::AccountId, BalanceOf<T>>;
+
+/// Rewards earned by a validator in a session per unit of stake, averaged over the past sessions
+/// it was at stake in.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct RewardRate {
+	/// Rate of the validator on its own bond, commission included.
+	pub validator: Perbill,
+	/// Rate of the nominators on their bonds.
+	pub nominator: Perbill,
+	/// Number of sessions the rates are averaged over.
+	pub sessions: u32,
+}
+
+impl<T: Config> Pallet<T> {
+	pub fn validator_info(validator: &T::AccountId) -> Option<ValidatorInfoOf<T>> {
+		let state = Self::validator_state(validator)?;
+		let session_idx = Self::active_session();
+		let exposure = if <AtStake<T>>::contains_key(session_idx, validator) {
+			let snapshot = <AtStake<T>>::get(session_idx, validator);
+			Some(Exposure {
+				bond: snapshot.bond,
+				total: snapshot.total,
+				nominators: Self::exposed_nominators(session_idx, validator, snapshot.nominator_count),
+			})
+		} else {
+			None
+		};
+		let mut nominators = <NominatorBonds<T>>::iter_prefix(validator)
+			.map(|(owner, amount)| Bond { owner, amount })
+			.collect::<Vec<_>>();
+		nominators.sort();
+
+		Some(ValidatorInfo {
+			bond: state.bond,
+			total: state.total,
+			commission: Self::commission_within_bounds(state.commission),
+			status: state.state,
+			selected: Self::selected_validators().contains(validator),
+			nominators,
+			unlocking: state.unlocking.into_inner(),
+			exposure,
+		})
+	}
+
+	pub fn nominator_info(nominator: &T::AccountId) -> Option<NominatorInfoOf<T>> {
+		let state = Self::nominator_state(nominator)?;
+		Some(NominatorInfo {
+			nominations: state.nominations.0.into_inner(),
+			total: state.total,
+			active_bond: state.active_bond,
+			frozen_bond: state.frozen_bond,
+			unlocking: state.unlocking.into_inner(),
+		})
+	}
+
+	/// Rewards paid to `who` and not withdrawn yet.
+	pub fn claimable_rewards(who: &T::AccountId) -> BalanceOf<T> {
+		<StakeRewards<T>>::get(who)
+			.iter()
+			.fold(Zero::zero(), |total: BalanceOf<T>, reward| {
+				total.saturating_add(reward.value)
+			})
+	}
+
+	/// Past sessions with rewards of `validator` still to pay out, along with the pages left.
+	pub fn unclaimed_payouts(validator: &T::AccountId) -> Vec<(SessionIndex, Vec<u32>)> {
+		Self::ended_bonded_sessions()
+			.filter(|&session_idx| Self::validator_reward(session_idx, validator) > T::Currency::minimum_balance())
+			.filter_map(|session_idx| {
+				let nominators = <AtStake<T>>::get(session_idx, validator).nominator_count;
+				let pages = Self::exposure_pages(nominators).max(1);
+				let claimed = <ClaimedRewards<T>>::get(session_idx, validator);
+				let unclaimed = (0..pages).filter(|page| !claimed.contains(page)).collect::<Vec<_>>();
+				(!unclaimed.is_empty()).then_some((session_idx, unclaimed))
+			})
+			.collect()
+	}
+
+	/// Estimated rewards per session of `validator` and its nominators, from the past sessions
+	/// still bonded. `None` if the validator wasn't at stake in any of them.
+	pub fn reward_rate(validator: &T::AccountId) -> Option<RewardRate> {
+		let (mut validator_parts, mut nominator_parts, mut sessions) = (0u64, 0u64, 0u32);
+		for session_idx in Self::ended_bonded_sessions() {
+			let snapshot = <AtStake<T>>::get(session_idx, validator);
+			if snapshot.total.is_zero() {
+				continue;
+			}
+
+			let mut amt_due = Self::validator_reward(session_idx, validator);
+			let commission = snapshot.commission * amt_due;
+			let commission = if commission > T::Currency::minimum_balance() {
+				amt_due = amt_due.saturating_sub(commission);
+				commission
+			} else {
+				Zero::zero()
+			};
+			let val_due = (Perbill::from_rational(snapshot.bond, snapshot.total) * amt_due).saturating_add(commission);
+
+			validator_parts += Perbill::from_rational(val_due, snapshot.bond).deconstruct() as u64;
+			nominator_parts += Perbill::from_rational(amt_due, snapshot.total).deconstruct() as u64;
+			sessions += 1;
+		}
+
+		if sessions.is_zero() {
+			return None;
+		}
+		Some(RewardRate {
+			validator: Perbill::from_parts((validator_parts / sessions as u64) as u32),
+			nominator: Perbill::from_parts((nominator_parts / sessions as u64) as u32),
+			sessions,
+		})
+	}
+
+	/// Slashes deferred until a later session, the soonest applied first.
+	pub fn pending_slashes() -> Vec<PendingSlashOf<T>> {
+		let mut pending = <UnappliedSlashes<T>>::iter()
+			.flat_map(|(apply_at, slashes)| {
+				slashes.into_iter().map(move |slash| PendingSlash {
+					validator: slash.validator,
+					own: slash.own,
+					others: slash.others.into_inner(),
+					reporters: slash.reporters.into_inner(),
+					payout: slash.payout,
+					slash_session: slash.slash_session,
+					apply_at,
+				})
+			})
+			.collect::<Vec<_>>();
+		// the order of the slashes of a session is kept.
+		pending.sort_by_key(|slash| slash.apply_at);
+		pending
+	}
+
+	/// Share of the rewards of `session_idx` earned by `validator` and its nominators.
+	fn validator_reward(session_idx: SessionIndex, validator: &T::AccountId) -> BalanceOf<T> {
+		let pts = <AwardedPts<T>>::get(session_idx, validator);
+		if pts.is_zero() {
+			return Zero::zero();
+		}
+		Perbill::from_rational(pts, <Points<T>>::get(session_idx)) * <SessionValidatorReward<T>>::get(session_idx)
+	}
+
+	fn ended_bonded_sessions() -> impl Iterator<Item = SessionIndex> {
+		let active_session = Self::active_session();
+		Self::bonded_sessions()
+			.into_iter()
+			.filter(move |&session_idx| session_idx < active_session)
+	}
+}
@@ -25,20 +25,57 @@ use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whiteli
 use frame_support::{
 	assert_ok,
 	traits::{Currency, EnsureOrigin, Get, UnfilteredDispatchable},
+	BoundedVec,
 };
 use frame_system::{EventRecord, RawOrigin};
-use sp_runtime::traits::Zero;
+use sp_consensus_aura::digests::CompatibleDigestItem;
+use sp_consensus_slots::{EquivocationProof, Slot};
+use sp_runtime::{
+	traits::{Header, One, Saturating, Zero},
+	DigestItem, Perbill, RuntimeAppPublic,
+};
+use sp_session::GetSessionNumber;
 use sp_std::prelude::*;
 
-use crate::types::{StakeReward, UnappliedSlash};
+use crate::equivocation::SignatureOf;
+use crate::types::StakeReward;
 use crate::Pallet as NodleStaking;
 
 pub type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
 const SEED: u32 = 0;
-const MAX_VALIDATORS: u32 = 1000;
-const MAX_SLASHES: u32 = 1000;
-const MAX_SESSION: u32 = 1000;
+
+/// Sets up the session keys of the validators in the benchmarks, they are opaque to this pallet.
+pub trait BenchmarkHelper<AccountId, AuthorityId, KeyOwnerProof> {
+	/// Make `authority_id` the key of `validator` in a new session it is selected for, returns
+	/// the proof of it.
+	fn key_owner_proof(validator: &AccountId, authority_id: AuthorityId) -> KeyOwnerProof;
+}
+
+/// Header of block `number` sealed by `author` for the Aura `slot`.
+fn aura_header<T: Config>(number: T::BlockNumber, slot: Slot, author: &T::AuthorityId) -> T::Header {
+	let mut header = T::Header::new(
+		number,
+		Default::default(),
+		Default::default(),
+		Default::default(),
+		Default::default(),
+	);
+	header
+		.digest_mut()
+		.push(<DigestItem as CompatibleDigestItem<SignatureOf<T>>>::aura_pre_digest(
+			slot,
+		));
+	let signature = author
+		.sign(&header.hash())
+		.expect("the key was generated in the keystore");
+	header
+		.digest_mut()
+		.push(<DigestItem as CompatibleDigestItem<SignatureOf<T>>>::aura_seal(
+			signature,
+		));
+	header
+}
 
 fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
 	let events = frame_system::Pallet::<T>::events();
@@ -56,7 +93,7 @@ fn create_funded_user<T: Config>(string: &'static str, n: u32, balance: BalanceO
 }
 
 fn update_stake_config<T: Config>() {
-	let max_validators = MAX_VALIDATORS + 3;
+	let max_validators = T::MaxValidators::get();
 	let min_stake_session_selection = T::DefaultStakingMinStakeSessionSelection::get();
 	let min_validator_bond = T::DefaultStakingMinValidatorBond::get();
 	let min_nominator_total_bond = T::DefaultStakingMinNominatorTotalBond::get();
@@ -135,7 +172,7 @@ benchmarks! {
    // Benchmark `set_staking_limits` extrinsic with the best possible conditions:
    // * Origin of the Call may be from CancelOrigin or ROOT account.
    set_staking_limits {
-		let max_validators = T::DefaultStakingMaxValidators::get() * 2u32;
+		let max_validators = (T::DefaultStakingMaxValidators::get() * 2u32).min(T::MaxValidators::get());
 		let min_stake_session_selection = T::DefaultStakingMinStakeSessionSelection::get() * 2u32.into();
 		let min_validator_bond = T::DefaultStakingMinValidatorBond::get() * 2u32.into();
 		let min_nominator_total_bond = T::DefaultStakingMinNominatorTotalBond::get() * 2u32.into();
@@ -509,7 +546,7 @@ benchmarks! {
 			)
 		);
 
-		let max_validators = T::DefaultStakingMaxValidators::get() * 2u32;
+		let max_validators = (T::DefaultStakingMaxValidators::get() * 2u32).min(T::MaxValidators::get());
 		let min_stake_session_selection = T::DefaultStakingMinStakeSessionSelection::get() * 2u32.into();
 		let min_validator_bond = T::DefaultStakingMinValidatorBond::get() * 2u32.into();
 		let min_nominator_total_bond = T::DefaultStakingMinNominatorTotalBond::get() * 2u32.into();
@@ -536,27 +573,22 @@ benchmarks! {
 	// * Origin of the Call must be Root.
 	// * Call will create the validator & nominator account.
 	slash_cancel_deferred {
-		let s in 1 .. MAX_SLASHES;
-		let c in 1 .. MAX_VALIDATORS;
-		let mut unapplied_slashes = Vec::new();
+		let s in 1 .. T::MaxValidators::get();
+		let c in 1 .. T::MaxValidators::get();
+		let mut unapplied_slashes: BoundedVec<UnappliedSlashOf<T>, T::MaxValidators> = Default::default();
 		let session_idx = 1u32;
 
 		update_stake_config::<T>();
-		let reg_validators = register_validator::<T>("def-validator", MAX_VALIDATORS);
+		let max_validators = T::MaxValidators::get().saturating_sub(crate::pool::len::<T>());
+		let reg_validators = register_validator::<T>("def-validator", max_validators);
 		let mut deferred_validators = Vec::new();
 
-		for idx in 0 .. MAX_SLASHES {
-
-			// let unapl_slainst = UnappliedSlash::<T::AccountId, BalanceOf<T>>{
-			//     validator: reg_validators[idx as usize].clone(),
-			//     ..Default::default()
-			// };
+		for (idx, validator) in reg_validators.iter().enumerate() {
+			let unapl_slainst = UnappliedSlashOf::<T>::from_default(validator.clone());
 
-			let unapl_slainst = UnappliedSlash::<T::AccountId, BalanceOf<T>>::from_default(reg_validators[idx as usize].clone());
+			if idx % 2 == 0 { deferred_validators.push(validator.clone()) };
 
-			if idx % 2 == 0 { deferred_validators.push(reg_validators[idx as usize].clone()) };
-
-			unapplied_slashes.push(unapl_slainst);
+			assert_ok!(unapplied_slashes.try_push(unapl_slainst).map_err(|_| "too many unapplied slashes"));
 		}
 
 		<UnappliedSlashes<T>>::insert(
@@ -571,7 +603,46 @@ benchmarks! {
 			<UnappliedSlashes<T>>::get(
 				session_idx.saturating_add(T::SlashDeferDuration::get()),
 			).len(),
-			(MAX_VALIDATORS / 2) as usize
+			reg_validators.len() / 2
+		);
+	}
+
+	// Benchmark `slash_reduce_deferred` extrinsic with the worst possible conditions:
+	// * Origin of the Call is from root origin.
+	// * Slash to reduce is the last of `MaxValidators` deferred slashes.
+	// * The slash to reduce slashes `n` nominators, every other one `MaxNominatorsPerValidator`.
+	slash_reduce_deferred {
+		let n in 0 .. T::MaxNominatorsPerValidator::get();
+
+		let apply_at = 1u32.saturating_add(T::SlashDeferDuration::get());
+		let last = T::MaxValidators::get() - 1;
+		let mut unapplied_slashes: BoundedVec<UnappliedSlashOf<T>, T::MaxValidators> = Default::default();
+		for idx in 0..T::MaxValidators::get() {
+			let mut unapl_slainst = UnappliedSlashOf::<T>::from_default(account("red-validator", idx, SEED));
+			unapl_slainst.own = 100u32.into();
+			unapl_slainst.slash_session = 1u32;
+			let nominators = if idx == last { n } else { T::MaxNominatorsPerValidator::get() };
+			for n in 0..nominators {
+				let nominator: T::AccountId = account("red-nominator", n, SEED);
+				assert_ok!(unapl_slainst.others.try_push((nominator, 100u32.into())).map_err(|_| "too many nominators"));
+			}
+			assert_ok!(unapplied_slashes.try_push(unapl_slainst).map_err(|_| "too many unapplied slashes"));
+		}
+
+		<UnappliedSlashes<T>>::insert(apply_at, &unapplied_slashes);
+
+		let validator: T::AccountId = account("red-validator", last, SEED);
+	}: _(RawOrigin::Root, apply_at, last, validator.clone(), Perbill::from_percent(50))
+	verify {
+		assert_last_event::<T>(
+			Event::DeferredSlashReduced(
+				apply_at,
+				validator,
+				Perbill::from_percent(50),
+				(100u32.into(), 50u32.into()),
+				((100 * n).into(), (50 * n).into()),
+				(Zero::zero(), Zero::zero()),
+			).into()
 		);
 	}
 
@@ -580,19 +651,17 @@ benchmarks! {
 	// * Call will create the validator accounts.
 	withdraw_staking_rewards {
 		update_stake_config::<T>();
-		let reg_validators = register_validator::<T>("wstk-validator", MAX_VALIDATORS);
-		for validator_idx in 0 .. MAX_VALIDATORS {
-			let mut stake_reward: Vec<StakeReward::<BalanceOf<T>>> = Default::default();
-			for session_idx in 0 .. MAX_SESSION {
-				stake_reward.push(StakeReward::<BalanceOf<T>>{
+		let max_validators = T::MaxValidators::get().saturating_sub(crate::pool::len::<T>());
+		let reg_validators = register_validator::<T>("wstk-validator", max_validators);
+		for validator in reg_validators.iter() {
+			let mut stake_reward: BoundedVec<StakeReward::<BalanceOf<T>>, T::MaxStakeRewards> = Default::default();
+			for session_idx in 0 .. T::MaxStakeRewards::get() {
+				assert_ok!(stake_reward.try_push(StakeReward::<BalanceOf<T>>{
 					value: Zero::zero(),
 					session_idx,
-				});
+				}).map_err(|_| "too many stake rewards"));
 			}
-			<StakeRewards<T>>::insert(
-				reg_validators[validator_idx as usize].clone(),
-				stake_reward,
-			);
+			<StakeRewards<T>>::insert(validator.clone(), stake_reward);
 		}
 
 	}: _(RawOrigin::Signed(reg_validators[0_usize].clone()))
@@ -605,6 +674,254 @@ benchmarks! {
 		);
 	}
 
+	// Benchmark `validator_set_commission` extrinsic with the worst possible conditions:
+	// * Origin of the Call is from signed origin.
+	// * Call raises the commission of an existing validator.
+	validator_set_commission {
+		let validator_bond_val: BalanceOf<T> = <StakingMinValidatorBond<T>>::get() * 2u32.into();
+		let validator = create_funded_user::<T>("vsc-validator", SEED, validator_bond_val);
+		assert_ok!(
+			<NodleStaking<T>>::validator_join_pool(
+				RawOrigin::Signed(validator.clone()).into(),
+				validator_bond_val
+			)
+		);
+		<MinValidatorCommission<T>>::put(Perbill::zero());
+		<MaxValidatorCommission<T>>::put(Perbill::from_percent(100));
+		<MaxCommissionIncrease<T>>::put(Perbill::from_percent(100));
+		<ActiveSession<T>>::put(<NodleStaking<T>>::active_session().saturating_add(1));
+		let old = <NodleStaking<T>>::validator_state(&validator).unwrap().commission;
+		let new = old.saturating_add(Perbill::from_percent(1));
+	}: _(RawOrigin::Signed(validator.clone()), new)
+	verify {
+		assert_last_event::<T>(
+			Event::ValidatorCommissionSet(validator, old, new).into()
+		);
+	}
+
+	// Benchmark `set_commission_limits` extrinsic with the best possible conditions:
+	// * Origin of the Call may be from CancelOrigin or ROOT account.
+	set_commission_limits {
+		let min_commission = Perbill::from_percent(1);
+		let max_commission = Perbill::from_percent(50);
+		let max_increase = Perbill::from_percent(2);
+		let caller = T::CancelOrigin::successful_origin();
+		let call = Call::<T>::set_commission_limits {
+			min_commission,
+			max_commission,
+			max_increase,
+		};
+	}: { call.dispatch_bypass_filter(caller)? }
+	verify {
+		assert_last_event::<T>(
+			Event::CommissionLimitsSet(min_commission, max_commission, max_increase).into()
+		);
+	}
+
+	// Benchmark `set_auto_compound` extrinsic with the worst possible conditions:
+	// * Origin of the Call is from signed origin.
+	// * Caller is a nominator, checked after the validators.
+	set_auto_compound {
+		let validator_bond_val: BalanceOf<T> = <StakingMinValidatorBond<T>>::get() * 2u32.into();
+		let validator = create_funded_user::<T>("sac-validator", SEED, validator_bond_val);
+		assert_ok!(
+			<NodleStaking<T>>::validator_join_pool(
+				RawOrigin::Signed(validator.clone()).into(),
+				validator_bond_val
+			)
+		);
+		let nominator_bond_val: BalanceOf<T> = <StakingMinNominatorTotalBond<T>>::get() * 2u32.into();
+		let nominator = create_funded_user::<T>("sac-nominator", SEED, nominator_bond_val);
+		whitelist_account!(nominator);
+		assert_ok!(
+			<NodleStaking<T>>::nominator_nominate(
+				RawOrigin::Signed(nominator.clone()).into(),
+				validator,
+				nominator_bond_val,
+				false
+			)
+		);
+		let share = Perbill::from_percent(50);
+	}: _(RawOrigin::Signed(nominator.clone()), share)
+	verify {
+		assert_last_event::<T>(
+			Event::AutoCompoundSet(nominator, share).into()
+		);
+	}
+
+	// Benchmark `payout_stakers` extrinsic with the worst possible conditions:
+	// * Origin of the Call is from signed origin.
+	// * Call pays the first page, the validator along with a full page of nominators.
+	// * Validator and nominators all auto-compound their rewards.
+	payout_stakers {
+		let n in 0 .. T::MaxNominatorRewardedPerPage::get().min(T::MaxNominatorsPerValidator::get());
+		let validator_bond_val: BalanceOf<T> = <StakingMinValidatorBond<T>>::get() * 2u32.into();
+		let validator = create_funded_user::<T>("ps-validator", SEED, validator_bond_val);
+		assert_ok!(
+			<NodleStaking<T>>::validator_join_pool(
+				RawOrigin::Signed(validator.clone()).into(),
+				validator_bond_val
+			)
+		);
+		assert_ok!(
+			<NodleStaking<T>>::set_auto_compound(
+				RawOrigin::Signed(validator.clone()).into(),
+				Perbill::from_percent(50)
+			)
+		);
+		let nominator_bond_val: BalanceOf<T> = <StakingMinNominatorTotalBond<T>>::get() * 2u32.into();
+		for nomi_idx in 0..n {
+			let nominator = create_funded_user::<T>("ps-nominator", nomi_idx, nominator_bond_val);
+			assert_ok!(
+				<NodleStaking<T>>::nominator_nominate(
+					RawOrigin::Signed(nominator.clone()).into(),
+					validator.clone(),
+					nominator_bond_val,
+					false
+				)
+			);
+			assert_ok!(
+				<NodleStaking<T>>::set_auto_compound(
+					RawOrigin::Signed(nominator).into(),
+					Perbill::from_percent(50)
+				)
+			);
+		}
+		let session_idx = <NodleStaking<T>>::active_session();
+		let state = <NodleStaking<T>>::validator_state(&validator).unwrap();
+		<NodleStaking<T>>::store_exposure(session_idx, state);
+		<AwardedPts<T>>::insert(session_idx, &validator, 20);
+		<Points<T>>::insert(session_idx, 20);
+		<SessionValidatorReward<T>>::insert(session_idx, validator_bond_val * 100u32.into());
+		<BondedSessions<T>>::put(BoundedVec::truncate_from(vec![session_idx]));
+		<ActiveSession<T>>::put(session_idx.saturating_add(1));
+		let caller = create_funded_user::<T>("ps-caller", SEED, 0u32.into());
+		whitelist_account!(caller);
+	}: _(RawOrigin::Signed(caller), validator.clone(), session_idx, 0)
+	verify {
+		assert_eq!(<NodleStaking<T>>::claimed_rewards(session_idx, &validator).into_inner(), vec![0]);
+	}
+
+	// Benchmark `validator_rebond` extrinsic with the worst possible conditions:
+	// * Origin of the Call is from signed origin.
+	// * Validator rebonds all of its unbonding funds.
+	validator_rebond {
+		let validator_bond_val: BalanceOf<T> = <StakingMinValidatorBond<T>>::get() * 4u32.into();
+		let validator = create_funded_user::<T>("vrb-validator", SEED, validator_bond_val);
+		whitelist_account!(validator);
+		assert_ok!(
+			<NodleStaking<T>>::validator_join_pool(
+				RawOrigin::Signed(validator.clone()).into(),
+				validator_bond_val
+			)
+		);
+		let validator_bond_removal: BalanceOf<T> = <StakingMinValidatorBond<T>>::get() * 1u32.into();
+		assert_ok!(
+			<NodleStaking<T>>::validator_bond_less(
+				RawOrigin::Signed(validator.clone()).into(),
+				validator_bond_removal
+			)
+		);
+		let before = validator_bond_val - validator_bond_removal;
+	}: _(RawOrigin::Signed(validator.clone()), validator_bond_removal)
+	verify {
+		assert_last_event::<T>(Event::ValidatorBondedMore(validator, before, validator_bond_val).into());
+	}
+
+	// Benchmark `nominator_rebond` extrinsic with the worst possible conditions:
+	// * Origin of the Call is from signed origin.
+	// * Nominator rebonds all of its unbonding funds.
+	nominator_rebond {
+		let validator_bond_val: BalanceOf<T> = <StakingMinValidatorBond<T>>::get() * 2u32.into();
+		let validator = create_funded_user::<T>("nrb-validator", SEED, validator_bond_val);
+		assert_ok!(
+			<NodleStaking<T>>::validator_join_pool(
+				RawOrigin::Signed(validator.clone()).into(),
+				validator_bond_val
+			)
+		);
+		let nominator_bond_val: BalanceOf<T> = <StakingMinNominatorTotalBond<T>>::get() * 4u32.into();
+		let nominator = create_funded_user::<T>("nrb-nominator", SEED, nominator_bond_val);
+		whitelist_account!(nominator);
+		assert_ok!(
+			<NodleStaking<T>>::nominator_nominate(
+				RawOrigin::Signed(nominator.clone()).into(),
+				validator.clone(),
+				nominator_bond_val,
+				false
+			)
+		);
+		let nominator_bond_removal: BalanceOf<T> = <StakingMinNominatorTotalBond<T>>::get() * 1u32.into();
+		assert_ok!(
+			<NodleStaking<T>>::nominator_bond_less(
+				RawOrigin::Signed(nominator.clone()).into(),
+				validator.clone(),
+				nominator_bond_removal
+			)
+		);
+	}: _(RawOrigin::Signed(nominator.clone()), validator.clone(), nominator_bond_removal)
+	verify {
+		let after = <NodleStaking<T>>::validator_state(&validator).unwrap().bond +
+			<NodleStaking<T>>::validator_state(&validator).unwrap().nomi_bond_total;
+
+		assert_last_event::<T>(
+			Event::NominationIncreased(
+				nominator,
+				nominator_bond_val,
+				validator,
+				after - nominator_bond_removal,
+				after,
+			).into()
+		);
+	}
+
+	// Benchmark `report_equivocation` extrinsic with the worst possible conditions:
+	// * Origin of the Call is from signed origin.
+	// * Offender is nominated by `n` nominators, slashed along with it.
+	report_equivocation {
+		let n in 0 .. T::MaxNominatorsPerValidator::get();
+		let validator_bond_val: BalanceOf<T> = <StakingMinValidatorBond<T>>::get() * 2u32.into();
+		let validator = create_funded_user::<T>("re-validator", SEED, validator_bond_val);
+		assert_ok!(
+			<NodleStaking<T>>::validator_join_pool(
+				RawOrigin::Signed(validator.clone()).into(),
+				validator_bond_val
+			)
+		);
+		let nominator_bond_val: BalanceOf<T> = <StakingMinNominatorTotalBond<T>>::get() * 2u32.into();
+		for nomi_idx in 0..n {
+			let nominator = create_funded_user::<T>("re-nominator", nomi_idx, nominator_bond_val);
+			assert_ok!(
+				<NodleStaking<T>>::nominator_nominate(
+					RawOrigin::Signed(nominator).into(),
+					validator.clone(),
+					nominator_bond_val,
+					false
+				)
+			);
+		}
+		// every validator in the pool is selected
+		<TotalSelected<T>>::put(T::MaxValidators::get());
+		let authority_id = T::AuthorityId::generate_pair(None);
+		let key_owner_proof = T::BenchmarkHelper::key_owner_proof(&validator, authority_id.clone());
+		let session_idx = key_owner_proof.session();
+		// the slot lies between the starts of the session and the next one
+		<SessionStartSlot<T>>::insert(session_idx, Slot::from(1));
+		<SessionStartSlot<T>>::insert(session_idx.saturating_add(1), Slot::from(3));
+		let slot = Slot::from(2);
+		let equivocation_proof = Box::new(EquivocationProof {
+			offender: authority_id.clone(),
+			slot,
+			first_header: aura_header::<T>(One::one(), slot, &authority_id),
+			second_header: aura_header::<T>(2u32.into(), slot, &authority_id),
+		});
+		let caller = create_funded_user::<T>("re-caller", SEED, 0u32.into());
+		whitelist_account!(caller);
+	}: _(RawOrigin::Signed(caller.clone()), equivocation_proof, key_owner_proof)
+	verify {
+		assert!(<ReportedEquivocations<T>>::contains_key(session_idx, (validator.clone(), slot)));
+	}
+
 	impl_benchmark_test_suite!(
 		NodleStaking,
 		crate::mock::ExtBuilder::default().has_stakers(true).build(),
@@ -0,0 +1,70 @@
+/*
+ * This file is part of the Nodle Chain distributed at https://github.com/NodleCode/chain
+ * Copyright (C) 2020-2022  Nodle International
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Checks of the reports of Aura authorities sealing two different blocks for the same slot.
+
+use super::{Config, Pallet, SessionStartSlot};
+use frame_support::traits::Get;
+use sp_consensus_aura::digests::CompatibleDigestItem;
+use sp_consensus_slots::{EquivocationProof, Slot};
+use sp_runtime::{traits::Header, RuntimeAppPublic};
+use sp_staking::SessionIndex;
+
+pub(crate) type SignatureOf<T> = <<T as Config>::AuthorityId as RuntimeAppPublic>::Signature;
+
+impl<T: Config> Pallet<T> {
+	/// Whether both headers of `proof` are different blocks sealed by its offender for its slot.
+	pub(crate) fn check_equivocation_proof(proof: &EquivocationProof<T::Header, T::AuthorityId>) -> bool {
+		proof.first_header.hash() != proof.second_header.hash()
+			&& Self::check_aura_seal(&proof.first_header, proof.slot, &proof.offender)
+			&& Self::check_aura_seal(&proof.second_header, proof.slot, &proof.offender)
+	}
+
+	/// Whether `slot` is one of the slots of `session_idx`. The first block of a session is
+	/// sealed by an authority of the previous one, so a session owns the slots after the one it
+	/// started at, up to the one the next session started at or the current one.
+	pub(crate) fn is_session_slot(session_idx: SessionIndex, slot: Slot) -> bool {
+		let start = match <SessionStartSlot<T>>::get(session_idx) {
+			Some(start) => start,
+			None => return false,
+		};
+		let end = <SessionStartSlot<T>>::get(session_idx.saturating_add(1)).unwrap_or_else(T::CurrentSlot::get);
+		start < slot && slot <= end
+	}
+
+	/// Whether `header` is pre-digested for `slot` and sealed by `author`, as Aura does.
+	fn check_aura_seal(header: &T::Header, slot: Slot, author: &T::AuthorityId) -> bool {
+		let mut header = header.clone();
+		let signature = match header
+			.digest_mut()
+			.pop()
+			.and_then(|seal| CompatibleDigestItem::<SignatureOf<T>>::as_aura_seal(&seal))
+		{
+			Some(signature) => signature,
+			None => return false,
+		};
+		let pre_digest_slot = header
+			.digest()
+			.logs()
+			.iter()
+			.find_map(CompatibleDigestItem::<SignatureOf<T>>::as_aura_pre_digest);
+
+		// the seal signs the hash of the header without it.
+		pre_digest_slot == Some(slot) && author.verify(&header.hash(), &signature)
+	}
+}
@@ -17,11 +17,12 @@
  */
 
 use super::{
-	ActiveSession, BalanceOf, BondedSessions, Config, Event, NegativeImbalanceOf, Pallet, SessionAccumulatedBalance,
-	SessionValidatorReward, SlashRewardProportion, Staked, Store, Total,
+	ActiveSession, AtStake, BalanceOf, BondedSessions, Config, Event, NegativeImbalanceOf, Pallet, Performance,
+	PoorSessions, SessionAccumulatedBalance, SessionStartSlot, SessionValidatorReward, SlashRewardProportion,
+	SnapshotOf, Staked, Store, Total,
 };
 use crate::slashing;
-use crate::types::{ValidatorSnapshot, ValidatorSnapshotOf};
+use crate::types::ValidatorSnapshotOf;
 use frame_support::{
 	pallet_prelude::*,
 	traits::{Currency, Get, Imbalance, OnUnbalanced},
@@ -29,7 +30,7 @@ use frame_support::{
 use frame_system::{self as system};
 use pallet_session::historical;
 use sp_runtime::{
-	traits::{AccountIdConversion, Convert, Saturating},
+	traits::{AccountIdConversion, Convert, Saturating, Zero},
 	Perbill,
 };
 use sp_staking::{
@@ -60,12 +61,17 @@ impl<T: Config> OnUnbalanced<NegativeImbalanceOf<T>> for Pallet<T> {
 /// * 20 points to the block producer for producing a (non-uncle) block in the relay chain,
 /// * 2 points to the block producer for each reference to a previously unreferenced uncle, and
 /// * 1 point to the producer of each referenced uncle block.
+///
+/// Authored blocks are also counted to score the performance of the validators.
 impl<T> pallet_authorship::EventHandler<T::AccountId, T::BlockNumber> for Pallet<T>
 where
 	T: Config + pallet_authorship::Config + pallet_session::Config,
 {
 	fn note_author(author: T::AccountId) {
 		log::trace!("note_author:[{:#?}] - Author[{:#?}]", line!(), author);
+		<Performance<T>>::mutate(Self::active_session(), &author, |performance| {
+			performance.authored = performance.authored.saturating_add(1)
+		});
 		Self::reward_by_ids(vec![(author, 20)])
 	}
 	fn note_uncle(uncle_author: T::AccountId, _age: T::BlockNumber) {
@@ -109,32 +115,44 @@ impl<T: Config> pallet_session::SessionManager<T::AccountId> for Pallet<T> {
 			total_staked,
 		);
 
-		Some(Self::selected_validators())
+		Some(Self::selected_validators().into_inner())
 	}
 	fn start_session(start_index: SessionIndex) {
 		log::trace!("start_session:[{:#?}] - Sess-idx[{:#?}]", line!(), start_index);
 
 		<ActiveSession<T>>::put(start_index);
+		<SessionStartSlot<T>>::insert(start_index, T::CurrentSlot::get());
 
 		let bonding_duration = T::BondedDuration::get();
 
 		<BondedSessions<T>>::mutate(|bonded| {
-			bonded.push(start_index);
-
 			if start_index > bonding_duration {
 				let first_kept = start_index - bonding_duration;
 
 				// prune out everything that's from before the first-kept index.
-				let n_to_prune = bonded
+				let to_prune = bonded
 					.iter()
 					.take_while(|&&session_idx| session_idx < first_kept)
-					.count();
+					.cloned()
+					.collect::<Vec<SessionIndex>>();
+				bonded.retain(|&session_idx| session_idx >= first_kept);
 
-				for prune_session in bonded.drain(..n_to_prune) {
+				for prune_session in to_prune {
 					// Clear the DB cached state of last session
 					Self::clear_session_information(prune_session);
 				}
+			}
 
+			// after pruning at most `BondedDuration` sessions are left, so there is room for this one.
+			if bonded.try_push(start_index).is_err() {
+				log::error!(
+					"start_session:[{:#?}] - Bonded sessions full, Sess-idx[{:#?}] not tracked",
+					line!(),
+					start_index
+				);
+			}
+
+			if start_index > bonding_duration {
 				if let Some(&first_session) = bonded.first() {
 					T::SessionInterface::prune_historical_up_to(first_session);
 				}
@@ -155,11 +173,12 @@ impl<T: Config> pallet_session::SessionManager<T::AccountId> for Pallet<T> {
 		if Self::active_session() == end_index {
 			let payout = Self::session_accumulated_balance(end_index);
 
-			// Set ending session reward.
+			// Set ending session reward, stakers claim it with `payout_stakers`.
 			<SessionValidatorReward<T>>::insert(end_index, payout);
 
-			// pay all stakers for T::BondedDuration rounds ago
-			Self::pay_stakers(end_index);
+			// scoring runs outside of any extrinsic, account for it in the block weight.
+			let weight = Self::score_validators(end_index);
+			<frame_system::Pallet<T>>::register_extra_weight_unchecked(weight, DispatchClass::Mandatory);
 
 		// // Clear the DB cached state of last session
 		// Self::clear_session_information(Self::active_session());
@@ -194,7 +213,7 @@ impl<T: Config> SessionInterface<<T as frame_system::Config>::AccountId> for T
 where
 	T: pallet_session::Config<ValidatorId = <T as frame_system::Config>::AccountId>,
 	T: pallet_session::historical::Config<
-		FullIdentification = ValidatorSnapshot<<T as frame_system::Config>::AccountId, BalanceOf<T>>,
+		FullIdentification = SnapshotOf<T>,
 		FullIdentificationOf = ValidatorSnapshotOf<T>,
 	>,
 	T::SessionHandler: pallet_session::SessionHandler<<T as frame_system::Config>::AccountId>,
@@ -217,10 +236,8 @@ where
 	}
 }
 
-impl<T: Config> historical::SessionManager<T::AccountId, ValidatorSnapshot<T::AccountId, BalanceOf<T>>> for Pallet<T> {
-	fn new_session(
-		new_index: SessionIndex,
-	) -> Option<Vec<(T::AccountId, ValidatorSnapshot<T::AccountId, BalanceOf<T>>)>> {
+impl<T: Config> historical::SessionManager<T::AccountId, SnapshotOf<T>> for Pallet<T> {
+	fn new_session(new_index: SessionIndex) -> Option<Vec<(T::AccountId, SnapshotOf<T>)>> {
 		<Self as pallet_session::SessionManager<_>>::new_session(new_index).map(|validators| {
 			validators
 				.into_iter()
@@ -244,7 +261,7 @@ impl<T: Config> OnOffenceHandler<T::AccountId, pallet_session::historical::Ident
 where
 	T: pallet_session::Config<ValidatorId = <T as frame_system::Config>::AccountId>,
 	T: pallet_session::historical::Config<
-		FullIdentification = ValidatorSnapshot<<T as frame_system::Config>::AccountId, BalanceOf<T>>,
+		FullIdentification = SnapshotOf<T>,
 		FullIdentificationOf = ValidatorSnapshotOf<T>,
 	>,
 	T::SessionHandler: pallet_session::SessionHandler<<T as frame_system::Config>::AccountId>,
@@ -256,6 +273,19 @@ where
 		slash_fraction: &[Perbill],
 		slash_session: SessionIndex,
 		disable_strategy: DisableStrategy,
+	) -> Weight {
+		Self::handle_offences(offenders, slash_fraction, slash_session, disable_strategy)
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Compute the slashes of the `offenders`, applying them now or deferring them by
+	/// `SlashDeferDuration`.
+	pub(crate) fn handle_offences(
+		offenders: &[OffenceDetails<T::AccountId, (T::AccountId, SnapshotOf<T>)>],
+		slash_fraction: &[Perbill],
+		slash_session: SessionIndex,
+		disable_strategy: DisableStrategy,
 	) -> Weight {
 		log::trace!(
 			"on_offence:[{:#?}] - Sess-idx [{:#?}] | Slash-Frac [{:#?}]",
@@ -309,8 +339,39 @@ where
 					let rw = upper_bound + nominators_len * upper_bound;
 					add_db_reads_writes(rw, rw);
 				}
-				unapplied.reporters = details.reporters.clone();
-				if slash_defer_duration == 0 {
+				unapplied.reporters = BoundedVec::truncate_from(details.reporters.clone());
+				let apply_now = if slash_defer_duration == 0 {
+					Some(unapplied)
+				} else {
+					// defer to end of some `slash_defer_duration` from now.
+					let apply_at = active_session.saturating_add(slash_defer_duration);
+
+					let deferred = <Self as Store>::UnappliedSlashes::mutate(apply_at, |for_later| {
+						for_later.try_push(unapplied.clone())
+					});
+					add_db_reads_writes(1, 1);
+
+					match deferred {
+						Ok(()) => {
+							<Pallet<T>>::deposit_event(Event::DeferredUnappliedSlash(
+								active_session,
+								unapplied.validator,
+							));
+							None
+						}
+						Err(unapplied) => {
+							// no room left to defer the slash, better apply it than lose it.
+							log::warn!(
+								"on_offence:[{:#?}] - Deferred slashes full for Sess-idx[{:#?}], applying now",
+								line!(),
+								apply_at,
+							);
+							Some(unapplied)
+						}
+					}
+				};
+
+				if let Some(unapplied) = apply_now {
 					// apply right away.
 					slashing::apply_slash::<T>(unapplied);
 
@@ -320,15 +381,6 @@ where
 						(1 + nominators_len) * slash_cost.0 + reward_cost.0 * reporters_len,
 						(1 + nominators_len) * slash_cost.1 + reward_cost.1 * reporters_len,
 					);
-				} else {
-					// defer to end of some `slash_defer_duration` from now.
-					let apply_at = active_session.saturating_add(slash_defer_duration);
-
-					<Self as Store>::UnappliedSlashes::mutate(apply_at, |for_later| for_later.push(unapplied.clone()));
-
-					<Pallet<T>>::deposit_event(Event::DeferredUnappliedSlash(active_session, unapplied.validator));
-
-					add_db_reads_writes(1, 1);
 				}
 			} else {
 				log::trace!("on_offence:[{:#?}] - NOP", line!(),);
@@ -337,4 +389,103 @@ where
 		}
 		consumed_weight
 	}
+
+	/// Score the validators at stake in `session_idx` on the blocks they authored, against an even
+	/// share of the Aura slots the session lasted. Validators performing poorly for
+	/// `MaxPoorSessions` sessions in a row are chilled, and slashed by `PoorPerformanceSlash` if
+	/// any. Returns the weight consumed.
+	///
+	/// Scoring is done on chain rather than by an offchain worker: the blocks authored are
+	/// already counted on chain by `note_author` and the slots of the session are known from
+	/// `SessionStartSlot` and `CurrentSlot`, so there is nothing to fetch off chain. Scoring in
+	/// `end_session` is deterministic and needs no unsigned transactions to be validated.
+	pub(crate) fn score_validators(session_idx: SessionIndex) -> Weight {
+		let validators = <AtStake<T>>::iter_key_prefix(session_idx).collect::<Vec<_>>();
+		let mut consumed_weight = T::DbWeight::get().reads(validators.len() as u64 + 1);
+		// sessions started before their slot was recorded are not scored.
+		let start_slot = match <SessionStartSlot<T>>::get(session_idx) {
+			Some(start_slot) if !validators.is_empty() => start_slot,
+			_ => return consumed_weight,
+		};
+		// CurrentSlot, Invulnerables
+		consumed_weight = consumed_weight.saturating_add(T::DbWeight::get().reads(2));
+		// Performance and PoorSessions of every validator
+		consumed_weight = consumed_weight.saturating_add(
+			T::DbWeight::get()
+				.reads_writes(2, 2)
+				.saturating_mul(validators.len() as u64),
+		);
+
+		let slots = (*T::CurrentSlot::get()).saturating_sub(*start_slot);
+		let expected = u32::try_from(slots / validators.len() as u64).unwrap_or(u32::MAX);
+		let threshold = T::PoorPerformanceThreshold::get() * expected;
+		let max_poor_sessions = T::MaxPoorSessions::get();
+		let invulnerables = Self::invulnerables();
+
+		let mut chilled = Vec::new();
+		for validator in validators {
+			let performance = <Performance<T>>::mutate(session_idx, &validator, |performance| {
+				performance.expected = expected;
+				*performance
+			});
+			if performance.authored >= threshold {
+				<PoorSessions<T>>::remove(&validator);
+				continue;
+			}
+
+			let poor_sessions = <PoorSessions<T>>::mutate(&validator, |poor_sessions| {
+				*poor_sessions = poor_sessions.saturating_add(1);
+				*poor_sessions
+			});
+			Self::deposit_event(Event::ValidatorPerformedPoorly(
+				session_idx,
+				validator.clone(),
+				performance.authored,
+				expected,
+				poor_sessions,
+			));
+			log::debug!(
+				"score_validators:[{:#?}] - Acc[{:#?}] | Authored[{}] | Expected[{}] | Poor[{}]",
+				line!(),
+				validator,
+				performance.authored,
+				expected,
+				poor_sessions,
+			);
+
+			if max_poor_sessions.is_zero() || poor_sessions < max_poor_sessions || invulnerables.contains(&validator) {
+				continue;
+			}
+			// validators already idle or leaving are left as they are.
+			consumed_weight = consumed_weight.saturating_add(T::DbWeight::get().reads(1));
+			if Self::validator_state(&validator).map_or(false, |state| state.is_active()) {
+				// ValidatorState, ValidatorPool
+				consumed_weight = consumed_weight.saturating_add(T::DbWeight::get().reads_writes(1, 2));
+				Self::validator_deactivate(&validator);
+				Self::deposit_event(Event::ValidatorChilled(session_idx, validator.clone()));
+				chilled.push(validator.clone());
+			}
+			<PoorSessions<T>>::remove(&validator);
+		}
+
+		let slash = T::PoorPerformanceSlash::get();
+		if slash.is_zero() || chilled.is_empty() {
+			return consumed_weight;
+		}
+		consumed_weight = consumed_weight.saturating_add(T::DbWeight::get().reads(chilled.len() as u64));
+		let offenders = chilled
+			.into_iter()
+			.map(|validator| OffenceDetails {
+				offender: (validator.clone(), <AtStake<T>>::get(session_idx, &validator)),
+				reporters: Vec::new(),
+			})
+			.collect::<Vec<_>>();
+		let slash_fraction = vec![slash; offenders.len()];
+		consumed_weight.saturating_add(Self::handle_offences(
+			&offenders,
+			&slash_fraction,
+			session_idx,
+			DisableStrategy::Never,
+		))
+	}
 }
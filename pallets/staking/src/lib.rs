@@ -21,7 +21,7 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-mod benchmarking;
+pub mod benchmarking;
 
 #[cfg(test)]
 mod mock;
@@ -29,6 +29,7 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+pub mod migrations;
 mod set;
 pub mod weights;
 
@@ -37,7 +38,10 @@ use frame_support::{pallet, pallet_prelude::MaxEncodedLen};
 use scale_info::TypeInfo;
 use sp_runtime::RuntimeDebug;
 
+pub(crate) mod api;
+pub(crate) mod equivocation;
 pub(crate) mod hooks;
+pub(crate) mod pool;
 pub(crate) mod slashing;
 pub(crate) mod types;
 
@@ -53,6 +57,9 @@ pub use pallet::*;
 enum Releases {
 	V0, // Legacy version
 	V1, // Adds storage info
+	V2, // Adds per validator commission
+	V3, // Bounded storage
+	V4, // Validators pool and nominator bonds in maps
 }
 
 impl Default for Releases {
@@ -67,33 +74,89 @@ pub mod pallet {
 	use crate::set::OrderedSet;
 	use frame_support::{
 		pallet_prelude::*,
+		storage::with_storage_layer,
 		traits::{
-			Currency, ExistenceRequirement, Get, Imbalance, LockIdentifier, LockableCurrency, OnUnbalanced,
-			ValidatorRegistration, WithdrawReasons,
+			Currency, ExistenceRequirement, Get, Imbalance, KeyOwnerProofSystem, LockIdentifier, LockableCurrency,
+			OnUnbalanced, ValidatorRegistration, WithdrawReasons,
 		},
 		PalletId,
 	};
 	use frame_system::pallet_prelude::*;
+	use sp_consensus_slots::{EquivocationProof, Slot};
 	use sp_runtime::{
 		traits::{AccountIdConversion, Saturating, Zero},
-		DispatchResult, Perbill,
+		DispatchResult, KeyTypeId, Perbill, RuntimeAppPublic,
+	};
+	use sp_session::{GetSessionNumber, GetValidatorCount};
+	use sp_staking::{
+		offence::{DisableStrategy, OffenceDetails},
+		SessionIndex,
 	};
-	use sp_staking::SessionIndex;
 	use sp_std::{convert::From, prelude::*};
 
 	pub use weights::WeightInfo;
 
+	use pool::PoolNode;
 	use types::{Bond, Nominator, RewardPoint, SpanIndex, StakeReward, UnappliedSlash, UnlockChunk, Validator};
 
-	pub use types::{ValidatorSnapshot, ValidatorSnapshotOf};
+	pub use api::{
+		Exposure, NominatorInfo, NominatorInfoOf, PendingSlash, PendingSlashOf, RewardRate, ValidatorInfo,
+		ValidatorInfoOf,
+	};
+	pub use types::{SessionPerformance, ValidatorSnapshot, ValidatorSnapshotOf};
 
 	pub use hooks::{SessionInterface, StashOf};
 
-	pub(crate) type StakingInvulnerables<T> = Vec<<T as frame_system::Config>::AccountId>;
+	pub(crate) type StakingInvulnerables<T> =
+		BoundedVec<<T as frame_system::Config>::AccountId, <T as Config>::MaxValidators>;
 
 	pub(crate) type BalanceOf<T> =
 		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
+	pub(crate) type ValidatorOf<T> =
+		Validator<<T as frame_system::Config>::AccountId, BalanceOf<T>, <T as Config>::MaxChunkUnlock>;
+
+	pub(crate) type NominatorOf<T> = Nominator<
+		<T as frame_system::Config>::AccountId,
+		BalanceOf<T>,
+		<T as Config>::MaxValidatorPerNominator,
+		<T as Config>::MaxChunkUnlock,
+	>;
+
+	pub(crate) type SnapshotOf<T> = ValidatorSnapshot<BalanceOf<T>>;
+
+	pub(crate) type ExposurePageOf<T> = BoundedVec<
+		Bond<<T as frame_system::Config>::AccountId, BalanceOf<T>>,
+		<T as Config>::MaxNominatorRewardedPerPage,
+	>;
+
+	pub(crate) type UnappliedSlashOf<T> = UnappliedSlash<
+		<T as frame_system::Config>::AccountId,
+		BalanceOf<T>,
+		<T as Config>::MaxNominatorsPerValidator,
+		<T as Config>::MaxSlashReporters,
+	>;
+
+	/// Bound of the still-bonded sessions, the active one and the `BondedDuration` previous ones.
+	pub struct MaxBondedSessions<T>(PhantomData<T>);
+
+	impl<T: Config> Get<u32> for MaxBondedSessions<T> {
+		fn get() -> u32 {
+			T::BondedDuration::get().saturating_add(1)
+		}
+	}
+
+	/// Bound of the pages of a validator payout, enough to page through `MaxNominatorsPerValidator`
+	/// nominators `MaxNominatorRewardedPerPage` at a time.
+	pub struct MaxPayoutPages<T>(PhantomData<T>);
+
+	impl<T: Config> Get<u32> for MaxPayoutPages<T> {
+		fn get() -> u32 {
+			let per_page = T::MaxNominatorRewardedPerPage::get().max(1);
+			(T::MaxNominatorsPerValidator::get().saturating_add(per_page - 1) / per_page).max(1)
+		}
+	}
+
 	pub(crate) type NegativeImbalanceOf<T> =
 		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::NegativeImbalance;
 
@@ -116,6 +179,12 @@ pub mod pallet {
 		type MaxValidatorPerNominator: Get<u32>;
 		/// Fee due to validators, set at genesis
 		type DefaultValidatorFee: Get<Perbill>;
+		/// Minimum commission validators may charge, set at genesis
+		type DefaultMinValidatorCommission: Get<Perbill>;
+		/// Maximum commission validators may charge, set at genesis
+		type DefaultMaxValidatorCommission: Get<Perbill>;
+		/// Maximum commission raise validators may do per session, set at genesis
+		type DefaultMaxCommissionIncrease: Get<Perbill>;
 		/// Default Slash reward propostion, set at genesis
 		type DefaultSlashRewardProportion: Get<Perbill>;
 		/// The proportion of the slashing reward to be paid out on the first slashing detection.
@@ -143,7 +212,42 @@ pub mod pallet {
 		/// staking pallet Lock Identifier used for set_lock()
 		type StakingLockId: Get<LockIdentifier>;
 		/// Max number of unbond request supported by queue
-		type MaxChunkUnlock: Get<usize>;
+		type MaxChunkUnlock: Get<u32>;
+		/// Maximum validators in the validators pool, the exit queue and the selected set,
+		/// `StakingMaxValidators` can't be set above it
+		type MaxValidators: Get<u32>;
+		/// Maximum rewards kept per staker until withdrawn, further ones are added up to the last
+		type MaxStakeRewards: Get<u32>;
+		/// Maximum nominators paid by a single page of `payout_stakers`
+		type MaxNominatorRewardedPerPage: Get<u32>;
+		/// Maximum reporters sharing the reward of a slash
+		type MaxSlashReporters: Get<u32>;
+		/// Maximum prior slashing spans kept per staker
+		type MaxSlashingSpans: Get<u32>;
+		/// Aura slot of the block being built, sessions are scored on the slots they lasted
+		type CurrentSlot: Get<Slot>;
+		/// Share of its expected blocks a validator has to author in a session not to perform poorly
+		type PoorPerformanceThreshold: Get<Perbill>;
+		/// Sessions in a row a validator may perform poorly before being chilled, zero never chills
+		type MaxPoorSessions: Get<u32>;
+		/// Slash of the validators chilled for performing poorly, zero doesn't slash them
+		type PoorPerformanceSlash: Get<Perbill>;
+		/// The Aura authority id blocks are sealed with
+		type AuthorityId: Member + Parameter + RuntimeAppPublic;
+		/// Proof of an authority id being part of the keys of a session
+		type KeyOwnerProof: Parameter + GetSessionNumber + GetValidatorCount;
+		/// Checks the key ownership proofs of the equivocation reports, against the historical
+		/// sessions
+		type KeyOwnerProofSystem: KeyOwnerProofSystem<
+			(KeyTypeId, Self::AuthorityId),
+			Proof = Self::KeyOwnerProof,
+			IdentificationTuple = (Self::AccountId, SnapshotOf<Self>),
+		>;
+		/// Slash of the validators sealing two blocks for the same slot
+		type EquivocationSlash: Get<Perbill>;
+		/// Sets up the session keys of the validators reported in the benchmarks
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: benchmarking::BenchmarkHelper<Self::AccountId, Self::AuthorityId, Self::KeyOwnerProof>;
 		/// The origin which can cancel a deferred slash. Root can always do this.
 		type CancelOrigin: EnsureOrigin<Self::RuntimeOrigin>;
 		/// Weight information for extrinsics in this pallet.
@@ -152,9 +256,15 @@ pub mod pallet {
 
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(crate) trait Store)]
-	#[pallet::without_storage_info]
 	pub struct Pallet<T>(PhantomData<T>);
 
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			migrations::MigrateToBoundedStorage::<T>::step(remaining_weight)
+		}
+	}
+
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Set the validators who cannot be slashed (if any).
@@ -164,8 +274,10 @@ pub mod pallet {
 		#[pallet::weight(T::WeightInfo::set_invulnerables(invulnerables.len() as u32))]
 		pub fn set_invulnerables(origin: OriginFor<T>, invulnerables: Vec<T::AccountId>) -> DispatchResultWithPostInfo {
 			T::CancelOrigin::try_origin(origin).map(|_| ()).or_else(ensure_root)?;
+			let invulnerables: StakingInvulnerables<T> =
+				invulnerables.try_into().map_err(|_| <Error<T>>::TooManyInvulnerables)?;
 			<Invulnerables<T>>::put(&invulnerables);
-			Self::deposit_event(Event::NewInvulnerables(invulnerables));
+			Self::deposit_event(Event::NewInvulnerables(invulnerables.into_inner()));
 			Ok(().into())
 		}
 		/// Set the total number of validator selected per round
@@ -193,6 +305,10 @@ pub mod pallet {
 			T::CancelOrigin::try_origin(origin).map(|_| ()).or_else(ensure_root)?;
 
 			ensure!(max_stake_validators > 0, <Error<T>>::InvalidArguments);
+			ensure!(
+				max_stake_validators <= T::MaxValidators::get(),
+				<Error<T>>::InvalidArguments
+			);
 			ensure!(min_stake_session_selection > Zero::zero(), <Error<T>>::InvalidArguments);
 			ensure!(min_validator_bond > Zero::zero(), <Error<T>>::InvalidArguments);
 			ensure!(min_nominator_total_bond > Zero::zero(), <Error<T>>::InvalidArguments);
@@ -235,7 +351,7 @@ pub mod pallet {
 		}
 		/// Join the set of validators pool
 		#[pallet::call_index(3)]
-		#[pallet::weight(T::WeightInfo::validator_join_pool())]
+		#[pallet::weight(T::WeightInfo::validator_join_pool().saturating_add(Pallet::<T>::pool_update_weight()))]
 		pub fn validator_join_pool(origin: OriginFor<T>, bond: BalanceOf<T>) -> DispatchResultWithPostInfo {
 			log::debug!("validator_join_pool:[{:#?}] - Entry!!!", line!(),);
 
@@ -256,18 +372,11 @@ pub mod pallet {
 
 			log::debug!("validator_join_pool:[{:#?}]", line!(),);
 
-			let mut validators = <ValidatorPool<T>>::get();
 			ensure!(
-				validators.0.len() < Self::staking_max_validators() as usize,
+				pool::len::<T>() < Self::staking_max_validators(),
 				<Error<T>>::ValidatorPoolFull
 			);
-			ensure!(
-				validators.insert(Bond {
-					owner: acc.clone(),
-					amount: bond
-				}),
-				<Error<T>>::ValidatorExists
-			);
+			ensure!(!pool::contains::<T>(&acc), <Error<T>>::ValidatorExists);
 			log::debug!("validator_join_pool:[{:#?}]", line!());
 
 			let validator_free_balance = T::Currency::free_balance(&acc);
@@ -280,15 +389,21 @@ pub mod pallet {
 			);
 			ensure!(validator_free_balance >= bond, <Error<T>>::InsufficientBalance);
 
+			pool::upsert::<T>(&acc, bond).map_err(|_| <Error<T>>::ValidatorPoolFull)?;
+
 			log::debug!("validator_join_pool:[{:#?}]", line!(),);
 
 			T::Currency::set_lock(T::StakingLockId::get(), &acc, bond, WithdrawReasons::all());
 
-			let validator = Validator::new(acc.clone(), bond);
+			let validator = Validator::new(
+				acc.clone(),
+				bond,
+				Self::commission_within_bounds(<ValidatorFee<T>>::get()),
+				Self::active_session(),
+			);
 
 			<Total<T>>::mutate(|x| *x = x.saturating_add(bond));
 			<ValidatorState<T>>::insert(&acc, validator);
-			<ValidatorPool<T>>::put(validators);
 			Self::deposit_event(Event::JoinedValidatorPool(acc, bond, Self::total()));
 			log::debug!("validator_join_pool:[{:#?}] - Exit!!!", line!(),);
 			Ok(().into())
@@ -313,12 +428,14 @@ pub mod pallet {
 			let now = Self::active_session();
 			let when = now.saturating_add(T::BondedDuration::get());
 
-			<ExitQueue<T>>::mutate(|exits| {
-				exits.insert(Bond {
-					owner: validator.clone(),
-					amount: when,
-				});
-			});
+			<ExitQueue<T>>::try_mutate(|exits| {
+				exits
+					.try_insert(Bond {
+						owner: validator.clone(),
+						amount: when,
+					})
+					.map_err(|_| <Error<T>>::ExitQueueFull)
+			})?;
 
 			<ValidatorState<T>>::mutate(&validator, |maybe_validator| {
 				if let Some(state) = maybe_validator {
@@ -326,61 +443,24 @@ pub mod pallet {
 				}
 			});
 
-			<ValidatorPool<T>>::mutate(|validators| {
-				validators.remove(&Bond::from_owner(validator.clone()));
-			});
+			pool::remove::<T>(&validator);
 
 			Self::deposit_event(Event::ValidatorScheduledExit(now, validator, when));
 			Ok(().into())
 		}
 		/// Bond more for validator
 		#[pallet::call_index(5)]
-		#[pallet::weight(T::WeightInfo::validator_bond_more())]
+		#[pallet::weight(T::WeightInfo::validator_bond_more().saturating_add(Pallet::<T>::pool_update_weight()))]
 		pub fn validator_bond_more(origin: OriginFor<T>, more: BalanceOf<T>) -> DispatchResultWithPostInfo {
 			let validator = ensure_signed(origin)?;
 
-			ensure!(Self::is_validator(&validator), <Error<T>>::ValidatorDNE);
-
-			let valid_state = Self::validator_state(&validator).ok_or(<Error<T>>::ValidatorDNE)?;
-
-			ensure!(!valid_state.is_leaving(), <Error<T>>::CannotActivateIfLeaving,);
-
-			let validator_free_balance = T::Currency::free_balance(&validator);
-			ensure!(
-				validator_free_balance >= valid_state.bond.saturating_add(more),
-				<Error<T>>::InsufficientBalance,
-			);
-
-			ensure!(
-				valid_state.bond.saturating_add(more) > Self::staking_min_validator_bond(),
-				<Error<T>>::ValidatorBondBelowMin,
-			);
-
-			<ValidatorState<T>>::mutate(validator.clone(), |maybe_validator| {
-				if let Some(state) = maybe_validator {
-					let before = state.bond;
-					state.bond_more(more);
-					T::Currency::set_lock(T::StakingLockId::get(), &validator, state.bond, WithdrawReasons::all());
-					let after = state.bond;
-					state.go_online();
-					if state.is_active() {
-						Self::update_validators_pool(
-							validator.clone(),
-							state.bond.saturating_add(state.nomi_bond_total),
-						);
-					}
-					<Total<T>>::mutate(|x| *x = x.saturating_add(more));
-					Self::deposit_event(Event::ValidatorBondedMore(validator.clone(), before, after));
-				}
-			});
-
-			Self::validator_stake_reconciliation(&validator);
+			Self::validator_bonds_more(validator, more)?;
 
 			Ok(().into())
 		}
 		/// Bond less for validator
 		#[pallet::call_index(6)]
-		#[pallet::weight(T::WeightInfo::validator_bond_less())]
+		#[pallet::weight(T::WeightInfo::validator_bond_less().saturating_add(Pallet::<T>::pool_update_weight()))]
 		pub fn validator_bond_less(origin: OriginFor<T>, less: BalanceOf<T>) -> DispatchResultWithPostInfo {
 			let validator = ensure_signed(origin)?;
 
@@ -396,7 +476,7 @@ pub mod pallet {
 					<Error<T>>::ValidatorBondBelowMin
 				);
 				ensure!(
-					state.unlocking.len() < T::MaxChunkUnlock::get(),
+					(state.unlocking.len() as u32) < T::MaxChunkUnlock::get(),
 					<Error<T>>::NoMoreChunks,
 				);
 
@@ -412,10 +492,13 @@ pub mod pallet {
 				<Total<T>>::mutate(|x| *x = x.saturating_sub(less));
 
 				// T::Currency::unreserve(&validator, less);
-				state.unlocking.push(UnlockChunk {
-					value: less,
-					session_idx: Self::active_session().saturating_add(T::BondedDuration::get()),
-				});
+				state
+					.unlocking
+					.try_push(UnlockChunk {
+						value: less,
+						session_idx: Self::active_session().saturating_add(T::BondedDuration::get()),
+					})
+					.map_err(|_| <Error<T>>::NoMoreChunks)?;
 
 				<ValidatorState<T>>::insert(&validator, state);
 				Self::deposit_event(Event::ValidatorBondedLess(validator, before, after));
@@ -425,7 +508,7 @@ pub mod pallet {
 		/// If caller is not a nominator, then join the set of nominators
 		/// If caller is a nominator, then makes nomination to change their nomination state
 		#[pallet::call_index(7)]
-		#[pallet::weight(T::WeightInfo::nominator_nominate())]
+		#[pallet::weight(T::WeightInfo::nominator_nominate().saturating_add(Pallet::<T>::pool_update_weight()))]
 		pub fn nominator_nominate(
 			origin: OriginFor<T>,
 			validator: T::AccountId,
@@ -466,7 +549,7 @@ pub mod pallet {
 			);
 
 			ensure!(
-				(validator_state.nominators.0.len() as u32) < T::MaxNominatorsPerValidator::get(),
+				validator_state.nominator_count < T::MaxNominatorsPerValidator::get(),
 				<Error<T>>::TooManyNominators,
 			);
 
@@ -496,12 +579,8 @@ pub mod pallet {
 				<Error<T>>::NominatorBondBelowMin,
 			);
 
-			let nomination = Bond {
-				owner: nominator_acc.clone(),
-				amount,
-			};
 			ensure!(
-				validator_state.nominators.insert(nomination),
+				!<NominatorBonds<T>>::contains_key(&validator, &nominator_acc),
 				<Error<T>>::NominatorExists,
 			);
 
@@ -512,8 +591,8 @@ pub mod pallet {
 				WithdrawReasons::all(),
 			);
 
-			validator_state.nomi_bond_total = validator_state.nomi_bond_total.saturating_add(amount);
-			validator_state.total = validator_state.total.saturating_add(amount);
+			<NominatorBonds<T>>::insert(&validator, &nominator_acc, amount);
+			validator_state.add_nominator(amount);
 			let validator_new_total = validator_state.total;
 			if validator_state.is_active() {
 				Self::update_validators_pool(validator.clone(), validator_state.total);
@@ -531,7 +610,7 @@ pub mod pallet {
 		}
 		/// Revoke an existing nomination
 		#[pallet::call_index(8)]
-		#[pallet::weight(T::WeightInfo::nominator_denominate())]
+		#[pallet::weight(T::WeightInfo::nominator_denominate().saturating_add(Pallet::<T>::pool_update_weight()))]
 		pub fn nominator_denominate(origin: OriginFor<T>, validator: T::AccountId) -> DispatchResultWithPostInfo {
 			let acc = ensure_signed(origin)?;
 
@@ -544,7 +623,9 @@ pub mod pallet {
 
 		/// Quit the set of nominators and, by implication, revoke all ongoing nominations
 		#[pallet::call_index(9)]
-		#[pallet::weight(T::WeightInfo::nominator_denominate_all())]
+		#[pallet::weight(T::WeightInfo::nominator_denominate_all().saturating_add(
+			Pallet::<T>::pool_update_weight().saturating_mul(T::MaxValidatorPerNominator::get().into())
+		))]
 		pub fn nominator_denominate_all(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
 			let acc = ensure_signed(origin)?;
 
@@ -558,7 +639,7 @@ pub mod pallet {
 		}
 		/// Bond more for nominators with respect to a specific validator
 		#[pallet::call_index(10)]
-		#[pallet::weight(T::WeightInfo::nominator_bond_more())]
+		#[pallet::weight(T::WeightInfo::nominator_bond_more().saturating_add(Pallet::<T>::pool_update_weight()))]
 		pub fn nominator_bond_more(
 			origin: OriginFor<T>,
 			validator: T::AccountId,
@@ -566,58 +647,14 @@ pub mod pallet {
 			unfreeze_bond: bool,
 		) -> DispatchResultWithPostInfo {
 			let nominator = ensure_signed(origin)?;
-			let mut nominations = <NominatorState<T>>::get(&nominator).ok_or(<Error<T>>::NominatorDNE)?;
-			let mut validator_state = <ValidatorState<T>>::get(&validator).ok_or(<Error<T>>::ValidatorDNE)?;
-
-			let more = if unfreeze_bond {
-				more.saturating_add(nominations.frozen_bond)
-			} else {
-				more
-			};
-
-			let new_nomination_bond = nominations
-				.inc_nomination(validator.clone(), more, unfreeze_bond)
-				.ok_or(<Error<T>>::NominationDNE)?;
-
-			ensure!(
-				new_nomination_bond >= <StakingMinNominationChillThreshold<T>>::get(),
-				<Error<T>>::NominationBelowMin
-			);
-
-			let nominator_free_balance = T::Currency::free_balance(&nominator);
-			ensure!(
-				nominator_free_balance >= nominations.total,
-				<Error<T>>::InsufficientBalance
-			);
 
-			T::Currency::set_lock(
-				T::StakingLockId::get(),
-				&nominator,
-				nominations.total,
-				WithdrawReasons::all(),
-			);
+			Self::nominator_bonds_more(nominator, validator, more, unfreeze_bond)?;
 
-			let before = validator_state.total;
-			validator_state.inc_nominator(nominator.clone(), more);
-			let after = validator_state.total;
-			<Total<T>>::mutate(|x| *x = x.saturating_add(more));
-			if validator_state.is_active() {
-				Self::update_validators_pool(validator.clone(), validator_state.total);
-			}
-			<ValidatorState<T>>::insert(&validator, validator_state);
-			<NominatorState<T>>::insert(&nominator, nominations);
-			Self::deposit_event(Event::NominationIncreased(
-				nominator,
-				new_nomination_bond,
-				validator,
-				before,
-				after,
-			));
 			Ok(().into())
 		}
 		/// Bond less for nominators with respect to a specific nominated validator
 		#[pallet::call_index(11)]
-		#[pallet::weight(T::WeightInfo::nominator_bond_less())]
+		#[pallet::weight(T::WeightInfo::nominator_bond_less().saturating_add(Pallet::<T>::pool_update_weight()))]
 		pub fn nominator_bond_less(
 			origin: OriginFor<T>,
 			validator: T::AccountId,
@@ -643,19 +680,22 @@ pub mod pallet {
 			);
 
 			ensure!(
-				nominations.unlocking.len() < T::MaxChunkUnlock::get(),
+				(nominations.unlocking.len() as u32) < T::MaxChunkUnlock::get(),
 				<Error<T>>::NoMoreChunks,
 			);
 
 			let mut validator_state = <ValidatorState<T>>::get(&validator).ok_or(<Error<T>>::ValidatorDNE)?;
 
-			nominations.unlocking.push(UnlockChunk {
-				value: less,
-				session_idx: Self::active_session().saturating_add(T::BondedDuration::get()),
-			});
+			nominations
+				.unlocking
+				.try_push(UnlockChunk {
+					value: less,
+					session_idx: Self::active_session().saturating_add(T::BondedDuration::get()),
+				})
+				.map_err(|_| <Error<T>>::NoMoreChunks)?;
 
 			let before = validator_state.bond.saturating_add(validator_state.nomi_bond_total);
-			validator_state.dec_nominator(nominator.clone(), less);
+			Self::dec_nominator_bond(&mut validator_state, &nominator, less);
 			let after = validator_state.bond.saturating_add(validator_state.nomi_bond_total);
 			<Total<T>>::mutate(|x| *x = x.saturating_sub(less));
 			if validator_state.is_active() {
@@ -671,7 +711,8 @@ pub mod pallet {
 		}
 
 		#[pallet::call_index(12)]
-		#[pallet::weight(T::WeightInfo::nominator_move_nomination())]
+		#[pallet::weight(T::WeightInfo::nominator_move_nomination()
+			.saturating_add(Pallet::<T>::pool_update_weight().saturating_mul(2)))]
 		pub fn nominator_move_nomination(
 			origin: OriginFor<T>,
 			from_validator: T::AccountId,
@@ -703,7 +744,7 @@ pub mod pallet {
 						<ValidatorState<T>>::get(&to_validator).ok_or(<Error<T>>::ValidatorDNE)?;
 
 					ensure!(
-						(to_validator_state.nominators.0.len() as u32) < T::MaxNominatorsPerValidator::get(),
+						to_validator_state.nominator_count < T::MaxNominatorsPerValidator::get(),
 						<Error<T>>::TooManyNominators,
 					);
 
@@ -741,18 +782,14 @@ pub mod pallet {
 						unfreeze_bond,
 					) {
 						// Validator is new to the nomination pool
-						let nomination = Bond {
-							owner: nominator_acc.clone(),
-							amount: total_nomination_amount,
-						};
-						to_validator_state.nominators.insert(nomination);
-						to_validator_state.inc_nominator(nominator_acc.clone(), total_nomination_amount);
+						<NominatorBonds<T>>::insert(&to_validator, &nominator_acc, total_nomination_amount);
+						to_validator_state.add_nominator(total_nomination_amount);
 					} else {
 						// Validator already exist in nomination pool
 						let _ = nominator_state
 							.inc_nomination(to_validator.clone(), total_nomination_amount, unfreeze_bond)
 							.ok_or(<Error<T>>::NominationDNE)?;
-						to_validator_state.inc_nominator(nominator_acc.clone(), total_nomination_amount);
+						Self::inc_nominator_bond(&mut to_validator_state, &nominator_acc, total_nomination_amount);
 					}
 
 					ensure!(
@@ -946,6 +983,312 @@ pub mod pallet {
 			});
 			Ok(().into())
 		}
+
+		/// Reduce a deferred slash by `reduction`, the slash of the validator, the ones of its
+		/// nominators and the payout of the reporters alike. The slashing records of the offence
+		/// are reduced with it so a later offence is measured against the slash applied.
+		///
+		/// Can be called by the `T::CancelOrigin`.
+		///
+		/// Parameters: session the slash is applied at, its index among the slashes of that
+		/// session as listed by the `pending_slashes` runtime API, and its validator.
+		#[pallet::call_index(24)]
+		#[pallet::weight(T::WeightInfo::slash_reduce_deferred(T::MaxNominatorsPerValidator::get()))]
+		pub fn slash_reduce_deferred(
+			origin: OriginFor<T>,
+			apply_at: SessionIndex,
+			index: u32,
+			controller: T::AccountId,
+			reduction: Perbill,
+		) -> DispatchResultWithPostInfo {
+			T::CancelOrigin::try_origin(origin).map(|_| ()).or_else(ensure_root)?;
+
+			let (own, others, payout, nominators) = <UnappliedSlashes<T>>::try_mutate(apply_at, |unapplied| {
+				let slash = unapplied
+					.get_mut(index as usize)
+					.filter(|ustat| ustat.validator == controller)
+					.ok_or(<Error<T>>::UnappliedSlashDNE)?;
+
+				let others_total = |slash: &UnappliedSlashOf<T>| {
+					slash
+						.others
+						.iter()
+						.fold(BalanceOf::<T>::zero(), |total, (_, value)| total.saturating_add(*value))
+				};
+				let before = (slash.own, others_total(slash), slash.payout);
+				slashing::reduce_slash::<T>(slash, reduction);
+
+				Ok::<_, Error<T>>((
+					(before.0, slash.own),
+					(before.1, others_total(slash)),
+					(before.2, slash.payout),
+					slash.others.len() as u32,
+				))
+			})?;
+
+			Self::deposit_event(Event::DeferredSlashReduced(
+				apply_at, controller, reduction, own, others, payout,
+			));
+			Ok(Some(T::WeightInfo::slash_reduce_deferred(nominators)).into())
+		}
+
+		/// Set the commission the validator takes off of the rewards before sharing them with
+		/// its nominators, it must lay within the `MinValidatorCommission` and
+		/// `MaxValidatorCommission` bounds.
+		///
+		/// Lowering the commission is always possible, raising it is limited to once per session
+		/// and by `MaxCommissionIncrease` at most, so nominators have time to react.
+		/// The new commission applies from the next selection of the session validators.
+		#[pallet::call_index(17)]
+		#[pallet::weight(T::WeightInfo::validator_set_commission())]
+		pub fn validator_set_commission(origin: OriginFor<T>, commission: Perbill) -> DispatchResultWithPostInfo {
+			let validator = ensure_signed(origin)?;
+
+			ensure!(
+				commission >= <MinValidatorCommission<T>>::get() && commission <= <MaxValidatorCommission<T>>::get(),
+				<Error<T>>::CommissionOutOfBounds
+			);
+
+			<ValidatorState<T>>::try_mutate(&validator, |maybe_validator| -> DispatchResultWithPostInfo {
+				let state = maybe_validator.as_mut().ok_or(<Error<T>>::ValidatorDNE)?;
+				let old = state.commission;
+
+				if commission > old {
+					let now = Self::active_session();
+					ensure!(
+						state.commission_raised_at < now,
+						<Error<T>>::CommissionRaisedThisSession
+					);
+					ensure!(
+						commission.saturating_sub(old) <= <MaxCommissionIncrease<T>>::get(),
+						<Error<T>>::CommissionIncreaseTooLarge
+					);
+					state.commission_raised_at = now;
+				}
+				state.commission = commission;
+
+				Self::deposit_event(Event::ValidatorCommissionSet(validator.clone(), old, commission));
+				Ok(().into())
+			})
+		}
+
+		/// Set the bounds of the validators commission and by how much validators can raise it
+		/// per session.
+		///
+		/// The dispatch origin must be `CancelOrigin` or Root.
+		#[pallet::call_index(18)]
+		#[pallet::weight(T::WeightInfo::set_commission_limits())]
+		pub fn set_commission_limits(
+			origin: OriginFor<T>,
+			min_commission: Perbill,
+			max_commission: Perbill,
+			max_increase: Perbill,
+		) -> DispatchResultWithPostInfo {
+			T::CancelOrigin::try_origin(origin).map(|_| ()).or_else(ensure_root)?;
+
+			ensure!(min_commission <= max_commission, <Error<T>>::InvalidArguments);
+
+			<MinValidatorCommission<T>>::put(min_commission);
+			<MaxValidatorCommission<T>>::put(max_commission);
+			<MaxCommissionIncrease<T>>::put(max_increase);
+
+			Self::deposit_event(Event::CommissionLimitsSet(min_commission, max_commission, max_increase));
+			Ok(().into())
+		}
+
+		/// Set the share of the staking rewards to restake as they are paid.
+		///
+		/// The share of the rewards of a validator goes to its own bond, the one of a nominator to
+		/// its nomination of the validator the reward was earned with. Whatever cannot be
+		/// restaked, because the validator left or the bond is not allowed, is kept with the
+		/// rewards to withdraw. A zero share stops auto-compounding.
+		#[pallet::call_index(19)]
+		#[pallet::weight(T::WeightInfo::set_auto_compound())]
+		pub fn set_auto_compound(origin: OriginFor<T>, share: Perbill) -> DispatchResultWithPostInfo {
+			let acc = ensure_signed(origin)?;
+
+			ensure!(
+				Self::is_validator(&acc) || Self::is_nominator(&acc),
+				<Error<T>>::NotStaker
+			);
+
+			if share.is_zero() {
+				<AutoCompound<T>>::remove(&acc);
+			} else {
+				<AutoCompound<T>>::insert(&acc, share);
+			}
+
+			Self::deposit_event(Event::AutoCompoundSet(acc, share));
+			Ok(().into())
+		}
+
+		/// Pay out a page of the rewards earned by a validator and its nominators in a past
+		/// session, any account can call it while the session is within `BondedDuration`.
+		///
+		/// The first page pays the validator along with its first `MaxNominatorRewardedPerPage`
+		/// nominators, every following page the next `MaxNominatorRewardedPerPage` ones.
+		///
+		/// Every staker of the page auto-compounding its reward may move its validator in the
+		/// pool, so each of them is charged a pool update.
+		#[pallet::call_index(20)]
+		#[pallet::weight(T::WeightInfo::payout_stakers(T::MaxNominatorRewardedPerPage::get()).saturating_add(
+			Pallet::<T>::pool_update_weight()
+				.saturating_mul(T::MaxNominatorRewardedPerPage::get().saturating_add(1).into())
+		))]
+		pub fn payout_stakers(
+			origin: OriginFor<T>,
+			validator: T::AccountId,
+			session_idx: SessionIndex,
+			page: u32,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+
+			ensure!(
+				session_idx < Self::active_session() && Self::bonded_sessions().contains(&session_idx),
+				<Error<T>>::InvalidSessionIndex
+			);
+
+			let (paid, compounded) = Self::pay_stakers(session_idx, &validator, page)?;
+
+			Ok(Some(
+				T::WeightInfo::payout_stakers(paid)
+					.saturating_add(Self::pool_update_weight().saturating_mul(compounded.into())),
+			)
+			.into())
+		}
+
+		/// Rebond up to `amount` of the funds the validator is unbonding, the newest chunks
+		/// first, back to its bond.
+		#[pallet::call_index(21)]
+		#[pallet::weight(T::WeightInfo::validator_rebond().saturating_add(Pallet::<T>::pool_update_weight()))]
+		pub fn validator_rebond(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResultWithPostInfo {
+			let validator = ensure_signed(origin)?;
+
+			let mut state = <ValidatorState<T>>::get(&validator).ok_or(<Error<T>>::ValidatorDNE)?;
+			ensure!(!state.is_leaving(), <Error<T>>::CannotActivateIfLeaving);
+
+			let before = state.bond;
+			let rebonded = state.rebond(amount);
+			ensure!(!rebonded.is_zero(), <Error<T>>::NoUnlockingChunk);
+			let after = state.bond;
+
+			if state.is_active() {
+				Self::update_validators_pool(validator.clone(), state.bond.saturating_add(state.nomi_bond_total));
+			}
+			<Total<T>>::mutate(|x| *x = x.saturating_add(rebonded));
+
+			// the rebonded funds were locked all along, make sure the lock still covers the
+			// bond along with the chunks left.
+			T::Currency::set_lock(
+				T::StakingLockId::get(),
+				&validator,
+				state.total.saturating_sub(state.nomi_bond_total),
+				WithdrawReasons::all(),
+			);
+
+			<ValidatorState<T>>::insert(&validator, state);
+			Self::deposit_event(Event::ValidatorBondedMore(validator, before, after));
+			Ok(().into())
+		}
+
+		/// Rebond up to `amount` of the funds the nominator is unbonding, the newest chunks
+		/// first, to its nomination of `validator`.
+		#[pallet::call_index(22)]
+		#[pallet::weight(T::WeightInfo::nominator_rebond().saturating_add(Pallet::<T>::pool_update_weight()))]
+		pub fn nominator_rebond(
+			origin: OriginFor<T>,
+			validator: T::AccountId,
+			amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let nominator = ensure_signed(origin)?;
+
+			let mut nominations = <NominatorState<T>>::get(&nominator).ok_or(<Error<T>>::NominatorDNE)?;
+			let mut validator_state = <ValidatorState<T>>::get(&validator).ok_or(<Error<T>>::ValidatorDNE)?;
+
+			let (rebonded, nomination) = nominations
+				.rebond(validator.clone(), amount)
+				.ok_or(<Error<T>>::NominationDNE)?;
+			ensure!(!rebonded.is_zero(), <Error<T>>::NoUnlockingChunk);
+
+			let before = validator_state.bond.saturating_add(validator_state.nomi_bond_total);
+			Self::inc_nominator_bond(&mut validator_state, &nominator, rebonded);
+			let after = validator_state.bond.saturating_add(validator_state.nomi_bond_total);
+			if validator_state.is_active() {
+				Self::update_validators_pool(validator.clone(), after);
+			}
+			<Total<T>>::mutate(|x| *x = x.saturating_add(rebonded));
+
+			T::Currency::set_lock(
+				T::StakingLockId::get(),
+				&nominator,
+				nominations.total,
+				WithdrawReasons::all(),
+			);
+
+			<ValidatorState<T>>::insert(&validator, validator_state);
+			<NominatorState<T>>::insert(&nominator, nominations);
+
+			Self::deposit_event(Event::NominationIncreased(
+				nominator, nomination, validator, before, after,
+			));
+			Ok(().into())
+		}
+
+		/// Report an authority sealing two different blocks for the same Aura slot, along with
+		/// the proof of its key being part of the session of the slot.
+		///
+		/// The offender is slashed by `EquivocationSlash`, the reporter is paid out of the slash
+		/// as for any other offence. An offender is reported once per slot, valid reports are
+		/// free.
+		#[pallet::call_index(23)]
+		#[pallet::weight(T::WeightInfo::report_equivocation(T::MaxNominatorsPerValidator::get()))]
+		pub fn report_equivocation(
+			origin: OriginFor<T>,
+			equivocation_proof: Box<EquivocationProof<T::Header, T::AuthorityId>>,
+			key_owner_proof: T::KeyOwnerProof,
+		) -> DispatchResultWithPostInfo {
+			let reporter = ensure_signed(origin)?;
+
+			ensure!(
+				Self::check_equivocation_proof(&equivocation_proof),
+				<Error<T>>::InvalidEquivocationProof
+			);
+
+			let session_idx = key_owner_proof.session();
+			ensure!(
+				Self::bonded_sessions().contains(&session_idx),
+				<Error<T>>::InvalidSessionIndex
+			);
+			ensure!(
+				Self::is_session_slot(session_idx, equivocation_proof.slot),
+				<Error<T>>::InvalidEquivocationSlot
+			);
+
+			let key = (<T::AuthorityId as RuntimeAppPublic>::ID, equivocation_proof.offender);
+			let offender = T::KeyOwnerProofSystem::check_proof(key, key_owner_proof)
+				.ok_or(<Error<T>>::InvalidKeyOwnershipProof)?;
+			let validator = offender.0.clone();
+			let reported = (validator.clone(), equivocation_proof.slot);
+			ensure!(
+				!<ReportedEquivocations<T>>::contains_key(session_idx, &reported),
+				<Error<T>>::DuplicateEquivocationReport
+			);
+			<ReportedEquivocations<T>>::insert(session_idx, reported, ());
+
+			let nominators = offender.1.nominator_count;
+			Self::deposit_event(Event::EquivocationReported(session_idx, validator, reporter.clone()));
+			Self::handle_offences(
+				&[OffenceDetails {
+					offender,
+					reporters: vec![reporter],
+				}],
+				&[T::EquivocationSlash::get()],
+				session_idx,
+				DisableStrategy::WhenSlashed,
+			);
+
+			Ok((Some(T::WeightInfo::report_equivocation(nominators)), Pays::No).into())
+		}
 	}
 
 	#[pallet::error]
@@ -996,13 +1339,41 @@ pub mod pallet {
 		BadState,
 		/// Error Invalid arguments
 		InvalidArguments,
+		/// Commission is below `MinValidatorCommission` or above `MaxValidatorCommission`.
+		CommissionOutOfBounds,
+		/// Commission is raised by more than `MaxCommissionIncrease`.
+		CommissionIncreaseTooLarge,
+		/// Commission was already raised during the session.
+		CommissionRaisedThisSession,
+		/// More invulnerables than `MaxValidators`.
+		TooManyInvulnerables,
+		/// Exit queue already holds `MaxValidators` validators.
+		ExitQueueFull,
+		/// Account is neither a validator nor a nominator.
+		NotStaker,
+		/// Payout page beyond the nominators of the validator.
+		InvalidPage,
+		/// Payout page already claimed.
+		AlreadyClaimed,
+		/// No unbonding funds to rebond.
+		NoUnlockingChunk,
+		/// Headers not sealed by the offender for the slot.
+		InvalidEquivocationProof,
+		/// Key ownership proof not matching the offender.
+		InvalidKeyOwnershipProof,
+		/// Offender already reported for the slot.
+		DuplicateEquivocationReport,
+		/// No deferred slash of the validator for the session.
+		UnappliedSlashDNE,
+		/// Slot of the equivocation not part of the session of the key ownership proof.
+		InvalidEquivocationSlot,
 	}
 
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
 		/// Updated InVulnerable validator list \[validator_list\],
-		NewInvulnerables(StakingInvulnerables<T>),
+		NewInvulnerables(Vec<T::AccountId>),
 		/// Updated total validators per session \[old, new\],
 		TotalSelectedSet(u32, u32),
 		/// Updated staking config, maximum Validators allowed to join the validators pool
@@ -1101,6 +1472,38 @@ pub mod pallet {
 		/// Staked value unlocked or withdrawn from unlocking queue.
 		/// \[controller_account, amount\].
 		Withdrawn(T::AccountId, BalanceOf<T>),
+		/// Validator changed its commission, effective from the next session selection
+		/// \[account, old_commission, new_commission\]
+		ValidatorCommissionSet(T::AccountId, Perbill, Perbill),
+		/// Updated validators commission limits
+		/// \[min_commission, max_commission, max_increase_per_session\]
+		CommissionLimitsSet(Perbill, Perbill, Perbill),
+		/// Staker set the share of its rewards to restake as they are paid
+		/// \[account, share\]
+		AutoCompoundSet(T::AccountId, Perbill),
+		/// Share of a reward restaked on the validator it was earned with
+		/// \[account, validator_account, restaked_value\]
+		RewardCompounded(T::AccountId, T::AccountId, BalanceOf<T>),
+		/// Validator authored less blocks than expected in the session
+		/// \[session_index, account, authored_blocks, expected_blocks, poor_sessions_in_a_row\]
+		ValidatorPerformedPoorly(SessionIndex, T::AccountId, u32, u32, u32),
+		/// Validator chilled after performing poorly for `MaxPoorSessions` sessions in a row
+		/// \[session_index, account\]
+		ValidatorChilled(SessionIndex, T::AccountId),
+		/// Validator reported for sealing two blocks for the same slot
+		/// \[session_index, offender_account, reporter_account\]
+		EquivocationReported(SessionIndex, T::AccountId, T::AccountId),
+		/// Deferred slash of a validator reduced by governance \[apply_at_session, validator_account,
+		/// reduction, (before, after) own slash, (before, after) nominators slashes, (before, after)
+		/// reporters payout\]
+		DeferredSlashReduced(
+			SessionIndex,
+			T::AccountId,
+			Perbill,
+			(BalanceOf<T>, BalanceOf<T>),
+			(BalanceOf<T>, BalanceOf<T>),
+			(BalanceOf<T>, BalanceOf<T>),
+		),
 	}
 
 	#[pallet::storage]
@@ -1148,22 +1551,35 @@ pub mod pallet {
 	#[pallet::getter(fn active_session)]
 	pub(crate) type ActiveSession<T: Config> = StorageValue<_, SessionIndex, ValueQuery>;
 
-	/// Commission percent taken off of rewards for all validators
+	/// Commission percent validators start with when joining the pool
 	#[pallet::storage]
 	#[pallet::getter(fn validator_fee)]
 	pub(crate) type ValidatorFee<T: Config> = StorageValue<_, Perbill, ValueQuery>;
 
-	/// Get validator state associated with an account if account is collating else None
+	/// Minimum commission validators may charge
+	#[pallet::storage]
+	#[pallet::getter(fn min_validator_commission)]
+	pub(crate) type MinValidatorCommission<T: Config> = StorageValue<_, Perbill, ValueQuery>;
+
+	/// Maximum commission validators may charge
+	#[pallet::storage]
+	#[pallet::getter(fn max_validator_commission)]
+	pub(crate) type MaxValidatorCommission<T: Config> = StorageValue<_, Perbill, ValueQuery>;
+
+	/// Maximum commission raise validators may do per session
+	#[pallet::storage]
+	#[pallet::getter(fn max_commission_increase)]
+	pub(crate) type MaxCommissionIncrease<T: Config> = StorageValue<_, Perbill, ValueQuery>;
+
+	/// Get validator state associated with an account if account is collating else None
 	#[pallet::storage]
 	#[pallet::getter(fn validator_state)]
-	pub(crate) type ValidatorState<T: Config> =
-		StorageMap<_, Twox64Concat, T::AccountId, Validator<T::AccountId, BalanceOf<T>>, OptionQuery>;
+	pub(crate) type ValidatorState<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, ValidatorOf<T>, OptionQuery>;
 
 	/// Get nominator state associated with an account if account is nominating else None
 	#[pallet::storage]
 	#[pallet::getter(fn nominator_state)]
-	pub(crate) type NominatorState<T: Config> =
-		StorageMap<_, Twox64Concat, T::AccountId, Nominator<T::AccountId, BalanceOf<T>>, OptionQuery>;
+	pub(crate) type NominatorState<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, NominatorOf<T>, OptionQuery>;
 
 	/// The total validators selected every round
 	#[pallet::storage]
@@ -1173,29 +1589,49 @@ pub mod pallet {
 	/// The validators selected for the current round
 	#[pallet::storage]
 	#[pallet::getter(fn selected_validators)]
-	pub(crate) type SelectedValidators<T: Config> = StorageValue<_, Vec<T::AccountId>, ValueQuery>;
+	pub(crate) type SelectedValidators<T: Config> =
+		StorageValue<_, BoundedVec<T::AccountId, T::MaxValidators>, ValueQuery>;
 
-	/// The pool of validator validators, each with their total backing stake
+	/// The pool of validator validators, each with their total backing stake, linked by
+	/// decreasing stake
 	#[pallet::storage]
-	#[pallet::getter(fn validator_pool)]
 	pub(crate) type ValidatorPool<T: Config> =
-		StorageValue<_, OrderedSet<Bond<T::AccountId, BalanceOf<T>>>, ValueQuery>;
+		CountedStorageMap<_, Twox64Concat, T::AccountId, PoolNode<T::AccountId, BalanceOf<T>>, OptionQuery>;
+
+	/// The validator with the highest stake in the pool
+	#[pallet::storage]
+	#[pallet::getter(fn validator_pool_head)]
+	pub(crate) type ValidatorPoolHead<T: Config> = StorageValue<_, T::AccountId, OptionQuery>;
+
+	/// Bond of a nominator on a validator, keyed by validator then nominator
+	#[pallet::storage]
+	#[pallet::getter(fn nominator_bond)]
+	pub(crate) type NominatorBonds<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, T::AccountId, BalanceOf<T>, OptionQuery>;
 
 	/// A queue of validators awaiting exit `BondedDuration` delay after request
 	#[pallet::storage]
 	#[pallet::getter(fn exit_queue)]
-	pub(crate) type ExitQueue<T: Config> = StorageValue<_, OrderedSet<Bond<T::AccountId, SessionIndex>>, ValueQuery>;
+	pub(crate) type ExitQueue<T: Config> =
+		StorageValue<_, OrderedSet<Bond<T::AccountId, SessionIndex>, T::MaxValidators>, ValueQuery>;
 
 	/// Snapshot of validator nomination stake at the start of the round
 	#[pallet::storage]
 	#[pallet::getter(fn at_stake)]
-	pub(crate) type AtStake<T: Config> = StorageDoubleMap<
+	pub(crate) type AtStake<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, SessionIndex, Twox64Concat, T::AccountId, SnapshotOf<T>, ValueQuery>;
+
+	/// Nominators at stake with a validator in the round, keyed by validator and page. A page
+	/// holds `MaxNominatorRewardedPerPage` of them and is paid out by the page of the same index
+	#[pallet::storage]
+	#[pallet::getter(fn at_stake_page)]
+	pub(crate) type AtStakePage<T: Config> = StorageDoubleMap<
 		_,
 		Twox64Concat,
 		SessionIndex,
 		Twox64Concat,
-		T::AccountId,
-		ValidatorSnapshot<T::AccountId, BalanceOf<T>>,
+		(T::AccountId, u32),
+		ExposurePageOf<T>,
 		ValueQuery,
 	>;
 
@@ -1216,6 +1652,19 @@ pub mod pallet {
 	pub(crate) type SessionValidatorReward<T: Config> =
 		StorageMap<_, Twox64Concat, SessionIndex, BalanceOf<T>, ValueQuery>;
 
+	/// Payout pages already claimed for each validator per session
+	#[pallet::storage]
+	#[pallet::getter(fn claimed_rewards)]
+	pub(crate) type ClaimedRewards<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		SessionIndex,
+		Twox64Concat,
+		T::AccountId,
+		BoundedVec<u32, MaxPayoutPages<T>>,
+		ValueQuery,
+	>;
+
 	/// Total points awarded to validator for block production in the round
 	#[pallet::storage]
 	#[pallet::getter(fn points)]
@@ -1230,8 +1679,39 @@ pub mod pallet {
 	/// stakers nodle rewards per session
 	#[pallet::storage]
 	#[pallet::getter(fn stake_rewards)]
-	pub(crate) type StakeRewards<T: Config> =
-		StorageMap<_, Twox64Concat, T::AccountId, Vec<StakeReward<BalanceOf<T>>>, ValueQuery>;
+	pub(crate) type StakeRewards<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		BoundedVec<StakeReward<BalanceOf<T>>, T::MaxStakeRewards>,
+		ValueQuery,
+	>;
+
+	/// Share of the rewards of a staker restaked as they are paid
+	#[pallet::storage]
+	#[pallet::getter(fn auto_compound)]
+	pub(crate) type AutoCompound<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, Perbill, ValueQuery>;
+
+	/// Blocks authored by the validators per session
+	#[pallet::storage]
+	#[pallet::getter(fn performance)]
+	pub(crate) type Performance<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, SessionIndex, Twox64Concat, T::AccountId, SessionPerformance, ValueQuery>;
+
+	/// Aura slot the session started at
+	#[pallet::storage]
+	#[pallet::getter(fn session_start_slot)]
+	pub(crate) type SessionStartSlot<T: Config> = StorageMap<_, Twox64Concat, SessionIndex, Slot, OptionQuery>;
+
+	/// Sessions in a row the validator performed poorly
+	#[pallet::storage]
+	#[pallet::getter(fn poor_sessions)]
+	pub(crate) type PoorSessions<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, u32, ValueQuery>;
+
+	/// Validators reported for equivocating per session, along with the slot they equivocated for
+	#[pallet::storage]
+	pub(crate) type ReportedEquivocations<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, SessionIndex, Twox64Concat, (T::AccountId, Slot), (), OptionQuery>;
 
 	/// The percentage of the slash that is distributed to reporters.
 	///
@@ -1244,7 +1724,7 @@ pub mod pallet {
 	#[pallet::storage]
 	#[pallet::getter(fn slashing_spans)]
 	pub(crate) type SlashingSpans<T: Config> =
-		StorageMap<_, Twox64Concat, T::AccountId, slashing::SlashingSpans, OptionQuery>;
+		StorageMap<_, Twox64Concat, T::AccountId, slashing::SlashingSpans<T::MaxSlashingSpans>, OptionQuery>;
 
 	/// Snapshot of validator slash state
 	#[pallet::storage]
@@ -1277,12 +1757,17 @@ pub mod pallet {
 	#[pallet::storage]
 	#[pallet::getter(fn unapplied_slashes)]
 	pub(crate) type UnappliedSlashes<T: Config> =
-		StorageMap<_, Twox64Concat, SessionIndex, Vec<UnappliedSlash<T::AccountId, BalanceOf<T>>>, ValueQuery>;
+		StorageMap<_, Twox64Concat, SessionIndex, BoundedVec<UnappliedSlashOf<T>, T::MaxValidators>, ValueQuery>;
 
 	/// A mapping of still-bonded sessions
 	#[pallet::storage]
 	#[pallet::getter(fn bonded_sessions)]
-	pub(crate) type BondedSessions<T: Config> = StorageValue<_, Vec<SessionIndex>, ValueQuery>;
+	pub(crate) type BondedSessions<T: Config> =
+		StorageValue<_, BoundedVec<SessionIndex, MaxBondedSessions<T>>, ValueQuery>;
+
+	/// Progress of the multi-block migration to the bounded storage, if still ongoing
+	#[pallet::storage]
+	pub(crate) type MigrationCursor<T: Config> = StorageValue<_, migrations::BoundedStorageCursor, OptionQuery>;
 
 	#[allow(clippy::type_complexity)]
 	#[pallet::genesis_config]
@@ -1311,28 +1796,19 @@ pub mod pallet {
 				duplicate_invulnerables.len() == self.invulnerables.len(),
 				"duplicate invulnerables in genesis."
 			);
-			<Invulnerables<T>>::put(&self.invulnerables);
+			let invulnerables: StakingInvulnerables<T> = self
+				.invulnerables
+				.clone()
+				.try_into()
+				.expect("too many invulnerables in genesis.");
+			<Invulnerables<T>>::put(&invulnerables);
+			<StorageVersion<T>>::put(Releases::V4);
 
 			// Ensure balance is >= ED
 			let imbalance = T::Currency::issue(T::Currency::minimum_balance());
 			T::Currency::resolve_creating(&T::PalletId::get().into_account_truncating(), imbalance);
 
-			// Set collator commission to default config
-			<ValidatorFee<T>>::put(T::DefaultValidatorFee::get());
-			// Set total selected validators to minimum config
-			<TotalSelected<T>>::put(T::MinSelectedValidators::get());
-			// Set default slash reward fraction
-			<SlashRewardProportion<T>>::put(T::DefaultSlashRewardProportion::get());
-			// Maximum Validators allowed to join the validators pool
-			<StakingMaxValidators<T>>::put(T::DefaultStakingMaxValidators::get());
-			// Minimum stake required for any account to be in `SelectedCandidates` for the session
-			<StakingMinStakeSessionSelection<T>>::put(T::DefaultStakingMinStakeSessionSelection::get());
-			// Minimum stake required for any account to be a validator candidate
-			<StakingMinValidatorBond<T>>::put(T::DefaultStakingMinValidatorBond::get());
-			// Set default min nomination stake value
-			<StakingMinNominationChillThreshold<T>>::put(T::DefaultStakingMinNominationChillThreshold::get());
-			// Staking config minimum nominator total bond
-			<StakingMinNominatorTotalBond<T>>::put(T::DefaultStakingMinNominatorTotalBond::get());
+			<Pallet<T>>::put_default_staking_config();
 
 			log::trace!(
 				"GenesisBuild:[{:#?}] - Staking Cfg ([{:#?}],[{:#?}],[{:#?}],[{:#?}],[{:#?}])",
@@ -1405,6 +1881,38 @@ pub mod pallet {
 	}
 
 	impl<T: Config> Pallet<T> {
+		/// Initialize the staking configuration from the `Default*` config values.
+		pub(crate) fn put_default_staking_config() {
+			// Set collator commission to default config
+			<ValidatorFee<T>>::put(T::DefaultValidatorFee::get());
+			// Set collator commission bounds and rate limit to default config
+			<MinValidatorCommission<T>>::put(T::DefaultMinValidatorCommission::get());
+			<MaxValidatorCommission<T>>::put(T::DefaultMaxValidatorCommission::get());
+			<MaxCommissionIncrease<T>>::put(T::DefaultMaxCommissionIncrease::get());
+			// Set total selected validators to minimum config
+			<TotalSelected<T>>::put(T::MinSelectedValidators::get());
+			// Set default slash reward fraction
+			<SlashRewardProportion<T>>::put(T::DefaultSlashRewardProportion::get());
+			// Maximum Validators allowed to join the validators pool
+			<StakingMaxValidators<T>>::put(T::DefaultStakingMaxValidators::get().min(T::MaxValidators::get()));
+			// Minimum stake required for any account to be in `SelectedCandidates` for the session
+			<StakingMinStakeSessionSelection<T>>::put(T::DefaultStakingMinStakeSessionSelection::get());
+			// Minimum stake required for any account to be a validator candidate
+			<StakingMinValidatorBond<T>>::put(T::DefaultStakingMinValidatorBond::get());
+			// Set default min nomination stake value
+			<StakingMinNominationChillThreshold<T>>::put(T::DefaultStakingMinNominationChillThreshold::get());
+			// Staking config minimum nominator total bond
+			<StakingMinNominatorTotalBond<T>>::put(T::DefaultStakingMinNominatorTotalBond::get());
+		}
+
+		/// Clamp the given commission within the `MinValidatorCommission` and
+		/// `MaxValidatorCommission` bounds.
+		pub(crate) fn commission_within_bounds(commission: Perbill) -> Perbill {
+			commission
+				.max(<MinValidatorCommission<T>>::get())
+				.min(<MaxValidatorCommission<T>>::get())
+		}
+
 		pub(crate) fn is_validator(acc: &T::AccountId) -> bool {
 			<ValidatorState<T>>::get(acc).is_some()
 		}
@@ -1413,6 +1921,11 @@ pub mod pallet {
 			<NominatorState<T>>::get(acc).is_some()
 		}
 		// ensure validator is active before calling
+		/// Upper bound of the reads to move a validator in the pool, on top of the benchmarked
+		/// weights since the walk depends on how far the validator moves.
+		pub(crate) fn pool_update_weight() -> Weight {
+			T::DbWeight::get().reads(T::MaxValidators::get().into())
+		}
 		pub fn update_validators_pool(validator: T::AccountId, total: BalanceOf<T>) {
 			log::trace!(
 				"update_validators_pool:[{:#?}] | Own[{:#?}] | Tot[{:#?}]",
@@ -1420,21 +1933,95 @@ pub mod pallet {
 				validator,
 				total,
 			);
-			<ValidatorPool<T>>::mutate(|validators| {
-				validators.remove(&Bond::from_owner(validator.clone()));
-				validators.insert(Bond {
-					owner: validator,
-					amount: total,
-				});
-			});
+			if pool::upsert::<T>(&validator, total).is_err() {
+				log::error!(
+					"update_validators_pool:[{:#?}] | Pool full, Own[{:#?}] left out",
+					line!(),
+					validator,
+				);
+			}
 		}
 		// ensure validator is active before calling
 		pub fn remove_from_validators_pool(validator: T::AccountId) {
 			log::trace!("remove_from_validators_pool:[{:#?}] | Own[{:#?}]", line!(), validator);
-			<ValidatorPool<T>>::mutate(|validators| {
-				validators.remove(&Bond::from_owner(validator.clone()));
+			pool::remove::<T>(&validator);
+		}
+		/// The validators pool along with their total backing stake, highest stake first.
+		pub fn validator_pool() -> Vec<Bond<T::AccountId, BalanceOf<T>>> {
+			pool::iter::<T>()
+				.map(|(owner, amount)| Bond { owner, amount })
+				.collect()
+		}
+		/// Raise the bond of `nominator` on the validator of `state` by `more`, if they nominate it.
+		pub(crate) fn inc_nominator_bond(state: &mut ValidatorOf<T>, nominator: &T::AccountId, more: BalanceOf<T>) {
+			<NominatorBonds<T>>::mutate_exists(&state.id, nominator, |maybe_bond| {
+				if let Some(bond) = maybe_bond {
+					*bond = bond.saturating_add(more);
+					state.inc_nominator(more);
+				}
 			});
 		}
+		/// Lower the bond of `nominator` on the validator of `state` by `less`, if they nominate it.
+		pub(crate) fn dec_nominator_bond(state: &mut ValidatorOf<T>, nominator: &T::AccountId, less: BalanceOf<T>) {
+			<NominatorBonds<T>>::mutate_exists(&state.id, nominator, |maybe_bond| {
+				if let Some(bond) = maybe_bond {
+					*bond = bond.saturating_sub(less);
+					state.dec_nominator(less);
+				}
+			});
+		}
+		/// Snapshot the validator of `state` at stake in `session_idx` along with the bonds of
+		/// its nominators.
+		pub(crate) fn store_exposure(session_idx: SessionIndex, state: ValidatorOf<T>) {
+			let mut nominators = <NominatorBonds<T>>::iter_prefix(&state.id)
+				.map(|(owner, amount)| Bond { owner, amount })
+				.collect::<Vec<Bond<T::AccountId, BalanceOf<T>>>>();
+			nominators.sort();
+			// there are at most `MaxNominatorsPerValidator` of them.
+			nominators.truncate(T::MaxNominatorsPerValidator::get() as usize);
+			let nominator_count = Self::store_exposure_pages(session_idx, &state.id, nominators);
+			let snapshot = ValidatorSnapshot {
+				bond: state.bond,
+				total: state.bond.saturating_add(state.nomi_bond_total),
+				// bounds may have changed since the validator set its commission
+				commission: Self::commission_within_bounds(state.commission),
+				nominator_count,
+			};
+			<AtStake<T>>::insert(session_idx, &state.id, snapshot);
+		}
+		/// Store the `nominators` at stake with `validator` in `session_idx` split in pages,
+		/// returns how many they are.
+		pub(crate) fn store_exposure_pages(
+			session_idx: SessionIndex,
+			validator: &T::AccountId,
+			nominators: Vec<Bond<T::AccountId, BalanceOf<T>>>,
+		) -> u32 {
+			let per_page = T::MaxNominatorRewardedPerPage::get().max(1) as usize;
+			for (page, nominators) in nominators.chunks(per_page).enumerate() {
+				<AtStakePage<T>>::insert(
+					session_idx,
+					(validator, page as u32),
+					BoundedVec::truncate_from(nominators.to_vec()),
+				);
+			}
+			nominators.len() as u32
+		}
+		/// Number of `AtStakePage` pages holding `nominator_count` nominators.
+		pub(crate) fn exposure_pages(nominator_count: u32) -> u32 {
+			let per_page = T::MaxNominatorRewardedPerPage::get().max(1);
+			nominator_count.saturating_add(per_page - 1) / per_page
+		}
+		/// The `nominator_count` nominators at stake with `validator` in `session_idx`, in the
+		/// order they are paid out.
+		pub(crate) fn exposed_nominators(
+			session_idx: SessionIndex,
+			validator: &T::AccountId,
+			nominator_count: u32,
+		) -> Vec<Bond<T::AccountId, BalanceOf<T>>> {
+			(0..Self::exposure_pages(nominator_count))
+				.flat_map(|page| <AtStakePage<T>>::get(session_idx, (validator, page)).into_inner())
+				.collect()
+		}
 		pub(crate) fn validator_deactivate(controller: &T::AccountId) {
 			log::trace!("validator_deactivate:[{:#?}] - Acc[{:#?}]", line!(), controller);
 			<ValidatorState<T>>::mutate(controller, |maybe_validator| {
@@ -1444,32 +2031,152 @@ pub mod pallet {
 				}
 			});
 		}
+		/// Add `more` to the own bond of `validator`.
+		pub(crate) fn validator_bonds_more(validator: T::AccountId, more: BalanceOf<T>) -> DispatchResult {
+			ensure!(Self::is_validator(&validator), <Error<T>>::ValidatorDNE);
+
+			let valid_state = Self::validator_state(&validator).ok_or(<Error<T>>::ValidatorDNE)?;
+
+			ensure!(!valid_state.is_leaving(), <Error<T>>::CannotActivateIfLeaving,);
+
+			let validator_free_balance = T::Currency::free_balance(&validator);
+			ensure!(
+				validator_free_balance >= valid_state.bond.saturating_add(more),
+				<Error<T>>::InsufficientBalance,
+			);
+
+			ensure!(
+				valid_state.bond.saturating_add(more) > Self::staking_min_validator_bond(),
+				<Error<T>>::ValidatorBondBelowMin,
+			);
+
+			<ValidatorState<T>>::mutate(validator.clone(), |maybe_validator| {
+				if let Some(state) = maybe_validator {
+					let before = state.bond;
+					state.bond_more(more);
+					T::Currency::set_lock(T::StakingLockId::get(), &validator, state.bond, WithdrawReasons::all());
+					let after = state.bond;
+					state.go_online();
+					if state.is_active() {
+						Self::update_validators_pool(
+							validator.clone(),
+							state.bond.saturating_add(state.nomi_bond_total),
+						);
+					}
+					<Total<T>>::mutate(|x| *x = x.saturating_add(more));
+					Self::deposit_event(Event::ValidatorBondedMore(validator.clone(), before, after));
+				}
+			});
+
+			Self::validator_stake_reconciliation(&validator);
+
+			Ok(())
+		}
+		/// Add `more` to the bond of `nominator` on `validator`, along with its frozen bond if
+		/// `unfreeze_bond` is set.
+		pub(crate) fn nominator_bonds_more(
+			nominator: T::AccountId,
+			validator: T::AccountId,
+			more: BalanceOf<T>,
+			unfreeze_bond: bool,
+		) -> DispatchResult {
+			let mut nominations = <NominatorState<T>>::get(&nominator).ok_or(<Error<T>>::NominatorDNE)?;
+			let mut validator_state = <ValidatorState<T>>::get(&validator).ok_or(<Error<T>>::ValidatorDNE)?;
+
+			let more = if unfreeze_bond {
+				more.saturating_add(nominations.frozen_bond)
+			} else {
+				more
+			};
+
+			let new_nomination_bond = nominations
+				.inc_nomination(validator.clone(), more, unfreeze_bond)
+				.ok_or(<Error<T>>::NominationDNE)?;
+
+			ensure!(
+				new_nomination_bond >= <StakingMinNominationChillThreshold<T>>::get(),
+				<Error<T>>::NominationBelowMin
+			);
+
+			let nominator_free_balance = T::Currency::free_balance(&nominator);
+			ensure!(
+				nominator_free_balance >= nominations.total,
+				<Error<T>>::InsufficientBalance
+			);
+
+			T::Currency::set_lock(
+				T::StakingLockId::get(),
+				&nominator,
+				nominations.total,
+				WithdrawReasons::all(),
+			);
+
+			let before = validator_state.total;
+			Self::inc_nominator_bond(&mut validator_state, &nominator, more);
+			let after = validator_state.total;
+			<Total<T>>::mutate(|x| *x = x.saturating_add(more));
+			if validator_state.is_active() {
+				Self::update_validators_pool(validator.clone(), validator_state.total);
+			}
+			<ValidatorState<T>>::insert(&validator, validator_state);
+			<NominatorState<T>>::insert(&nominator, nominations);
+			Self::deposit_event(Event::NominationIncreased(
+				nominator,
+				new_nomination_bond,
+				validator,
+				before,
+				after,
+			));
+			Ok(())
+		}
+		/// Restake the share of `reward` that `staker` chose to auto-compound, on the validator
+		/// the reward was earned with. Returns what is left of the reward to withdraw and whether
+		/// a restake was attempted, which may have moved the validator in the pool.
+		fn compound_reward(
+			staker: &T::AccountId,
+			validator: &T::AccountId,
+			reward: BalanceOf<T>,
+		) -> (BalanceOf<T>, bool) {
+			let restake = <AutoCompound<T>>::get(staker) * reward;
+			if restake.is_zero() {
+				return (reward, false);
+			}
+
+			let compounded = with_storage_layer(|| -> DispatchResult {
+				let _ = T::Currency::deposit_into_existing(staker, restake)?;
+				if staker == validator {
+					Self::validator_bonds_more(staker.clone(), restake)
+				} else {
+					Self::nominator_bonds_more(staker.clone(), validator.clone(), restake, false)
+				}
+			});
+
+			match compounded {
+				Ok(()) => {
+					Self::deposit_event(Event::RewardCompounded(staker.clone(), validator.clone(), restake));
+					(reward.saturating_sub(restake), true)
+				}
+				Err(err) => {
+					log::debug!(
+						"compound_reward:[{:#?}] | Acc[{:#?}] | Val[{:#?}] | kept with the rewards [{:#?}]",
+						line!(),
+						staker,
+						validator,
+						err,
+					);
+					(reward, true)
+				}
+			}
+		}
 		fn nominator_leaves_validator(nominator: T::AccountId, validator: T::AccountId) -> DispatchResultWithPostInfo {
 			<ValidatorState<T>>::try_mutate_exists(
 				validator.clone(),
 				|maybe_validator| -> DispatchResultWithPostInfo {
-					let mut state = maybe_validator.as_mut().ok_or(<Error<T>>::ValidatorDNE)?;
-					let mut exists: Option<BalanceOf<T>> = None;
-					let noms = state
-						.clone()
-						.nominators
-						.0
-						.into_iter()
-						.filter_map(|nom| {
-							if nom.owner != nominator {
-								Some(nom)
-							} else {
-								exists = Some(nom.amount);
-								None
-							}
-						})
-						.collect();
-					let nominator_stake = exists.ok_or(<Error<T>>::ValidatorDNE)?;
-					let nominators = OrderedSet::from(noms);
-
-					state.nominators = nominators;
-					state.nomi_bond_total = state.nomi_bond_total.saturating_sub(nominator_stake);
-					state.total = state.total.saturating_sub(nominator_stake);
+					let state = maybe_validator.as_mut().ok_or(<Error<T>>::ValidatorDNE)?;
+					let nominator_stake =
+						<NominatorBonds<T>>::take(&validator, &nominator).ok_or(<Error<T>>::ValidatorDNE)?;
+
+					state.rm_nominator(nominator_stake);
 					if state.is_active() {
 						Self::update_validators_pool(validator.clone(), state.total);
 					}
@@ -1493,7 +2200,7 @@ pub mod pallet {
 			let mut nominator_state = <NominatorState<T>>::get(&acc).ok_or(<Error<T>>::NominatorDNE)?;
 
 			ensure!(
-				nominator_state.unlocking.len() < T::MaxChunkUnlock::get(),
+				(nominator_state.unlocking.len() as u32) < T::MaxChunkUnlock::get(),
 				<Error<T>>::NoMoreChunks,
 			);
 
@@ -1513,10 +2220,13 @@ pub mod pallet {
 
 			<Total<T>>::mutate(|x| *x = x.saturating_sub(old_active_bond.saturating_sub(remaining)));
 
-			nominator_state.unlocking.push(UnlockChunk {
-				value: old_active_bond.saturating_sub(nominator_state.active_bond),
-				session_idx: Self::active_session().saturating_add(T::BondedDuration::get()),
-			});
+			nominator_state
+				.unlocking
+				.try_push(UnlockChunk {
+					value: old_active_bond.saturating_sub(nominator_state.active_bond),
+					session_idx: Self::active_session().saturating_add(T::BondedDuration::get()),
+				})
+				.map_err(|_| <Error<T>>::NoMoreChunks)?;
 
 			<NominatorState<T>>::insert(acc, nominator_state);
 
@@ -1529,10 +2239,16 @@ pub mod pallet {
 					let old_active_bond = nominator_state.active_bond;
 
 					if let Some(_remaining) = nominator_state.rm_nomination(validator.clone(), false) {
-						nominator_state.unlocking.push(UnlockChunk {
+						let chunk = UnlockChunk {
 							value: old_active_bond.saturating_sub(nominator_state.active_bond),
 							session_idx: Self::active_session(),
-						});
+						};
+						if let Err(chunk) = nominator_state.unlocking.try_push(chunk) {
+							// no room for another chunk, the stake unlocks along with the earliest one.
+							if let Some(earliest) = nominator_state.unlocking.iter_mut().min_by_key(|c| c.session_idx) {
+								earliest.value = earliest.value.saturating_add(chunk.value);
+							}
+						}
 
 						Self::deposit_event(Event::NominatorLeftValidator(
 							nominator_acc.clone(),
@@ -1563,167 +2279,194 @@ pub mod pallet {
 			});
 		}
 
-		pub(crate) fn pay_stakers(next: SessionIndex) {
-			log::trace!("pay_stakers:[{:#?}] - Sess-idx[{:#?}]", line!(), next);
+		/// Pay `page` of the rewards earned by `val` in `session_idx`, returns the number of
+		/// nominators paid and the number of stakers whose reward was auto-compounded.
+		pub(crate) fn pay_stakers(
+			session_idx: SessionIndex,
+			val: &T::AccountId,
+			page: u32,
+		) -> Result<(u32, u32), DispatchError> {
+			log::trace!(
+				"pay_stakers:[{:#?}] - Sess-idx[{:#?}] | [{:#?}] | Page[{:#?}]",
+				line!(),
+				session_idx,
+				val,
+				page
+			);
 
-			let mint = |amt: BalanceOf<T>, to: T::AccountId| {
+			let mut compounded = 0u32;
+			let mut mint = |amt: BalanceOf<T>, to: T::AccountId| {
 				if amt > T::Currency::minimum_balance() {
+					let (amt, restaked) = Self::compound_reward(&to, val, amt);
+					if restaked {
+						compounded = compounded.saturating_add(1);
+					}
+					if amt.is_zero() {
+						return;
+					}
 					<StakeRewards<T>>::mutate(&to, |rewards| {
-						rewards.push(StakeReward {
-							session_idx: next,
+						let reward = StakeReward {
+							session_idx,
 							value: amt,
-						});
-						// *rewards = rewards;
+						};
+						if let Err(reward) = rewards.try_push(reward) {
+							// no room for another reward, add it up to the latest one.
+							if let Some(latest) = rewards.iter_mut().last() {
+								latest.value = latest.value.saturating_add(reward.value);
+								latest.session_idx = reward.session_idx;
+							}
+						}
 						Self::deposit_event(Event::StakeReward(to.clone(), amt));
 					});
 				}
 			};
 
-			let validator_fee = <ValidatorFee<T>>::get();
-			let total = <Points<T>>::get(next);
-			// let total_staked = <Staked<T>>::get(next);
+			let pts = <AwardedPts<T>>::get(session_idx, val);
+			let total = <Points<T>>::get(session_idx);
+			// let total_staked = <Staked<T>>::get(session_idx);
 			// let issuance = Self::compute_issuance(total_staked);
-			let issuance = Self::session_validator_reward(next);
-			for (val, pts) in <AwardedPts<T>>::iter_prefix(next) {
-				let pct_due = Perbill::from_rational(pts, total);
-				let mut amt_due = pct_due * issuance;
+			let issuance = Self::session_validator_reward(session_idx);
+			let pct_due = Perbill::from_rational(pts, total);
+			let mut amt_due = pct_due * issuance;
 
-				log::trace!(
-					"pay_stakers:[{:#?}] - L1 [{:#?}] | [{:#?}] | [{:#?}]",
-					line!(),
-					total,
-					issuance,
-					pct_due
-				);
+			log::trace!(
+				"pay_stakers:[{:#?}] - L1 [{:#?}] | [{:#?}] | [{:#?}] | [{:#?}]",
+				line!(),
+				total,
+				issuance,
+				pct_due,
+				amt_due
+			);
 
-				log::trace!(
-					"pay_stakers:[{:#?}] - L2 [{:#?}] | [{:#?}] | [{:#?}]",
-					line!(),
-					val,
-					pts,
-					amt_due
-				);
+			ensure!(
+				!pts.is_zero() && amt_due > T::Currency::minimum_balance(),
+				<Error<T>>::RewardsDNE
+			);
 
-				if amt_due <= T::Currency::minimum_balance() {
-					continue;
-				}
-				// Take the snapshot of block author and nominations
-				// let state = <AtStake<T>>::take(next, &val);
-				let state = Self::at_stake(next, &val);
-
-				if state.nominators.is_empty() {
-					// solo collator with no nominators
-					mint(amt_due, val.clone());
-					log::trace!("pay_stakers:[{:#?}] - L3 Solo Mode", line!());
-				} else {
-					let val_pct = Perbill::from_rational(state.bond, state.total);
-					let commission = validator_fee * amt_due;
-					let val_due = if commission > T::Currency::minimum_balance() {
-						amt_due = amt_due.saturating_sub(commission);
-						(val_pct * amt_due).saturating_add(commission)
-					} else {
-						// commission is negligible so not applied
-						val_pct * amt_due
-					};
+			// Take the snapshot of block author and nominations
+			let state = Self::at_stake(session_idx, val);
 
-					log::trace!(
-						"pay_stakers:[{:#?}] - L4 [{:#?}] | [{:#?}] | [{:#?}]",
-						line!(),
-						validator_fee,
-						val_due,
-						amt_due,
-					);
+			// a validator without nominators is still paid by a first page
+			let pages = Self::exposure_pages(state.nominator_count).max(1);
+			ensure!(page < pages, <Error<T>>::InvalidPage);
 
-					mint(val_due, val.clone());
-					// pay nominators due portion
-					for Bond { owner, amount } in state.nominators {
-						let percent = Perbill::from_rational(amount, state.total);
-						let due = percent * amt_due;
-						mint(due, owner);
-					}
-				}
+			<ClaimedRewards<T>>::try_mutate(session_idx, val, |claimed| -> DispatchResult {
+				ensure!(!claimed.contains(&page), <Error<T>>::AlreadyClaimed);
+				claimed.try_push(page).map_err(|_| <Error<T>>::InvalidPage)?;
+				Ok(())
+			})?;
+
+			if state.nominator_count.is_zero() {
+				// solo collator with no nominators
+				mint(amt_due, val.clone());
+				log::trace!("pay_stakers:[{:#?}] - L3 Solo Mode", line!());
+				return Ok((0, compounded));
 			}
+
+			let val_pct = Perbill::from_rational(state.bond, state.total);
+			let commission = state.commission * amt_due;
+			let val_due = if commission > T::Currency::minimum_balance() {
+				amt_due = amt_due.saturating_sub(commission);
+				(val_pct * amt_due).saturating_add(commission)
+			} else {
+				// commission is negligible so not applied
+				val_pct * amt_due
+			};
+
+			log::trace!(
+				"pay_stakers:[{:#?}] - L4 [{:#?}] | [{:#?}] | [{:#?}]",
+				line!(),
+				state.commission,
+				val_due,
+				amt_due,
+			);
+
+			if page.is_zero() {
+				mint(val_due, val.clone());
+			}
+
+			// pay the nominators of the page their due portion
+			let mut paid = 0u32;
+			for Bond { owner, amount } in <AtStakePage<T>>::get(session_idx, (val, page)) {
+				let percent = Perbill::from_rational(amount, state.total);
+				let due = percent * amt_due;
+				mint(due, owner);
+				paid = paid.saturating_add(1);
+			}
+
+			Ok((paid, compounded))
 		}
 		pub(crate) fn execute_delayed_validator_exits(next: SessionIndex) {
-			let remain_exits = <ExitQueue<T>>::get()
+			let mut exits = <ExitQueue<T>>::get();
+			let due_exits = exits
 				.0
-				.into_iter()
-				.filter_map(|x| {
-					if x.amount > next {
-						Some(x)
-					} else {
-						if let Some(state) = <ValidatorState<T>>::get(&x.owner) {
-							// revoke all nominations
-							for bond in state.nominators.0 {
-								Self::validator_revokes_nomination(bond.owner.clone(), x.owner.clone());
-							}
-							// return stake to validator
-							let mut unlock_chunk_total: BalanceOf<T> = Zero::zero();
-							let _ = state.unlocking.iter().map(|chunk| {
-								unlock_chunk_total = unlock_chunk_total.saturating_add(chunk.value);
-							});
+				.iter()
+				.filter(|x| x.amount <= next)
+				.cloned()
+				.collect::<Vec<Bond<T::AccountId, SessionIndex>>>();
+			exits.0.retain(|x| x.amount > next);
+			<ExitQueue<T>>::put(exits);
+
+			for x in due_exits {
+				if let Some(state) = <ValidatorState<T>>::get(&x.owner) {
+					// revoke all nominations
+					let mut nominators = <NominatorBonds<T>>::drain_prefix(&x.owner)
+						.map(|(nominator, _)| nominator)
+						.collect::<Vec<T::AccountId>>();
+					nominators.sort();
+					for nominator in nominators {
+						Self::validator_revokes_nomination(nominator, x.owner.clone());
+					}
+					// return stake to validator
+					let mut unlock_chunk_total: BalanceOf<T> = Zero::zero();
+					let _ = state.unlocking.iter().map(|chunk| {
+						unlock_chunk_total = unlock_chunk_total.saturating_add(chunk.value);
+					});
 
-							let new_total =
-								<Total<T>>::get().saturating_sub(state.total.saturating_sub(unlock_chunk_total));
-							<Total<T>>::put(new_total);
+					let new_total = <Total<T>>::get().saturating_sub(state.total.saturating_sub(unlock_chunk_total));
+					<Total<T>>::put(new_total);
 
-							T::Currency::remove_lock(T::StakingLockId::get(), &x.owner);
+					T::Currency::remove_lock(T::StakingLockId::get(), &x.owner);
 
-							let _ = Self::kill_state_info(&x.owner);
+					let _ = Self::kill_state_info(&x.owner);
 
-							Self::deposit_event(Event::ValidatorLeft(x.owner, state.total, new_total));
-						}
-						None
-					}
-				})
-				.collect::<Vec<Bond<T::AccountId, SessionIndex>>>();
-			<ExitQueue<T>>::put(OrderedSet::from(remain_exits));
+					Self::deposit_event(Event::ValidatorLeft(x.owner, state.total, new_total));
+				}
+			}
 		}
 
 		fn active_stake_reconciliation() {
-			let reconciled_list = <ValidatorPool<T>>::get()
-				.0
-				.into_iter()
-				.filter_map(|x| {
-					Self::validator_stake_reconciliation(&x.owner);
-					if let Some(valid_state) = <ValidatorState<T>>::get(&x.owner) {
-						if valid_state.is_active() && valid_state.bond > Self::staking_min_validator_bond() {
-							Some(Bond {
-								owner: x.owner,
-								amount: valid_state.total,
-							})
-						} else {
-							None
-						}
-					} else {
-						None
+			let mut validators = pool::iter::<T>().map(|(who, _)| who).collect::<Vec<T::AccountId>>();
+			validators.sort();
+			for who in validators {
+				Self::validator_stake_reconciliation(&who);
+				match <ValidatorState<T>>::get(&who) {
+					Some(valid_state)
+						if valid_state.is_active() && valid_state.bond > Self::staking_min_validator_bond() =>
+					{
+						// already in the pool, so there is room for it.
+						let _ = pool::upsert::<T>(&who, valid_state.total);
 					}
-				})
-				.collect::<Vec<Bond<T::AccountId, BalanceOf<T>>>>();
-			<ValidatorPool<T>>::put(OrderedSet::from(reconciled_list));
+					_ => {
+						pool::remove::<T>(&who);
+					}
+				}
+			}
 		}
 
 		pub(crate) fn validator_stake_reconciliation(controller: &T::AccountId) {
 			<ValidatorState<T>>::mutate(controller, |maybe_validator| {
 				if let Some(valid_state) = maybe_validator {
-					let noms = valid_state
-						.clone()
-						.nominators
-						.0
-						.into_iter()
-						.filter_map(|nom| {
-							if nom.amount < Self::staking_min_nomination_chill_threshold() {
-								Self::validator_freeze_nomination(nom.owner.clone(), controller.clone());
-								valid_state.dec_nominator(nom.owner.clone(), nom.amount);
-								None
-							} else {
-								Some(nom)
-							}
-						})
-						.collect();
-
-					let nominators = OrderedSet::from(noms);
-					valid_state.nominators = nominators;
+					let mut below_threshold = <NominatorBonds<T>>::iter_prefix(controller)
+						.filter(|(_, amount)| *amount < Self::staking_min_nomination_chill_threshold())
+						.collect::<Vec<(T::AccountId, BalanceOf<T>)>>();
+					below_threshold.sort_by(|a, b| a.0.cmp(&b.0));
+
+					for (nominator, amount) in below_threshold {
+						Self::validator_freeze_nomination(nominator.clone(), controller.clone());
+						<NominatorBonds<T>>::remove(controller, &nominator);
+						valid_state.rm_nominator(amount);
+					}
 
 					if valid_state.bond < Self::staking_min_validator_bond() && valid_state.is_active() {
 						valid_state.go_offline();
@@ -1740,22 +2483,20 @@ pub mod pallet {
 		/// Best as in most cumulatively supported in terms of stake
 		pub(crate) fn select_session_validators(next: SessionIndex) -> (u32, BalanceOf<T>) {
 			let (mut validators_count, mut total) = (0u32, <BalanceOf<T>>::zero());
-			let mut validators = <ValidatorPool<T>>::get().0;
-			// order validators pool by stake (least to greatest so requires `rev()`)
-			validators.sort_unstable_by(|a, b| a.amount.partial_cmp(&b.amount).unwrap());
-			let top_n = <TotalSelected<T>>::get() as usize;
+			let invulnerables = Self::invulnerables();
+			// leave room for the invulnerables within `MaxValidators`
+			let top_n = (<TotalSelected<T>>::get() as usize)
+				.min((T::MaxValidators::get() as usize).saturating_sub(invulnerables.len()));
 			// choose the top TotalSelected qualified validators, ordered by stake
-			let mut top_validators = validators
-				.into_iter()
-				.rev()
+			let mut top_validators = pool::iter::<T>()
 				.take(top_n)
-				.filter(|x| x.amount >= <StakingMinStakeSessionSelection<T>>::get())
-				.filter(|x| T::ValidatorRegistration::is_registered(&x.owner))
-				.map(|x| x.owner)
+				.filter(|(_, stake)| *stake >= <StakingMinStakeSessionSelection<T>>::get())
+				.filter(|(who, _)| T::ValidatorRegistration::is_registered(who))
+				.map(|(who, _)| who)
 				.collect::<Vec<T::AccountId>>();
 
-			if !<Invulnerables<T>>::get().is_empty() {
-				top_validators = Self::invulnerables()
+			if !invulnerables.is_empty() {
+				top_validators = invulnerables
 					.iter()
 					.chain(top_validators.iter())
 					.cloned()
@@ -1769,8 +2510,7 @@ pub mod pallet {
 			for account in top_validators.iter() {
 				let state = <ValidatorState<T>>::get(account).expect("all members of ValidatorQ must be validators");
 				let amount = state.bond.saturating_add(state.nomi_bond_total);
-				let exposure: ValidatorSnapshot<T::AccountId, BalanceOf<T>> = state.into();
-				<AtStake<T>>::insert(next, account, exposure);
+				Self::store_exposure(next, state);
 				validators_count = validators_count.saturating_add(1u32);
 				total = total.saturating_add(amount);
 				Self::deposit_event(Event::ValidatorChosen(next, account.clone(), amount));
@@ -1778,7 +2518,7 @@ pub mod pallet {
 
 			// top_validators.sort();
 			// insert canonical collator set
-			<SelectedValidators<T>>::put(top_validators);
+			<SelectedValidators<T>>::put(BoundedVec::truncate_from(top_validators));
 			(validators_count, total)
 		}
 		/// Add reward points to validators using their account ID.
@@ -1815,9 +2555,11 @@ pub mod pallet {
 
 			if Self::is_validator(controller) {
 				<ValidatorState<T>>::remove(controller);
+				<PoorSessions<T>>::remove(controller);
 			} else if Self::is_nominator(controller) {
 				<NominatorState<T>>::remove(controller);
 			}
+			<AutoCompound<T>>::remove(controller);
 			Ok(())
 		}
 
@@ -1833,11 +2575,22 @@ pub mod pallet {
 			// Since hook function context, Safe to Ignoring the result MultiRemovalResults,
 			// not used for weight computation
 			let _ = <AtStake<T>>::clear_prefix(session_idx, u32::max_value(), None);
+			let _ = <AtStakePage<T>>::clear_prefix(session_idx, u32::max_value(), None);
 			<Points<T>>::remove(session_idx);
 			// Since hook function context, Safe to Ignoring the result MultiRemovalResults,
 			// not used for weight computation
 			let _ = <AwardedPts<T>>::clear_prefix(session_idx, u32::max_value(), None);
 			<SessionValidatorReward<T>>::remove(session_idx);
+			// Since hook function context, Safe to Ignoring the result MultiRemovalResults,
+			// not used for weight computation
+			let _ = <ClaimedRewards<T>>::clear_prefix(session_idx, u32::max_value(), None);
+			// Since hook function context, Safe to Ignoring the result MultiRemovalResults,
+			// not used for weight computation
+			let _ = <Performance<T>>::clear_prefix(session_idx, u32::max_value(), None);
+			<SessionStartSlot<T>>::remove(session_idx);
+			// Since hook function context, Safe to Ignoring the result MultiRemovalResults,
+			// not used for weight computation
+			let _ = <ReportedEquivocations<T>>::clear_prefix(session_idx, u32::max_value(), None);
 			<UnappliedSlashes<T>>::remove(session_idx);
 			slashing::clear_session_metadata::<T>(session_idx);
 
@@ -0,0 +1,1072 @@
+/*
+ * This file is part of the Nodle Chain distributed at https://github.com/NodleCode/chain
+ * Copyright (C) 2020-2022  Nodle International
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Storage migrations for the staking pallet.
+
+use super::*;
+use crate::pool::{self, PoolNode};
+use crate::set::OrderedSet;
+use crate::types::{
+	Bond, Nominator, SpanIndex, UnappliedSlash, UnlockChunk, Validator, ValidatorSnapshot, ValidatorStatus,
+};
+use codec::DecodeAll;
+use frame_support::{
+	pallet_prelude::*,
+	storage::{unhashed, StoragePrefixedMap},
+	storage_alias,
+	traits::{
+		Currency, ExistenceRequirement, Get, LockableCurrency, OnRuntimeUpgrade, OnUnbalanced, ReservableCurrency,
+		WithdrawReasons,
+	},
+	StorageHasher, Twox128,
+};
+use sp_runtime::{
+	traits::{AccountIdConversion, Saturating, Zero},
+	Perbill,
+};
+use sp_staking::SessionIndex;
+use sp_std::{marker::PhantomData, prelude::*};
+
+const LOG_TARGET: &str = "runtime::staking";
+
+const COLLATOR_SELECTION_MODULE: &[u8] = b"CollatorSelection";
+
+pub mod legacy {
+	use super::*;
+
+	/// Validator state before the per validator commission.
+	#[derive(Encode, Decode, Clone, RuntimeDebug, TypeInfo)]
+	pub struct Validator<AccountId, Balance> {
+		pub id: AccountId,
+		pub bond: Balance,
+		pub nomi_bond_total: Balance,
+		pub nominators: Vec<Bond<AccountId, Balance>>,
+		pub total: Balance,
+		pub state: ValidatorStatus,
+		pub unlocking: Vec<UnlockChunk<Balance>>,
+	}
+
+	/// Validator snapshot before the per validator commission.
+	#[derive(Encode, Decode, Clone, RuntimeDebug, TypeInfo)]
+	pub struct ValidatorSnapshot<AccountId, Balance> {
+		pub bond: Balance,
+		pub nominators: Vec<Bond<AccountId, Balance>>,
+		pub total: Balance,
+	}
+
+	/// Validator state before the nominator bonds were moved to `NominatorBonds`, bounded or not.
+	#[derive(Encode, Decode, Clone, RuntimeDebug, TypeInfo)]
+	pub struct UnboundedValidator<AccountId, Balance> {
+		pub id: AccountId,
+		pub bond: Balance,
+		pub nomi_bond_total: Balance,
+		pub nominators: Vec<Bond<AccountId, Balance>>,
+		pub total: Balance,
+		pub state: ValidatorStatus,
+		pub unlocking: Vec<UnlockChunk<Balance>>,
+		pub commission: Perbill,
+		pub commission_raised_at: SessionIndex,
+	}
+
+	/// Nominator state before the storage was bounded.
+	#[derive(Encode, Decode, Clone, RuntimeDebug, TypeInfo)]
+	pub struct UnboundedNominator<AccountId, Balance> {
+		pub nominations: Vec<Bond<AccountId, Balance>>,
+		pub total: Balance,
+		pub active_bond: Balance,
+		pub frozen_bond: Balance,
+		pub unlocking: Vec<UnlockChunk<Balance>>,
+	}
+
+	/// Validator snapshot before the storage was bounded.
+	#[derive(Encode, Decode, Clone, RuntimeDebug, TypeInfo)]
+	pub struct UnboundedValidatorSnapshot<AccountId, Balance> {
+		pub bond: Balance,
+		pub nominators: Vec<Bond<AccountId, Balance>>,
+		pub total: Balance,
+		pub commission: Perbill,
+	}
+
+	/// Unapplied slash before the storage was bounded.
+	#[derive(Encode, Decode, Clone, RuntimeDebug, TypeInfo)]
+	pub struct UnboundedUnappliedSlash<AccountId, Balance> {
+		pub validator: AccountId,
+		pub own: Balance,
+		pub others: Vec<(AccountId, Balance)>,
+		pub reporters: Vec<AccountId>,
+		pub payout: Balance,
+	}
+
+	/// Slashing spans before the storage was bounded.
+	#[derive(Encode, Decode, Clone, RuntimeDebug, TypeInfo)]
+	pub struct UnboundedSlashingSpans {
+		pub span_index: SpanIndex,
+		pub last_start: SessionIndex,
+		pub last_nonzero_slash: SessionIndex,
+		pub prior: Vec<SessionIndex>,
+	}
+
+	/// Validators pool before it was moved to a storage map, sorted by account.
+	#[storage_alias]
+	pub type ValidatorPool<T: Config> =
+		StorageValue<Pallet<T>, Vec<Bond<<T as frame_system::Config>::AccountId, BalanceOf<T>>>, ValueQuery>;
+}
+
+/// The parts of `pallet_collator_selection` storage we need in order to move off it.
+mod collator_selection {
+	use super::*;
+
+	/// Mirrors `pallet_collator_selection::CandidateInfo`.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+	pub struct CandidateInfo<AccountId, Balance> {
+		pub who: AccountId,
+		pub deposit: Balance,
+	}
+
+	#[storage_alias]
+	pub type Invulnerables<T: Config> =
+		StorageValue<CollatorSelection, Vec<<T as frame_system::Config>::AccountId>, ValueQuery>;
+
+	#[storage_alias]
+	pub type Candidates<T: Config> = StorageValue<
+		CollatorSelection,
+		Vec<CandidateInfo<<T as frame_system::Config>::AccountId, BalanceOf<T>>>,
+		ValueQuery,
+	>;
+
+	#[storage_alias]
+	pub type DesiredCandidates = StorageValue<CollatorSelection, u32, ValueQuery>;
+}
+
+/// Hands the collator set of `pallet_collator_selection` over to this pallet.
+///
+/// Invulnerables stay invulnerable and become validators with no bond of their own, candidates
+/// have their candidacy bond unreserved and locked as their validator bond. Whatever is left in
+/// the collator selection `Pot` is carried over to the rewards of the ongoing session. The whole
+/// `CollatorSelection` storage is removed afterwards.
+pub struct MigrateFromCollatorSelection<T, Pot>(PhantomData<(T, Pot)>);
+
+impl<T, Pot> OnRuntimeUpgrade for MigrateFromCollatorSelection<T, Pot>
+where
+	T: Config + pallet_session::Config,
+	T::Currency: ReservableCurrency<T::AccountId>,
+	Pot: Get<T::AccountId>,
+{
+	fn on_runtime_upgrade() -> Weight {
+		if !collator_selection::Invulnerables::<T>::exists() && !collator_selection::Candidates::<T>::exists() {
+			log::info!(
+				target: LOG_TARGET,
+				"No collator selection in place, skipping the migration"
+			);
+			return T::DbWeight::get().reads(2);
+		}
+
+		let invulnerables = collator_selection::Invulnerables::<T>::get();
+		let candidates = collator_selection::Candidates::<T>::get();
+		let desired_candidates = collator_selection::DesiredCandidates::get();
+		let cleared = unhashed::clear_prefix(&Twox128::hash(COLLATOR_SELECTION_MODULE), None, None);
+
+		<Pallet<T>>::put_default_staking_config();
+		crate::pallet::StorageVersion::<T>::put(Releases::V4);
+		<TotalSelected<T>>::put(desired_candidates.max(T::MinSelectedValidators::get()));
+		<StakingMaxValidators<T>>::mutate(|max| {
+			*max = (*max).max(candidates.len() as u32).min(T::MaxValidators::get())
+		});
+
+		// Ensure balance is >= ED, as the genesis build does
+		let staking_account = T::PalletId::get().into_account_truncating();
+		if T::Currency::free_balance(&staking_account).is_zero() {
+			let imbalance = T::Currency::issue(T::Currency::minimum_balance());
+			T::Currency::resolve_creating(&staking_account, imbalance);
+		}
+
+		let session_idx = <pallet_session::Pallet<T>>::current_index();
+		let commission = <Pallet<T>>::commission_within_bounds(<ValidatorFee<T>>::get());
+
+		let staking_invulnerables = bounded_or_truncated::<_, T::MaxValidators>(invulnerables.clone(), "invulnerables");
+		for who in staking_invulnerables.iter() {
+			<ValidatorState<T>>::insert(who, Validator::new(who.clone(), Zero::zero(), commission, session_idx));
+		}
+		<Invulnerables<T>>::put(&staking_invulnerables);
+
+		let mut total = <Total<T>>::get();
+		for collator_selection::CandidateInfo { who, deposit } in candidates.iter() {
+			let bond = deposit.saturating_sub(T::Currency::unreserve(who, *deposit));
+			if pool::upsert::<T>(who, bond).is_err() {
+				log::error!(
+					target: LOG_TARGET,
+					"Validator pool is full, candidate {:?} is left out with its deposit unreserved",
+					who
+				);
+				continue;
+			}
+			if bond < <StakingMinValidatorBond<T>>::get() {
+				log::warn!(
+					target: LOG_TARGET,
+					"Candidate {:?} is bonded with {:?}, below the minimum validator bond",
+					who,
+					bond
+				);
+			}
+			T::Currency::set_lock(T::StakingLockId::get(), who, bond, WithdrawReasons::all());
+			<ValidatorState<T>>::insert(who, Validator::new(who.clone(), bond, commission, session_idx));
+			total = total.saturating_add(bond);
+		}
+		<Total<T>>::put(total);
+
+		// Start accounting from the ongoing session, the session pallet already has the
+		// validators of the next one queued.
+		<ActiveSession<T>>::put(session_idx);
+		<BondedSessions<T>>::put(BoundedVec::truncate_from(vec![session_idx]));
+		let (validator_count, total_staked) = <Pallet<T>>::select_session_validators(session_idx);
+		<Staked<T>>::insert(session_idx, total);
+
+		let pot = Pot::get();
+		let rewards = T::Currency::free_balance(&pot);
+		if !rewards.is_zero() {
+			match T::Currency::withdraw(&pot, rewards, WithdrawReasons::all(), ExistenceRequirement::AllowDeath) {
+				Ok(imbalance) => <Pallet<T>>::on_unbalanced(imbalance),
+				Err(err) => log::error!(
+					target: LOG_TARGET,
+					"Failed to carry over the collator selection pot: {:?}",
+					err
+				),
+			}
+		}
+
+		log::info!(
+			target: LOG_TARGET,
+			"Migrated {} invulnerables and {} candidates, {} validators selected for session {} with {:?} staked",
+			invulnerables.len(),
+			candidates.len(),
+			validator_count,
+			session_idx,
+			total_staked
+		);
+
+		let collators = (invulnerables.len() + candidates.len()) as u64;
+		// every candidate walks the validators pool to find its place.
+		T::DbWeight::get().reads_writes(
+			collators.saturating_mul(collators.saturating_add(3)).saturating_add(12),
+			collators
+				.saturating_mul(4)
+				.saturating_add(cleared.backend.into())
+				.saturating_add(14),
+		)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+		let invulnerables = collator_selection::Invulnerables::<T>::get();
+		let candidates = collator_selection::Candidates::<T>::get();
+		ensure!(
+			!invulnerables.is_empty() || !candidates.is_empty(),
+			"Staking: no collators to migrate, remove the runtime upgrade code"
+		);
+		Ok((invulnerables, candidates).encode())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+		#[allow(clippy::type_complexity)]
+		let (invulnerables, candidates): (
+			Vec<T::AccountId>,
+			Vec<collator_selection::CandidateInfo<T::AccountId, BalanceOf<T>>>,
+		) = Decode::decode(&mut state.as_slice()).map_err(|_| "Staking: failed to decode the pre-upgrade state")?;
+
+		ensure!(
+			!collator_selection::Invulnerables::<T>::exists()
+				&& !collator_selection::Candidates::<T>::exists()
+				&& !collator_selection::DesiredCandidates::exists(),
+			"Staking: collator selection storage was not removed"
+		);
+		ensure!(
+			invulnerables.starts_with(&<Invulnerables<T>>::get()),
+			"Staking: invulnerables were not carried over"
+		);
+		ensure!(
+			<Invulnerables<T>>::get().iter().all(<Pallet<T>>::is_validator),
+			"Staking: an invulnerable is not a validator"
+		);
+
+		let mut bonded: BalanceOf<T> = Zero::zero();
+		for collator_selection::CandidateInfo { who, deposit } in candidates.iter() {
+			let state = match <ValidatorState<T>>::get(who) {
+				Some(state) => state,
+				None => {
+					ensure!(
+						pool::len::<T>() == T::MaxValidators::get(),
+						"Staking: a candidate is not a validator"
+					);
+					continue;
+				}
+			};
+			ensure!(
+				state.bond <= *deposit,
+				"Staking: a candidate is bonded above its deposit"
+			);
+			ensure!(
+				pool::contains::<T>(who),
+				"Staking: a candidate is not in the validator pool"
+			);
+			bonded = bonded.saturating_add(state.bond);
+		}
+		ensure!(
+			<Total<T>>::get() == bonded,
+			"Staking: total stake does not match the candidates bonds"
+		);
+		ensure!(
+			<ActiveSession<T>>::get() == <pallet_session::Pallet<T>>::current_index(),
+			"Staking: active session is not the current session"
+		);
+		Ok(())
+	}
+}
+
+/// Gives every validator a commission of its own, starting from the `ValidatorFee` all of them
+/// used to be paid, and sets the commission bounds to their defaults. The `AtStake` snapshots
+/// keep the `ValidatorFee` they were taken with.
+pub struct MigrateToValidatorCommission<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for MigrateToValidatorCommission<T> {
+	fn on_runtime_upgrade() -> Weight {
+		let on_chain_version = crate::pallet::StorageVersion::<T>::get();
+		if !matches!(on_chain_version, Releases::V0 | Releases::V1) {
+			log::info!(
+				target: LOG_TARGET,
+				"Storage version {:?} already has validator commissions, skipping the migration",
+				on_chain_version
+			);
+			return T::DbWeight::get().reads(1);
+		}
+
+		let fee = <ValidatorFee<T>>::get();
+		<MinValidatorCommission<T>>::put(T::DefaultMinValidatorCommission::get());
+		<MaxValidatorCommission<T>>::put(T::DefaultMaxValidatorCommission::get());
+		<MaxCommissionIncrease<T>>::put(T::DefaultMaxCommissionIncrease::get());
+		let commission = <Pallet<T>>::commission_within_bounds(fee);
+		let session_idx = <ActiveSession<T>>::get();
+
+		let mut validators = 0u64;
+		for who in <ValidatorState<T>>::iter_keys() {
+			let key = <ValidatorState<T>>::hashed_key_for(&who);
+			if let Some(old) = unhashed::get::<legacy::Validator<T::AccountId, BalanceOf<T>>>(&key) {
+				validators = validators.saturating_add(1);
+				unhashed::put(
+					&key,
+					&legacy::UnboundedValidator {
+						id: old.id,
+						bond: old.bond,
+						nomi_bond_total: old.nomi_bond_total,
+						nominators: old.nominators,
+						total: old.total,
+						state: old.state,
+						unlocking: old.unlocking,
+						commission,
+						commission_raised_at: session_idx,
+					},
+				);
+			}
+		}
+
+		let (mut snapshots, mut pages) = (0u64, 0u64);
+		<AtStake<T>>::translate::<legacy::ValidatorSnapshot<T::AccountId, BalanceOf<T>>, _>(|session_idx, who, old| {
+			snapshots = snapshots.saturating_add(1);
+			pages = pages.saturating_add(<Pallet<T>>::exposure_pages(old.nominators.len() as u32).into());
+			Some(bounded_snapshot::<T>(
+				session_idx,
+				&who,
+				legacy::UnboundedValidatorSnapshot {
+					bond: old.bond,
+					nominators: old.nominators,
+					total: old.total,
+					commission: fee,
+				},
+			))
+		});
+
+		crate::pallet::StorageVersion::<T>::put(Releases::V2);
+
+		log::info!(
+			target: LOG_TARGET,
+			"Migrated {} validators and {} snapshots to a commission of their own",
+			validators,
+			snapshots
+		);
+
+		let translated = validators.saturating_add(snapshots);
+		T::DbWeight::get().reads_writes(
+			translated.saturating_add(3),
+			translated.saturating_add(pages).saturating_add(4),
+		)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+		Ok((<ValidatorState<T>>::iter_keys().count() as u32).encode())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+		let count_before =
+			u32::decode(&mut state.as_slice()).map_err(|_| "Staking: failed to decode the pre-upgrade state")?;
+		ensure!(
+			crate::pallet::StorageVersion::<T>::get() == Releases::V2,
+			"Staking: storage version was not updated"
+		);
+		let validators = legacy_validators::<T>().collect::<Vec<_>>();
+		ensure!(
+			validators.len() as u32 == count_before,
+			"Staking: the number of validators changed during the migration"
+		);
+		ensure!(
+			validators
+				.iter()
+				.all(|state| state.commission >= <MinValidatorCommission<T>>::get()
+					&& state.commission <= <MaxValidatorCommission<T>>::get()),
+			"Staking: a validator commission is out of bounds"
+		);
+		Ok(())
+	}
+}
+
+/// Bounds the staking storage, which used to be unbounded.
+///
+/// The storage values are migrated with the runtime upgrade. The storage maps may hold more
+/// entries than fit in a block, they are migrated by `on_idle` in the following blocks for as
+/// long as the `MigrationCursor` is set. Entries within the bounds already decode as they are
+/// and are left untouched.
+///
+/// Unlocking chunks and stake rewards over the bounds are added up, the validators pool keeps
+/// the highest stakes and the unapplied slashes over the bound are applied right away. The
+/// other lists were already kept within the configured bounds by the pallet, whatever is over
+/// is dropped and logged as an error.
+pub struct MigrateToBoundedStorage<T>(PhantomData<T>);
+
+/// How many later sessions `MigrateToBoundedStorage` looks at to defer the slashes over the
+/// bound of a session to.
+const MAX_SLASH_DEFERRAL: u32 = 8;
+
+/// The storage maps `MigrateToBoundedStorage` goes through, in order.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum BoundedStorageStage {
+	ValidatorState,
+	NominatorState,
+	AtStake,
+	StakeRewards,
+	UnappliedSlashes,
+	SlashingSpans,
+}
+
+impl BoundedStorageStage {
+	fn next(self) -> Option<Self> {
+		match self {
+			Self::ValidatorState => Some(Self::NominatorState),
+			Self::NominatorState => Some(Self::AtStake),
+			Self::AtStake => Some(Self::StakeRewards),
+			Self::StakeRewards => Some(Self::UnappliedSlashes),
+			Self::UnappliedSlashes => Some(Self::SlashingSpans),
+			Self::SlashingSpans => None,
+		}
+	}
+}
+
+/// How far `MigrateToBoundedStorage` went through the storage maps.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct BoundedStorageCursor {
+	/// The storage map being migrated.
+	pub stage: BoundedStorageStage,
+	/// The raw key of the last entry migrated in that map, if any.
+	/// Comfortably above the 84 bytes of the `AtStake` keys with 32 bytes account ids.
+	pub last_key: Option<BoundedVec<u8, ConstU32<192>>>,
+}
+
+impl<T: Config> OnRuntimeUpgrade for MigrateToBoundedStorage<T> {
+	fn on_runtime_upgrade() -> Weight {
+		let on_chain_version = crate::pallet::StorageVersion::<T>::get();
+		if on_chain_version != Releases::V2 {
+			log::info!(
+				target: LOG_TARGET,
+				"Storage version {:?} is not the unbounded one, skipping the migration",
+				on_chain_version
+			);
+			return T::DbWeight::get().reads(1);
+		}
+
+		log_if_failed(
+			<Invulnerables<T>>::translate::<Vec<T::AccountId>, _>(|old| {
+				old.map(|invulnerables| bounded_or_truncated(invulnerables, "invulnerables"))
+			}),
+			"invulnerables",
+		);
+		log_if_failed(
+			<SelectedValidators<T>>::translate::<Vec<T::AccountId>, _>(|old| {
+				old.map(|selected| bounded_or_truncated(selected, "selected validators"))
+			}),
+			"selected validators",
+		);
+
+		let mut idle = 0u64;
+		log_if_failed(
+			<legacy::ValidatorPool<T>>::translate::<Vec<Bond<T::AccountId, BalanceOf<T>>>, _>(|old| {
+				old.map(|mut pool| {
+					let max = T::MaxValidators::get() as usize;
+					if pool.len() > max {
+						// keep the highest stakes, the others go idle until they bond more.
+						pool.sort_by(|a, b| b.amount.cmp(&a.amount));
+						for left_out in pool.split_off(max) {
+							log::error!(
+								target: LOG_TARGET,
+								"Validator pool is full, {:?} goes idle",
+								left_out.owner
+							);
+							let key = <ValidatorState<T>>::hashed_key_for(&left_out.owner);
+							if let Some(mut state) =
+								unhashed::get::<legacy::UnboundedValidator<T::AccountId, BalanceOf<T>>>(&key)
+							{
+								state.state = ValidatorStatus::Idle;
+								unhashed::put(&key, &state);
+							}
+							idle = idle.saturating_add(1);
+						}
+						pool.sort();
+					}
+					pool
+				})
+			}),
+			"validator pool",
+		);
+		log_if_failed(
+			<ExitQueue<T>>::translate::<Vec<Bond<T::AccountId, SessionIndex>>, _>(|old| {
+				old.map(|mut exits| {
+					// keep the earliest exits.
+					exits.sort_by_key(|exit| exit.amount);
+					let mut exits = bounded_or_truncated::<_, T::MaxValidators>(exits, "exits").into_inner();
+					exits.sort();
+					OrderedSet::from_sorted_set(BoundedVec::truncate_from(exits))
+				})
+			}),
+			"exit queue",
+		);
+		log_if_failed(
+			<BondedSessions<T>>::translate::<Vec<SessionIndex>, _>(|old| {
+				old.map(|mut sessions| {
+					// keep the latest sessions, the older ones are pruned already.
+					let over = sessions.len().saturating_sub(MaxBondedSessions::<T>::get() as usize);
+					BoundedVec::truncate_from(sessions.split_off(over))
+				})
+			}),
+			"bonded sessions",
+		);
+		<StakingMaxValidators<T>>::mutate(|max| *max = (*max).min(T::MaxValidators::get()));
+
+		<MigrationCursor<T>>::put(BoundedStorageCursor {
+			stage: BoundedStorageStage::ValidatorState,
+			last_key: None,
+		});
+		crate::pallet::StorageVersion::<T>::put(Releases::V3);
+
+		log::info!(
+			target: LOG_TARGET,
+			"Migrated the staking storage values to bounded ones, the storage maps follow in the next blocks"
+		);
+
+		T::DbWeight::get().reads_writes(idle.saturating_add(7), idle.saturating_add(8))
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+		ensure!(
+			crate::pallet::StorageVersion::<T>::get() == Releases::V2,
+			"Staking: storage is not unbounded, remove the runtime upgrade code"
+		);
+		Ok(Vec::new())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+		ensure!(
+			crate::pallet::StorageVersion::<T>::get() == Releases::V3,
+			"Staking: storage version was not updated"
+		);
+		ensure!(
+			<MigrationCursor<T>>::exists(),
+			"Staking: migration of the storage maps was not scheduled"
+		);
+		ensure!(
+			<StakingMaxValidators<T>>::get() <= T::MaxValidators::get(),
+			"Staking: maximum validators is over the bound"
+		);
+		ensure!(
+			<Invulnerables<T>>::try_get().is_ok() || !<Invulnerables<T>>::exists(),
+			"Staking: invulnerables are not bounded"
+		);
+		ensure!(
+			<legacy::ValidatorPool<T>>::get().len() as u32 <= T::MaxValidators::get(),
+			"Staking: validator pool is not bounded"
+		);
+		Ok(())
+	}
+}
+
+impl<T: Config> MigrateToBoundedStorage<T> {
+	/// Migrates storage map entries as far as `limit` allows, called on idle until all of them
+	/// are migrated.
+	pub(crate) fn step(limit: Weight) -> Weight {
+		let db = T::DbWeight::get();
+		let mut consumed = db.reads(1);
+		let mut cursor = match <MigrationCursor<T>>::get() {
+			Some(cursor) => cursor,
+			None => return consumed,
+		};
+
+		// leave room for the costliest entry of the stage and for writing the cursor back.
+		let reserved = |stage| Self::max_entry_weight(stage).saturating_add(db.writes(1));
+		if !consumed.saturating_add(reserved(cursor.stage)).all_lte(limit) {
+			return consumed;
+		}
+
+		let mut last_key = cursor.last_key.take().map(|key| key.into_inner());
+		while consumed.saturating_add(reserved(cursor.stage)).all_lte(limit) {
+			match Self::migrate_next(cursor.stage, last_key.take()) {
+				Some((key, weight)) => {
+					consumed = consumed.saturating_add(weight);
+					last_key = Some(key);
+				}
+				None => {
+					consumed = consumed.saturating_add(db.reads(1));
+					match cursor.stage.next() {
+						Some(stage) => cursor.stage = stage,
+						None => {
+							<MigrationCursor<T>>::kill();
+							log::info!(target: LOG_TARGET, "Migrated the staking storage maps to bounded ones");
+							return consumed.saturating_add(db.writes(1));
+						}
+					}
+				}
+			}
+		}
+
+		cursor.last_key = last_key.map(BoundedVec::truncate_from);
+		<MigrationCursor<T>>::put(cursor);
+		consumed.saturating_add(db.writes(1))
+	}
+
+	/// Upper bound of the weight `migrate_next` consumes for an entry of the map of `stage`.
+	fn max_entry_weight(stage: BoundedStorageStage) -> Weight {
+		let db = T::DbWeight::get();
+		match stage {
+			BoundedStorageStage::ValidatorState
+			| BoundedStorageStage::NominatorState
+			| BoundedStorageStage::StakeRewards => db.reads_writes(2, 1),
+			// the snapshot and its pages
+			BoundedStorageStage::AtStake => db.reads_writes(
+				2,
+				<Pallet<T>>::exposure_pages(T::MaxNominatorsPerValidator::get())
+					.saturating_add(1)
+					.into(),
+			),
+			// the session and the later ones looked at to defer its overflow to
+			BoundedStorageStage::UnappliedSlashes => db.reads_writes(MAX_SLASH_DEFERRAL.saturating_add(2).into(), 2),
+			// the spans and the records of the forgotten ones
+			BoundedStorageStage::SlashingSpans => {
+				db.reads_writes(2, T::MaxSlashingSpans::get().saturating_add(1).into())
+			}
+		}
+	}
+
+	/// Migrates the entry after `last_key` in the map of the `stage`, returns its raw key and the
+	/// weight consumed, or `None` once there are no entries left.
+	fn migrate_next(stage: BoundedStorageStage, last_key: Option<Vec<u8>>) -> Option<(Vec<u8>, Weight)> {
+		let db = T::DbWeight::get();
+		let untouched = db.reads(2);
+		let mut migrated = db.reads_writes(2, 1);
+
+		match stage {
+			BoundedStorageStage::ValidatorState => {
+				let who = <ValidatorState<T>>::iter_keys_from(start_key(last_key, <ValidatorState<T>>::final_prefix()))
+					.next()?;
+				let key = <ValidatorState<T>>::hashed_key_for(&who);
+				if let Some(old) = unhashed::get::<legacy::UnboundedValidator<T::AccountId, BalanceOf<T>>>(&key) {
+					if old.nominators.len() as u32 <= T::MaxNominatorsPerValidator::get()
+						&& old.unlocking.len() as u32 <= T::MaxChunkUnlock::get()
+					{
+						return Some((key, untouched));
+					}
+					unhashed::put(&key, &bounded_validator::<T>(old));
+				}
+				Some((key, migrated))
+			}
+			BoundedStorageStage::NominatorState => {
+				let who = <NominatorState<T>>::iter_keys_from(start_key(last_key, <NominatorState<T>>::final_prefix()))
+					.next()?;
+				let key = <NominatorState<T>>::hashed_key_for(&who);
+				if <NominatorState<T>>::try_get(&who).is_ok() {
+					return Some((key, untouched));
+				}
+				if let Some(old) = unhashed::get::<legacy::UnboundedNominator<T::AccountId, BalanceOf<T>>>(&key) {
+					<NominatorState<T>>::insert(&who, bounded_nominator::<T>(old));
+				}
+				Some((key, migrated))
+			}
+			BoundedStorageStage::AtStake => {
+				let (session_idx, who) =
+					<AtStake<T>>::iter_keys_from(start_key(last_key, <AtStake<T>>::final_prefix())).next()?;
+				let key = <AtStake<T>>::hashed_key_for(session_idx, &who);
+				// an unbounded snapshot may decode as a prefix of the new layout, check it decodes
+				// to the last byte
+				let raw = unhashed::get_raw(&key).unwrap_or_default();
+				if SnapshotOf::<T>::decode_all(&mut &raw[..]).is_ok() {
+					return Some((key, untouched));
+				}
+				if let Some(old) = unhashed::get::<legacy::UnboundedValidatorSnapshot<T::AccountId, BalanceOf<T>>>(&key)
+				{
+					let pages = <Pallet<T>>::exposure_pages(old.nominators.len() as u32);
+					migrated = migrated.saturating_add(db.writes(pages.into()));
+					<AtStake<T>>::insert(session_idx, &who, bounded_snapshot::<T>(session_idx, &who, old));
+				}
+				Some((key, migrated))
+			}
+			BoundedStorageStage::StakeRewards => {
+				let who =
+					<StakeRewards<T>>::iter_keys_from(start_key(last_key, <StakeRewards<T>>::final_prefix())).next()?;
+				let key = <StakeRewards<T>>::hashed_key_for(&who);
+				if <StakeRewards<T>>::try_get(&who).is_ok() {
+					return Some((key, untouched));
+				}
+				if let Some(old) = unhashed::get::<Vec<UnlockChunk<BalanceOf<T>>>>(&key) {
+					<StakeRewards<T>>::insert(&who, merged_chunks(old));
+				}
+				Some((key, migrated))
+			}
+			BoundedStorageStage::UnappliedSlashes => {
+				let session_idx =
+					<UnappliedSlashes<T>>::iter_keys_from(start_key(last_key, <UnappliedSlashes<T>>::final_prefix()))
+						.next()?;
+				let key = <UnappliedSlashes<T>>::hashed_key_for(session_idx);
+				// the legacy slashes may decode as a prefix of the new layout, check they decode to
+				// the last byte
+				let raw = unhashed::get_raw(&key).unwrap_or_default();
+				if BoundedVec::<UnappliedSlashOf<T>, T::MaxValidators>::decode_all(&mut &raw[..]).is_ok() {
+					return Some((key, untouched));
+				}
+				if let Some(mut old) =
+					unhashed::get::<Vec<legacy::UnboundedUnappliedSlash<T::AccountId, BalanceOf<T>>>>(&key)
+				{
+					// the legacy records do not keep the session of the offence, the session the
+					// slashes were deferred in stands for it.
+					let slash_session = session_idx.saturating_sub(T::SlashDeferDuration::get());
+					let over = old.split_off(old.len().min(T::MaxValidators::get() as usize));
+					let bounded = |old| bounded_unapplied_slash::<T>(old, slash_session);
+					let deferred = old.into_iter().map(bounded).collect::<Vec<_>>();
+					<UnappliedSlashes<T>>::insert(session_idx, BoundedVec::truncate_from(deferred));
+
+					if !over.is_empty() {
+						// no room left in this session, defer the rest to a later one without
+						// slashes yet rather than applying them on idle.
+						let later = (1..=MAX_SLASH_DEFERRAL)
+							.map(|delay| session_idx.saturating_add(delay))
+							.find(|later| {
+								migrated = migrated.saturating_add(db.reads(1));
+								!<UnappliedSlashes<T>>::contains_key(later)
+							});
+						match later {
+							Some(later) => {
+								let deferred = over.into_iter().map(bounded).collect::<Vec<_>>();
+								<UnappliedSlashes<T>>::insert(
+									later,
+									bounded_or_truncated::<_, T::MaxValidators>(deferred, "deferred slashes"),
+								);
+								migrated = migrated.saturating_add(db.writes(1));
+							}
+							None => log::error!(
+								target: LOG_TARGET,
+								"No session to defer {} slashes of session {} to, dropped",
+								over.len(),
+								session_idx
+							),
+						}
+					}
+				}
+				Some((key, migrated))
+			}
+			BoundedStorageStage::SlashingSpans => {
+				let who = <SlashingSpans<T>>::iter_keys_from(start_key(last_key, <SlashingSpans<T>>::final_prefix()))
+					.next()?;
+				let key = <SlashingSpans<T>>::hashed_key_for(&who);
+				if <SlashingSpans<T>>::try_get(&who).is_ok() {
+					return Some((key, untouched));
+				}
+				if let Some(mut old) = unhashed::get::<legacy::UnboundedSlashingSpans>(&key) {
+					let max = T::MaxSlashingSpans::get() as usize;
+					if old.prior.len() > max {
+						// forget the oldest spans along with their records.
+						let earliest = old.span_index.saturating_sub(old.prior.len() as SpanIndex);
+						let new_earliest = old.span_index.saturating_sub(max as SpanIndex);
+						for span_index in earliest..new_earliest {
+							<SpanSlash<T>>::remove(&(who.clone(), span_index));
+						}
+						migrated = migrated.saturating_add(db.writes(new_earliest.saturating_sub(earliest).into()));
+						old.prior.truncate(max);
+					}
+					// same layout, now within the bounds.
+					if let Ok(spans) = slashing::SlashingSpans::<T::MaxSlashingSpans>::decode(&mut &old.encode()[..]) {
+						<SlashingSpans<T>>::insert(&who, spans);
+					}
+				}
+				Some((key, migrated))
+			}
+		}
+	}
+}
+
+/// Moves the nominator bonds of every validator to `NominatorBonds` and the validators pool to
+/// nodes in `ValidatorPool` linked by decreasing stake.
+///
+/// The validators have to be bounded first, the migration is skipped for as long as
+/// `MigrateToBoundedStorage` goes through the storage maps and has to be run with a later
+/// runtime upgrade then.
+pub struct MigrateToMapBasedPool<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for MigrateToMapBasedPool<T> {
+	fn on_runtime_upgrade() -> Weight {
+		let on_chain_version = crate::pallet::StorageVersion::<T>::get();
+		if on_chain_version != Releases::V3 {
+			log::info!(
+				target: LOG_TARGET,
+				"Storage version {:?} does not keep the validators pool in a single value, skipping the migration",
+				on_chain_version
+			);
+			return T::DbWeight::get().reads(1);
+		}
+		if <MigrationCursor<T>>::exists() {
+			log::warn!(
+				target: LOG_TARGET,
+				"The staking storage maps are still being bounded, skipping the migration"
+			);
+			return T::DbWeight::get().reads(2);
+		}
+
+		let mut validators = 0u64;
+		let mut bonds = 0u64;
+		<ValidatorState<T>>::translate::<legacy::UnboundedValidator<T::AccountId, BalanceOf<T>>, _>(|who, old| {
+			validators = validators.saturating_add(1);
+			bonds = bonds.saturating_add(old.nominators.len() as u64);
+			for Bond { owner, amount } in old.nominators.iter() {
+				<NominatorBonds<T>>::insert(&who, owner, amount);
+			}
+			Some(Validator {
+				id: old.id,
+				bond: old.bond,
+				nomi_bond_total: old.nomi_bond_total,
+				nominator_count: old.nominators.len() as u32,
+				total: old.total,
+				state: old.state,
+				unlocking: merged_chunks(old.unlocking),
+				commission: old.commission,
+				commission_raised_at: old.commission_raised_at,
+			})
+		});
+
+		let mut validators_pool = <legacy::ValidatorPool<T>>::take();
+		// the pool is sorted by account, ties in stake keep that order.
+		validators_pool.sort_by(|a, b| b.amount.cmp(&a.amount));
+		validators_pool.truncate(T::MaxValidators::get() as usize);
+		for (idx, bond) in validators_pool.iter().enumerate() {
+			<ValidatorPool<T>>::insert(
+				&bond.owner,
+				PoolNode {
+					stake: bond.amount,
+					prev: idx.checked_sub(1).map(|prev| validators_pool[prev].owner.clone()),
+					next: validators_pool
+						.get(idx.saturating_add(1))
+						.map(|next| next.owner.clone()),
+				},
+			);
+		}
+		<ValidatorPoolHead<T>>::set(validators_pool.first().map(|bond| bond.owner.clone()));
+
+		crate::pallet::StorageVersion::<T>::put(Releases::V4);
+
+		log::info!(
+			target: LOG_TARGET,
+			"Migrated {} validators with {} nominator bonds and {} validators in the pool",
+			validators,
+			bonds,
+			validators_pool.len()
+		);
+
+		let pooled = validators_pool.len() as u64;
+		T::DbWeight::get().reads_writes(
+			validators.saturating_add(3),
+			validators
+				.saturating_add(bonds)
+				.saturating_add(pooled.saturating_mul(2))
+				.saturating_add(3),
+		)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+		ensure!(
+			crate::pallet::StorageVersion::<T>::get() == Releases::V3,
+			"Staking: validators pool is not in a single value, remove the runtime upgrade code"
+		);
+		ensure!(
+			!<MigrationCursor<T>>::exists(),
+			"Staking: storage maps are still being bounded"
+		);
+		let nominators = legacy_validators::<T>()
+			.map(|state| state.nominators.len() as u32)
+			.sum::<u32>();
+		Ok((<legacy::ValidatorPool<T>>::get().len() as u32, nominators).encode())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+		let (pooled, nominators): (u32, u32) =
+			Decode::decode(&mut state.as_slice()).map_err(|_| "Staking: failed to decode the pre-upgrade state")?;
+		ensure!(
+			crate::pallet::StorageVersion::<T>::get() == Releases::V4,
+			"Staking: storage version was not updated"
+		);
+		ensure!(
+			!<legacy::ValidatorPool<T>>::exists(),
+			"Staking: the old validators pool was not removed"
+		);
+		ensure!(
+			pool::len::<T>() == pooled,
+			"Staking: validators went missing from the pool"
+		);
+		let stakes = pool::iter::<T>().map(|(_, stake)| stake).collect::<Vec<_>>();
+		ensure!(
+			stakes.len() as u32 == pooled && stakes.windows(2).all(|pair| pair[0] >= pair[1]),
+			"Staking: validators pool is not sorted by stake"
+		);
+		ensure!(
+			<NominatorBonds<T>>::iter_keys().count() as u32 == nominators,
+			"Staking: nominator bonds went missing"
+		);
+		for (who, state) in <ValidatorState<T>>::iter() {
+			ensure!(
+				<NominatorBonds<T>>::iter_prefix(&who).count() as u32 == state.nominator_count,
+				"Staking: nominator count does not match the nominator bonds"
+			);
+		}
+		Ok(())
+	}
+}
+
+/// The raw key to iterate a storage map from, its first entry without a `last_key`.
+fn start_key(last_key: Option<Vec<u8>>, prefix: [u8; 32]) -> Vec<u8> {
+	last_key.unwrap_or_else(|| prefix.to_vec())
+}
+
+fn log_if_failed<V>(result: Result<V, ()>, what: &str) {
+	if result.is_err() {
+		log::error!(target: LOG_TARGET, "Failed to decode the {}, left as is", what);
+	}
+}
+
+/// Fits a list the pallet already kept within `S`, whatever is over the bound is dropped and
+/// logged as an error.
+fn bounded_or_truncated<V, S: Get<u32>>(items: Vec<V>, what: &str) -> BoundedVec<V, S> {
+	if items.len() > S::get() as usize {
+		log::error!(
+			target: LOG_TARGET,
+			"Dropping {} {} over the bound of {}",
+			items.len().saturating_sub(S::get() as usize),
+			what,
+			S::get()
+		);
+	}
+	BoundedVec::truncate_from(items)
+}
+
+/// Fits unlocking chunks or stake rewards within `S`, the ones over the bound are added up to
+/// the last one kept so no funds get lost, they are due with the latest of them.
+fn merged_chunks<B: Saturating + Copy, S: Get<u32>>(mut chunks: Vec<UnlockChunk<B>>) -> BoundedVec<UnlockChunk<B>, S> {
+	let max = S::get() as usize;
+	if max > 0 && chunks.len() > max {
+		let over = chunks.split_off(max);
+		if let Some(last) = chunks.last_mut() {
+			for chunk in over {
+				last.value = last.value.saturating_add(chunk.value);
+				last.session_idx = last.session_idx.max(chunk.session_idx);
+			}
+		}
+	}
+	bounded_or_truncated(chunks, "unlocking chunks")
+}
+
+/// The validators in the layout from before `MigrateToMapBasedPool`, the ones which do not
+/// decode as such are left out.
+#[cfg(feature = "try-runtime")]
+fn legacy_validators<T: Config>() -> impl Iterator<Item = legacy::UnboundedValidator<T::AccountId, BalanceOf<T>>> {
+	<ValidatorState<T>>::iter_keys().filter_map(|who| unhashed::get(&<ValidatorState<T>>::hashed_key_for(&who)))
+}
+
+/// Fits a validator within the bounds, it keeps the layout it had until `MigrateToMapBasedPool`.
+fn bounded_validator<T: Config>(
+	old: legacy::UnboundedValidator<T::AccountId, BalanceOf<T>>,
+) -> legacy::UnboundedValidator<T::AccountId, BalanceOf<T>> {
+	legacy::UnboundedValidator {
+		nominators: bounded_or_truncated::<_, T::MaxNominatorsPerValidator>(old.nominators, "nominators").into_inner(),
+		unlocking: merged_chunks::<_, T::MaxChunkUnlock>(old.unlocking).into_inner(),
+		..old
+	}
+}
+
+fn bounded_nominator<T: Config>(old: legacy::UnboundedNominator<T::AccountId, BalanceOf<T>>) -> NominatorOf<T> {
+	Nominator {
+		nominations: OrderedSet::from_sorted_set(bounded_or_truncated(old.nominations, "nominations")),
+		total: old.total,
+		active_bond: old.active_bond,
+		frozen_bond: old.frozen_bond,
+		unlocking: merged_chunks(old.unlocking),
+	}
+}
+
+/// Fits a snapshot within the bounds, its nominators are stored in the `AtStakePage` pages.
+fn bounded_snapshot<T: Config>(
+	session_idx: SessionIndex,
+	who: &T::AccountId,
+	old: legacy::UnboundedValidatorSnapshot<T::AccountId, BalanceOf<T>>,
+) -> SnapshotOf<T> {
+	let nominators = bounded_or_truncated::<_, T::MaxNominatorsPerValidator>(old.nominators, "exposed nominators");
+	ValidatorSnapshot {
+		bond: old.bond,
+		total: old.total,
+		commission: old.commission,
+		nominator_count: <Pallet<T>>::store_exposure_pages(session_idx, who, nominators.into_inner()),
+	}
+}
+
+fn bounded_unapplied_slash<T: Config>(
+	old: legacy::UnboundedUnappliedSlash<T::AccountId, BalanceOf<T>>,
+	slash_session: SessionIndex,
+) -> UnappliedSlashOf<T> {
+	UnappliedSlash {
+		validator: old.validator,
+		own: old.own,
+		others: bounded_or_truncated(old.others, "slashed nominators"),
+		reporters: bounded_or_truncated(old.reporters, "reporters"),
+		payout: old.payout,
+		slash_session,
+	}
+}
@@ -18,10 +18,11 @@
 use super::*;
 use crate as nodle_staking;
 use crate::hooks;
+use crate::types::Bond;
 use frame_support::{
 	assert_ok, ord_parameter_types, parameter_types,
 	traits::{
-		ConstU32, Currency, FindAuthor, Imbalance, LockIdentifier, OnFinalize, OnInitialize, OnUnbalanced,
+		ConstU32, Currency, FindAuthor, Get, Imbalance, LockIdentifier, OnFinalize, OnInitialize, OnUnbalanced,
 		OneSessionHandler,
 	},
 	weights::constants::RocksDbWeight,
@@ -30,10 +31,12 @@ use frame_support::{
 use frame_system::EnsureSignedBy;
 use sp_core::H256;
 
+use sp_consensus_aura::digests::CompatibleDigestItem;
+use sp_consensus_slots::Slot;
 use sp_runtime::{
-	testing::{Header, UintAuthorityId},
-	traits::{IdentityLookup, Zero},
-	Perbill,
+	testing::{Header, TestSignature, UintAuthorityId},
+	traits::{Header as _, IdentityLookup, Zero},
+	DigestItem, Perbill,
 };
 use sp_staking::{
 	offence::{DisableStrategy, OffenceDetails, OnOffenceHandler},
@@ -108,6 +111,14 @@ frame_support::construct_runtime!(
 	}
 );
 
+/// Blocks are built every other Aura slot
+pub struct CurrentSlot;
+impl Get<Slot> for CurrentSlot {
+	fn get() -> Slot {
+		Slot::from(System::block_number() * 2)
+	}
+}
+
 /// Author of block is always 11
 pub struct Author11;
 impl FindAuthor<AccountId> for Author11 {
@@ -134,6 +145,9 @@ parameter_types! {
 	pub static Period: BlockNumber = 5;
 	pub static Offset: BlockNumber = 0;
 	pub static MaxIterations: u32 = 0;
+	pub static PoorPerformanceSlash: Perbill = Perbill::zero();
+	// validators don't author blocks unless told to, so they are not scored by default
+	pub static PoorPerformanceThreshold: Perbill = Perbill::zero();
 }
 impl frame_system::Config for Test {
 	type BlockWeights = ();
@@ -201,7 +215,7 @@ impl pallet_session::Config for Test {
 	type WeightInfo = ();
 }
 impl pallet_session::historical::Config for Test {
-	type FullIdentification = crate::types::ValidatorSnapshot<AccountId, Balance>;
+	type FullIdentification = crate::types::ValidatorSnapshot<Balance>;
 	type FullIdentificationOf = crate::types::ValidatorSnapshotOf<Test>;
 }
 impl pallet_authorship::Config for Test {
@@ -245,6 +259,9 @@ parameter_types! {
 	pub const MaxNominatorsPerValidator: u32 = 4;
 	pub const MaxValidatorPerNominator: u32 = 4;
 	pub const DefaultValidatorFee: Perbill = Perbill::from_percent(20);
+	pub const DefaultMinValidatorCommission: Perbill = Perbill::from_percent(5);
+	pub const DefaultMaxValidatorCommission: Perbill = Perbill::from_percent(50);
+	pub const DefaultMaxCommissionIncrease: Perbill = Perbill::from_percent(2);
 	pub const DefaultSlashRewardProportion: Perbill = Perbill::from_percent(10);
 	pub const DefaultSlashRewardFraction: Perbill = Perbill::from_percent(50);
 	pub const DefaultStakingMaxValidators: u32 = 50;
@@ -252,7 +269,14 @@ parameter_types! {
 	pub const DefaultStakingMinValidatorBond: Balance = 10;
 	pub const DefaultStakingMinNominatorTotalBond: Balance = 5;
 	pub const DefaultStakingMinNominationChillThreshold: Balance = 3;
-	pub const MaxChunkUnlock: usize = 32;
+	pub const MaxChunkUnlock: u32 = 32;
+	pub const MaxValidators: u32 = 100;
+	pub const MaxStakeRewards: u32 = 32;
+	pub const MaxNominatorRewardedPerPage: u32 = 2;
+	pub const MaxSlashReporters: u32 = 8;
+	pub const MaxSlashingSpans: u32 = 16;
+	pub const MaxPoorSessions: u32 = 2;
+	pub const EquivocationSlash: Perbill = Perbill::from_percent(10);
 	pub const StakingPalletId: PalletId = PalletId(*b"mockstak");
 	pub const StakingLockId: LockIdentifier = *b"staking ";
 }
@@ -264,6 +288,9 @@ impl Config for Test {
 	type MaxNominatorsPerValidator = MaxNominatorsPerValidator;
 	type MaxValidatorPerNominator = MaxValidatorPerNominator;
 	type DefaultValidatorFee = DefaultValidatorFee;
+	type DefaultMinValidatorCommission = DefaultMinValidatorCommission;
+	type DefaultMaxValidatorCommission = DefaultMaxValidatorCommission;
+	type DefaultMaxCommissionIncrease = DefaultMaxCommissionIncrease;
 	type DefaultSlashRewardProportion = DefaultSlashRewardProportion;
 	type DefaultSlashRewardFraction = DefaultSlashRewardFraction;
 	type DefaultStakingMaxValidators = DefaultStakingMaxValidators;
@@ -273,6 +300,21 @@ impl Config for Test {
 	type DefaultStakingMinNominationChillThreshold = DefaultStakingMinNominationChillThreshold;
 	type RewardRemainder = RewardRemainderMock;
 	type MaxChunkUnlock = MaxChunkUnlock;
+	type MaxValidators = MaxValidators;
+	type MaxStakeRewards = MaxStakeRewards;
+	type MaxNominatorRewardedPerPage = MaxNominatorRewardedPerPage;
+	type MaxSlashReporters = MaxSlashReporters;
+	type MaxSlashingSpans = MaxSlashingSpans;
+	type CurrentSlot = CurrentSlot;
+	type PoorPerformanceThreshold = PoorPerformanceThreshold;
+	type MaxPoorSessions = MaxPoorSessions;
+	type PoorPerformanceSlash = PoorPerformanceSlash;
+	type AuthorityId = UintAuthorityId;
+	type KeyOwnerProof = sp_session::MembershipProof;
+	type KeyOwnerProofSystem = Historical;
+	type EquivocationSlash = EquivocationSlash;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = BenchmarkKeys;
 	type PalletId = StakingPalletId;
 	type StakingLockId = StakingLockId;
 	type Slash = ();
@@ -283,6 +325,28 @@ impl Config for Test {
 	type WeightInfo = ();
 }
 
+/// Sets the session keys of the validators reported in the benchmarks
+#[cfg(feature = "runtime-benchmarks")]
+pub struct BenchmarkKeys;
+#[cfg(feature = "runtime-benchmarks")]
+impl crate::benchmarking::BenchmarkHelper<AccountId, UintAuthorityId, sp_session::MembershipProof> for BenchmarkKeys {
+	fn key_owner_proof(validator: &AccountId, authority_id: UintAuthorityId) -> sp_session::MembershipProof {
+		assert_ok!(Session::set_keys(
+			RuntimeOrigin::signed(*validator),
+			authority_id.clone().into(),
+			vec![]
+		));
+		// the keys are queued by the first rotation and used from the second one
+		Session::rotate_session();
+		Session::rotate_session();
+		<Historical as frame_support::traits::KeyOwnerProofSystem<_>>::prove((
+			<UintAuthorityId as sp_runtime::RuntimeAppPublic>::ID,
+			authority_id,
+		))
+		.expect("the validator is selected")
+	}
+}
+
 thread_local! {
 	pub static REWARD_REMAINDER_UNBALANCED: RefCell<u128> = RefCell::new(0);
 }
@@ -589,6 +653,36 @@ pub(crate) fn run_to_block(n: BlockNumber) {
 	}
 }
 
+/// Note `blocks` blocks authored by `who` in the active session.
+pub(crate) fn author_blocks(who: AccountId, blocks: u32) {
+	for _ in 0..blocks {
+		<NodleStaking as pallet_authorship::EventHandler<AccountId, BlockNumber>>::note_author(who);
+	}
+}
+
+/// Header of a block authored by `author` for Aura `slot`, on top of `parent_hash`.
+pub(crate) fn aura_header(author: AccountId, slot: u64, parent_hash: H256) -> Header {
+	let mut header = Header::new(
+		1,
+		Default::default(),
+		Default::default(),
+		parent_hash,
+		Default::default(),
+	);
+	header
+		.digest_mut()
+		.push(<DigestItem as CompatibleDigestItem<TestSignature>>::aura_pre_digest(
+			Slot::from(slot),
+		));
+	let signature = TestSignature(author, header.hash().as_ref().to_vec());
+	header
+		.digest_mut()
+		.push(<DigestItem as CompatibleDigestItem<TestSignature>>::aura_seal(
+			signature,
+		));
+	header
+}
+
 /// Progresses from the current block number (whatever that may be) to the `P * session_index + 1`.
 pub(crate) fn start_session(session_index: SessionIndex) {
 	let end: u64 = if Offset::get().is_zero() {
@@ -629,11 +723,37 @@ pub(crate) fn bond_nominator(ctrl: AccountId, val: Balance, target: AccountId) {
 }
 
 pub(crate) fn validators_in_pool() -> Vec<AccountId> {
-	NodleStaking::validator_pool().0.into_iter().map(|s| s.owner).collect()
+	let mut validators = NodleStaking::validator_pool()
+		.into_iter()
+		.map(|s| s.owner)
+		.collect::<Vec<_>>();
+	validators.sort();
+	validators
+}
+
+pub(crate) fn nominators_of(validator: AccountId) -> Vec<Bond<AccountId, Balance>> {
+	let mut nominators = <NominatorBonds<Test>>::iter_prefix(validator)
+		.map(|(owner, amount)| Bond { owner, amount })
+		.collect::<Vec<_>>();
+	nominators.sort();
+	nominators
+}
+
+/// Pay out every page of every validator awarded points in `session_idx`.
+pub(crate) fn payout_stakers(session_idx: SessionIndex) {
+	let validators = <AwardedPts<Test>>::iter_prefix(session_idx)
+		.map(|(validator, _)| validator)
+		.collect::<Vec<_>>();
+	for validator in validators {
+		let mut page = 0;
+		while NodleStaking::payout_stakers(RuntimeOrigin::signed(999), validator, session_idx, page).is_ok() {
+			page += 1;
+		}
+	}
 }
 
 pub(crate) fn selected_validators() -> Vec<AccountId> {
-	NodleStaking::selected_validators()
+	NodleStaking::selected_validators().into_inner()
 }
 
 pub(crate) fn on_offence_now(
@@ -0,0 +1,165 @@
+/*
+ * This file is part of the Nodle Chain distributed at https://github.com/NodleCode/chain
+ * Copyright (C) 2020-2022  Nodle International
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! The validators pool, the active validators sorted by decreasing stake.
+//!
+//! Every validator has its own node in `ValidatorPool`, linked to the validators right before and
+//! after it. Changing the stake of a validator only touches its node and the ones of its old and
+//! new neighbours, and the top validators are read without going through the whole pool.
+
+use super::{BalanceOf, Config, ValidatorPool, ValidatorPoolHead};
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::traits::Get;
+use sp_runtime::RuntimeDebug;
+
+/// A validator in the pool.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, scale_info::TypeInfo)]
+pub struct PoolNode<AccountId, Balance> {
+	/// Bond of the validator along with the ones of its nominators.
+	pub stake: Balance,
+	/// The validator right before, with a stake at least as high.
+	pub prev: Option<AccountId>,
+	/// The validator right after, with a stake at most as high.
+	pub next: Option<AccountId>,
+}
+
+/// Number of validators in the pool.
+pub(crate) fn len<T: Config>() -> u32 {
+	<ValidatorPool<T>>::count()
+}
+
+pub(crate) fn contains<T: Config>(who: &T::AccountId) -> bool {
+	<ValidatorPool<T>>::contains_key(who)
+}
+
+/// The validators in the pool along with their stake, highest stake first.
+pub(crate) fn iter<T: Config>() -> impl Iterator<Item = (T::AccountId, BalanceOf<T>)> {
+	let mut cursor = <ValidatorPoolHead<T>>::get();
+	sp_std::iter::from_fn(move || {
+		let who = cursor.take()?;
+		let node = <ValidatorPool<T>>::get(&who)?;
+		cursor = node.next;
+		Some((who, node.stake))
+	})
+}
+
+/// Put `who` in the pool with `stake`, or move it to the place of its new `stake` if it is
+/// already in. Fails when there is no room left within `MaxValidators` for a new validator.
+pub(crate) fn upsert<T: Config>(who: &T::AccountId, stake: BalanceOf<T>) -> Result<(), ()> {
+	if let Some(mut node) = <ValidatorPool<T>>::get(who) {
+		let fits_after_prev = node
+			.prev
+			.as_ref()
+			.and_then(stake_of::<T>)
+			.map_or(true, |prev| prev >= stake);
+		let fits_before_next = node
+			.next
+			.as_ref()
+			.and_then(stake_of::<T>)
+			.map_or(true, |next| next <= stake);
+		if fits_after_prev && fits_before_next {
+			node.stake = stake;
+			<ValidatorPool<T>>::insert(who, node);
+			return Ok(());
+		}
+		remove::<T>(who);
+		// the new place is searched from the old one
+		insert::<T>(who, stake, node.prev.or(node.next));
+		return Ok(());
+	} else if len::<T>() >= T::MaxValidators::get() {
+		return Err(());
+	}
+
+	insert::<T>(who, stake, None);
+	Ok(())
+}
+
+/// Take `who` out of the pool, returns whether it was in.
+pub(crate) fn remove<T: Config>(who: &T::AccountId) -> bool {
+	let node = match <ValidatorPool<T>>::take(who) {
+		Some(node) => node,
+		None => return false,
+	};
+
+	match &node.prev {
+		Some(prev) => set_next::<T>(prev, node.next.clone()),
+		None => <ValidatorPoolHead<T>>::set(node.next.clone()),
+	}
+	if let Some(next) = &node.next {
+		set_prev::<T>(next, node.prev);
+	}
+	true
+}
+
+fn stake_of<T: Config>(who: &T::AccountId) -> Option<BalanceOf<T>> {
+	<ValidatorPool<T>>::get(who).map(|node| node.stake)
+}
+
+/// Link a new node for `who` after all the validators with a stake at least as high. The place
+/// is searched from the node of `hint` when given, or from the head. Either way the walk goes
+/// through at most `MaxValidators` nodes.
+fn insert<T: Config>(who: &T::AccountId, stake: BalanceOf<T>, hint: Option<T::AccountId>) {
+	let (mut prev, mut next) = match hint.and_then(|hint| <ValidatorPool<T>>::get(&hint).map(|node| (hint, node))) {
+		// walk backward from the hint while the stakes are lower
+		Some((hint, node)) if node.stake < stake => {
+			let (mut prev, mut next) = (node.prev, Some(hint));
+			while let Some(node) = prev.as_ref().and_then(|current| <ValidatorPool<T>>::get(current)) {
+				if node.stake >= stake {
+					break;
+				}
+				next = prev;
+				prev = node.prev;
+			}
+			(prev, next)
+		}
+		Some((hint, node)) => (Some(hint), node.next),
+		None => (None, <ValidatorPoolHead<T>>::get()),
+	};
+	while let Some(node) = next.as_ref().and_then(|current| <ValidatorPool<T>>::get(current)) {
+		if node.stake < stake {
+			break;
+		}
+		prev = next;
+		next = node.next;
+	}
+
+	match &prev {
+		Some(prev) => set_next::<T>(prev, Some(who.clone())),
+		None => <ValidatorPoolHead<T>>::put(who),
+	}
+	if let Some(next) = &next {
+		set_prev::<T>(next, Some(who.clone()));
+	}
+	<ValidatorPool<T>>::insert(who, PoolNode { stake, prev, next });
+}
+
+fn set_prev<T: Config>(who: &T::AccountId, prev: Option<T::AccountId>) {
+	<ValidatorPool<T>>::mutate_exists(who, |maybe_node| {
+		if let Some(node) = maybe_node {
+			node.prev = prev;
+		}
+	});
+}
+
+fn set_next<T: Config>(who: &T::AccountId, next: Option<T::AccountId>) {
+	<ValidatorPool<T>>::mutate_exists(who, |maybe_node| {
+		if let Some(node) = maybe_node {
+			node.next = next;
+		}
+	});
+}
@@ -15,53 +15,44 @@
  * You should have received a copy of the GNU General Public License
  * along with this program.  If not, see <http://www.gnu.org/licenses/>.
  */
-use codec::{Decode, Encode};
-#[cfg(feature = "std")]
-use serde::{Deserialize, Serialize};
-use sp_runtime::RuntimeDebug;
-use sp_std::prelude::*;
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{traits::Get, BoundedVec, CloneNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound};
+use sp_std::{convert::TryFrom, fmt::Debug, prelude::*};
 
-/// An ordered set backed by `Vec`
-#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
-#[derive(RuntimeDebug, PartialEq, Eq, Encode, Decode, Clone, scale_info::TypeInfo)]
-pub struct OrderedSet<T>(pub Vec<T>);
+/// An ordered set backed by `BoundedVec`
+#[derive(
+	CloneNoBound, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound, Encode, Decode, MaxEncodedLen, scale_info::TypeInfo,
+)]
+#[codec(mel_bound(T: MaxEncodedLen))]
+#[scale_info(skip_type_params(S))]
+pub struct OrderedSet<T: Ord + Clone + Debug, S: Get<u32>>(pub BoundedVec<T, S>);
 
-impl<T> Default for OrderedSet<T> {
+impl<T: Ord + Clone + Debug, S: Get<u32>> Default for OrderedSet<T, S> {
 	/// Create a default empty set
 	fn default() -> Self {
-		Self(Vec::new())
+		Self(BoundedVec::default())
 	}
 }
 
-impl<T: Ord> OrderedSet<T> {
+impl<T: Ord + Clone + Debug, S: Get<u32>> OrderedSet<T, S> {
 	/// Create a new empty set
 	pub fn new() -> Self {
-		Self(Vec::new())
+		Self(BoundedVec::default())
 	}
 
-	/// Create a set from a `Vec`.
-	/// `v` will be sorted and dedup first.
-	pub fn from(mut v: Vec<T>) -> Self {
-		v.sort();
-		v.dedup();
-		Self::from_sorted_set(v)
-	}
-
-	/// Create a set from a `Vec`.
+	/// Create a set from a `BoundedVec`.
 	/// Assume `v` is sorted and contain unique elements.
-	pub fn from_sorted_set(v: Vec<T>) -> Self {
+	pub fn from_sorted_set(v: BoundedVec<T, S>) -> Self {
 		Self(v)
 	}
 
 	/// Insert an element.
-	/// Return true if insertion happened.
-	pub fn insert(&mut self, value: T) -> bool {
+	/// Return true if insertion happened, false if the element is already in the set
+	/// and an error if the set is full.
+	pub fn try_insert(&mut self, value: T) -> Result<bool, ()> {
 		match self.0.binary_search(&value) {
-			Ok(_) => false,
-			Err(loc) => {
-				self.0.insert(loc, value);
-				true
-			}
+			Ok(_) => Ok(false),
+			Err(loc) => self.0.try_insert(loc, value).map(|_| true).map_err(|_| ()),
 		}
 	}
 
@@ -79,21 +70,35 @@ impl<T: Ord> OrderedSet<T> {
 
 	/// Return if the set contains `value`
 	pub fn contains(&self, value: &T) -> Option<usize> {
-		// self.0.binary_search(&value).is_ok()
 		match self.0.binary_search(value) {
 			Ok(loc) => Some(loc),
 			Err(_) => None,
 		}
 	}
 
+	/// Return a mutable reference to the element equal to `value`, if any.
+	/// The element must not be changed in a way that alters its ordering.
+	pub fn get_mut(&mut self, value: &T) -> Option<&mut T> {
+		match self.0.binary_search(value) {
+			Ok(loc) => self.0.get_mut(loc),
+			Err(_) => None,
+		}
+	}
+
 	/// Clear the set
 	pub fn clear(&mut self) {
-		self.0.clear();
+		self.0.truncate(0);
 	}
 }
 
-impl<T: Ord> From<Vec<T>> for OrderedSet<T> {
-	fn from(v: Vec<T>) -> Self {
-		Self::from(v)
+impl<T: Ord + Clone + Debug, S: Get<u32>> TryFrom<Vec<T>> for OrderedSet<T, S> {
+	type Error = ();
+
+	/// Create a set from a `Vec`.
+	/// `v` will be sorted and dedup first, fails if it holds more than `S` elements.
+	fn try_from(mut v: Vec<T>) -> Result<Self, Self::Error> {
+		v.sort();
+		v.dedup();
+		BoundedVec::try_from(v).map(Self::from_sorted_set).map_err(|_| ())
 	}
 }
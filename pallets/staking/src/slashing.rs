@@ -16,14 +16,17 @@
  * along with this program.  If not, see <http://www.gnu.org/licenses/>.
  */
 
-use super::{BalanceOf, Config, Event, NegativeImbalanceOf, Pallet, Store};
+use super::{BalanceOf, Config, Event, NegativeImbalanceOf, Pallet, SnapshotOf, Store, UnappliedSlashOf};
 use crate::hooks::SessionInterface;
-use crate::types::{SpanIndex, UnappliedSlash, ValidatorSnapshot};
-use codec::{Decode, Encode};
-use frame_support::traits::{Currency, Get, Imbalance, LockableCurrency, OnUnbalanced, WithdrawReasons};
+use crate::types::{SpanIndex, UnappliedSlash};
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	traits::{Currency, Get, Imbalance, LockableCurrency, OnUnbalanced, WithdrawReasons},
+	BoundedVec, RuntimeDebugNoBound,
+};
 use sp_runtime::{
 	traits::{Saturating, Zero},
-	DispatchResult, Perbill, RuntimeDebug,
+	DispatchResult, Perbill,
 };
 use sp_staking::{offence::DisableStrategy, SessionIndex};
 use sp_std::{cmp::Ordering, vec::Vec};
@@ -44,8 +47,9 @@ impl SlashingSpan {
 }
 
 /// An encoding of all of a nominator's slashing spans.
-#[derive(Encode, Decode, RuntimeDebug, scale_info::TypeInfo)]
-pub struct SlashingSpans {
+#[derive(Encode, Decode, RuntimeDebugNoBound, MaxEncodedLen, scale_info::TypeInfo)]
+#[scale_info(skip_type_params(MaxSpans))]
+pub struct SlashingSpans<MaxSpans: Get<u32>> {
 	// the index of the current slashing span of the nominator. different for
 	// every controller, resets when the account hits free balance 0.
 	span_index: SpanIndex,
@@ -55,10 +59,11 @@ pub struct SlashingSpans {
 	last_nonzero_slash: SessionIndex,
 	// all prior slashing spans' start indices, in reverse order (most recent first)
 	// encoded as offsets relative to the slashing span after it.
-	prior: Vec<SessionIndex>,
+	// at most `MaxSpans` are kept, the oldest one is dropped first.
+	prior: BoundedVec<SessionIndex, MaxSpans>,
 }
 
-impl SlashingSpans {
+impl<MaxSpans: Get<u32>> SlashingSpans<MaxSpans> {
 	// creates a new record of slashing spans for a controller, starting at the beginning
 	// of the bonding period, relative to now.
 	pub(crate) fn new(window_start: SessionIndex) -> Self {
@@ -69,7 +74,7 @@ impl SlashingSpans {
 			// the first slash is applied. setting equal to `window_start` would
 			// put a time limit on nominations.
 			last_nonzero_slash: 0,
-			prior: Vec::new(),
+			prior: BoundedVec::default(),
 		}
 	}
 
@@ -82,7 +87,11 @@ impl SlashingSpans {
 			return false;
 		}
 		let last_length = next_start.saturating_sub(self.last_start);
-		self.prior.insert(0, last_length);
+		// make room for the span being ended by forgetting the oldest one.
+		if self.prior.len() >= MaxSpans::get() as usize {
+			self.prior.truncate((MaxSpans::get() as usize).saturating_sub(1));
+		}
+		let _ = self.prior.try_insert(0, last_length);
 		self.last_start = next_start;
 		self.span_index = self.span_index.saturating_add(1);
 		true
@@ -117,6 +126,11 @@ impl SlashingSpans {
 		self.last_nonzero_slash
 	}
 
+	// the index of the oldest slashing span still recorded.
+	fn earliest_span_index(&self) -> SpanIndex {
+		self.span_index.saturating_sub(self.prior.len() as SpanIndex)
+	}
+
 	// prune the slashing spans against a window, whose start era index is given.
 	//
 	// If this returns `Some`, then it includes a range start..end of all the span
@@ -127,11 +141,11 @@ impl SlashingSpans {
 			.skip(1) // skip ongoing span.
 			.position(|span| span.length.map_or(false, |len| span.start + len <= window_start));
 
-		let earliest_span_index = self.span_index - self.prior.len() as SpanIndex;
+		let earliest_span_index = self.earliest_span_index();
 		let pruned = match old_idx {
 			Some(o) => {
 				self.prior.truncate(o);
-				let new_earliest = self.span_index - self.prior.len() as SpanIndex;
+				let new_earliest = self.earliest_span_index();
 				Some((earliest_span_index, new_earliest))
 			}
 			None => None,
@@ -144,7 +158,7 @@ impl SlashingSpans {
 }
 
 /// A slashing-span record for a particular controller.
-#[derive(Encode, Decode, Default, scale_info::TypeInfo)]
+#[derive(Encode, Decode, Default, MaxEncodedLen, scale_info::TypeInfo)]
 pub struct SpanRecord<Balance> {
 	pub slashed: Balance,
 	pub paid_out: Balance,
@@ -165,8 +179,8 @@ pub(crate) struct SlashParams<'a, T: 'a + Config> {
 	pub(crate) controller: &'a T::AccountId,
 	/// The proportion of the slash.
 	pub(crate) slash: Perbill,
-	/// The exposure of the controller and all nominators.
-	pub(crate) exposure: &'a ValidatorSnapshot<T::AccountId, BalanceOf<T>>,
+	/// The exposure of the controller, its nominators are read from the pages of `slash_session`.
+	pub(crate) exposure: &'a SnapshotOf<T>,
 	/// The session where the offence occurred.
 	pub(crate) slash_session: SessionIndex,
 	/// The first era in the current bonding period.
@@ -186,7 +200,7 @@ pub(crate) struct SlashParams<'a, T: 'a + Config> {
 ///
 /// The pending slash record returned does not have initialized reporters. Those have
 /// to be set at a higher level, if any.
-pub(crate) fn compute_slash<T: Config>(params: SlashParams<T>) -> Option<UnappliedSlash<T::AccountId, BalanceOf<T>>> {
+pub(crate) fn compute_slash<T: Config>(params: SlashParams<T>) -> Option<UnappliedSlashOf<T>> {
 	let SlashParams {
 		controller,
 		slash,
@@ -270,9 +284,11 @@ pub(crate) fn compute_slash<T: Config>(params: SlashParams<T>) -> Option<Unappli
 	Some(UnappliedSlash {
 		validator: controller.clone(),
 		own: spans.slash_of,
-		others: nominators_slashed,
-		reporters: Vec::new(),
+		// one entry per nominator of the exposure, which is bounded the same way.
+		others: BoundedVec::truncate_from(nominators_slashed),
+		reporters: BoundedVec::default(),
 		payout: spans.paid_out,
+		slash_session,
 	})
 }
 
@@ -310,7 +326,7 @@ fn slash_nominators<T: Config>(
 	nominators_slashed: &mut Vec<(T::AccountId, BalanceOf<T>)>,
 ) -> BalanceOf<T> {
 	let SlashParams {
-		controller: _,
+		controller: validator,
 		slash,
 		exposure,
 		slash_session,
@@ -320,10 +336,11 @@ fn slash_nominators<T: Config>(
 		disable_strategy: _,
 	} = params;
 
+	let nominators = <Pallet<T>>::exposed_nominators(slash_session, validator, exposure.nominator_count);
 	let mut reward_payout: BalanceOf<T> = Zero::zero();
-	nominators_slashed.reserve(exposure.nominators.len());
+	nominators_slashed.reserve(nominators.len());
 
-	for nominator in &exposure.nominators {
+	for nominator in &nominators {
 		let controller = &nominator.owner;
 
 		// the era slash of a nominator always grows, if the validator
@@ -378,7 +395,7 @@ struct InspectingSpans<'a, T: Config + 'a> {
 	dirty: bool,
 	window_start: SessionIndex,
 	controller: &'a T::AccountId,
-	spans: SlashingSpans,
+	spans: SlashingSpans<T::MaxSlashingSpans>,
 	paid_out: BalanceOf<T>,
 	slash_of: BalanceOf<T>,
 	reward_proportion: Perbill,
@@ -415,7 +432,13 @@ impl<'a, T: 'a + Config> InspectingSpans<'a, T> {
 	}
 
 	fn end_span(&mut self, now: SessionIndex) {
+		let earliest_span_index = self.spans.earliest_span_index();
 		self.dirty = self.spans.end_span(now) || self.dirty;
+
+		// drop the records of the spans forgotten to stay within `MaxSlashingSpans`.
+		for span_index in earliest_span_index..self.spans.earliest_span_index() {
+			<Pallet<T> as Store>::SpanSlash::remove(&(self.controller.clone(), span_index));
+		}
 	}
 
 	/// add some value to the slash of the staker.
@@ -503,6 +526,49 @@ impl<'a, T: 'a + Config> Drop for InspectingSpans<'a, T> {
 	}
 }
 
+/// Reduce a deferred slash by `reduction`, along with the slashing records of its offence so a
+/// later offence in the same session or span is measured against the slash actually applied.
+pub(crate) fn reduce_slash<T: Config>(slash: &mut UnappliedSlashOf<T>, reduction: Perbill) {
+	let session = slash.slash_session;
+
+	let own_cut = reduction * slash.own;
+	slash.own = slash.own.saturating_sub(own_cut);
+	<Pallet<T> as Store>::ValidatorSlashInSession::mutate(session, &slash.validator, |record| {
+		if let Some((proportion, amount)) = record {
+			*proportion = proportion.saturating_sub(reduction * *proportion);
+			*amount = amount.saturating_sub(reduction * *amount);
+		}
+	});
+	reduce_span_slash::<T>(&slash.validator, session, own_cut);
+
+	for (nominator, value) in slash.others.iter_mut() {
+		let cut = reduction * *value;
+		*value = value.saturating_sub(cut);
+		<Pallet<T> as Store>::NominatorSlashInSession::mutate(session, &*nominator, |record| {
+			if let Some(amount) = record {
+				*amount = amount.saturating_sub(cut);
+			}
+		});
+		reduce_span_slash::<T>(nominator, session, cut);
+	}
+
+	slash.payout = slash.payout.saturating_sub(reduction * slash.payout);
+}
+
+/// Take `cut` off of the slash recorded for the span of `controller` covering `session`.
+fn reduce_span_slash<T: Config>(controller: &T::AccountId, session: SessionIndex, cut: BalanceOf<T>) {
+	let spans = match <Pallet<T>>::slashing_spans(controller) {
+		None => return,
+		Some(s) => s,
+	};
+
+	if let Some(span) = spans.iter().find(|span| span.contains_era(session)) {
+		<Pallet<T> as Store>::SpanSlash::mutate(&(controller.clone(), span.index), |record| {
+			record.slashed = record.slashed.saturating_sub(cut);
+		});
+	}
+}
+
 /// Clear slashing metadata for an obsolete session.
 pub(crate) fn clear_session_metadata<T: Config>(obsolete_session: SessionIndex) {
 	// Since hook function context, Safe to Ignoring the result MultiRemovalResults,
@@ -626,7 +692,7 @@ fn do_slash_nominator<T: Config>(
 
 				<Pallet<T> as Store>::ValidatorState::mutate(validator, |validator_state| {
 					if let Some(validator_state) = validator_state {
-						validator_state.dec_nominator(controller.clone(), slashed_value);
+						<Pallet<T>>::dec_nominator_bond(validator_state, controller, slashed_value);
 						if validator_state.is_active() {
 							<Pallet<T>>::update_validators_pool(validator.clone(), validator_state.total);
 						}
@@ -671,7 +737,7 @@ fn do_slash_nominator<T: Config>(
 }
 
 /// Apply a previously-unapplied slash.
-pub(crate) fn apply_slash<T: Config>(unapplied_slash: UnappliedSlash<T::AccountId, BalanceOf<T>>) {
+pub(crate) fn apply_slash<T: Config>(unapplied_slash: UnappliedSlashOf<T>) {
 	let mut slashed_imbalance = NegativeImbalanceOf::<T>::zero();
 	let mut reward_payout = unapplied_slash.payout;
 
@@ -682,7 +748,7 @@ pub(crate) fn apply_slash<T: Config>(unapplied_slash: UnappliedSlash<T::AccountI
 		&mut slashed_imbalance,
 	);
 
-	for &(ref nominator, nominator_slash) in &unapplied_slash.others {
+	for &(ref nominator, nominator_slash) in unapplied_slash.others.iter() {
 		do_slash_nominator::<T>(
 			nominator,
 			&unapplied_slash.validator,
@@ -20,16 +20,25 @@
 use super::*;
 use crate::mock::{
 	balances, bond_nominator, bond_validator, events, is_disabled, last_event, on_offence_in_session, on_offence_now,
-	set_author, start_session, Balance, Balances, CancelOrigin, ExtBuilder, NodleStaking, RuntimeEvent as MetaEvent,
-	RuntimeOrigin, Session, System, Test,
+	set_author, start_session, AccountId, Balance, Balances, CancelOrigin, ExtBuilder, Historical, MaxChunkUnlock,
+	MaxStakeRewards, MaxValidatorPerNominator, MaxValidators, NodleStaking, RuntimeEvent as MetaEvent, RuntimeOrigin,
+	Session, System, Test,
 };
 use crate::set::OrderedSet;
-use crate::types::{Bond, StakeReward, ValidatorSnapshot, ValidatorStatus};
-use frame_support::{assert_noop, assert_ok, traits::Currency};
+use crate::types::{Bond, SessionPerformance, StakeReward, UnlockChunk, ValidatorSnapshot, ValidatorStatus};
+use frame_support::{
+	assert_noop, assert_ok,
+	dispatch::{DispatchClass, Pays},
+	traits::{Currency, Get, KeyOwnerProofSystem},
+	BoundedVec,
+};
+use sp_consensus_aura::digests::CompatibleDigestItem;
+use sp_consensus_slots::{EquivocationProof, Slot};
+use sp_core::H256;
 use sp_runtime::{
-	testing::UintAuthorityId,
-	traits::{BadOrigin, Zero},
-	Perbill,
+	testing::{Header, TestSignature, UintAuthorityId},
+	traits::{BadOrigin, Header as _, Zero},
+	Perbill, RuntimeAppPublic,
 };
 use sp_staking::offence::{DisableStrategy, OffenceDetails};
 
@@ -1093,6 +1102,7 @@ fn payout_distribution_to_solo_validators() {
 			mock::mint_rewards(1_000_000);
 
 			mock::start_active_session(6);
+			mock::payout_stakers(5);
 
 			let mut new1 = vec![
 				Event::ValidatorChosen(6, 1, 100),
@@ -1101,13 +1111,13 @@ fn payout_distribution_to_solo_validators() {
 				Event::ValidatorChosen(6, 4, 70),
 				Event::ValidatorChosen(6, 5, 60),
 				Event::NewSession(25, 6, 5, 400),
-				Event::StakeReward(1, 1000000),
 				Event::ValidatorChosen(7, 1, 100),
 				Event::ValidatorChosen(7, 2, 90),
 				Event::ValidatorChosen(7, 3, 80),
 				Event::ValidatorChosen(7, 4, 70),
 				Event::ValidatorChosen(7, 5, 60),
 				Event::NewSession(30, 7, 5, 400),
+				Event::StakeReward(1, 1000000),
 			];
 			expected.append(&mut new1);
 			assert_eq!(events(), expected);
@@ -1118,16 +1128,17 @@ fn payout_distribution_to_solo_validators() {
 			mock::mint_rewards(1_000_000);
 
 			mock::start_active_session(7);
+			mock::payout_stakers(6);
 
 			let mut new2 = vec![
-				Event::StakeReward(1, 600000),
-				Event::StakeReward(2, 400000),
 				Event::ValidatorChosen(8, 1, 100),
 				Event::ValidatorChosen(8, 2, 90),
 				Event::ValidatorChosen(8, 3, 80),
 				Event::ValidatorChosen(8, 4, 70),
 				Event::ValidatorChosen(8, 5, 60),
 				Event::NewSession(35, 8, 5, 400),
+				Event::StakeReward(1, 600000),
+				Event::StakeReward(2, 400000),
 			];
 			expected.append(&mut new2);
 			assert_eq!(events(), expected);
@@ -1141,19 +1152,20 @@ fn payout_distribution_to_solo_validators() {
 			mock::mint_rewards(1_000_000);
 
 			mock::start_active_session(8);
+			mock::payout_stakers(7);
 
 			let mut new3 = vec![
-				Event::StakeReward(5, 200000),
-				Event::StakeReward(3, 200000),
-				Event::StakeReward(4, 200000),
-				Event::StakeReward(1, 200000),
-				Event::StakeReward(2, 200000),
 				Event::ValidatorChosen(9, 1, 100),
 				Event::ValidatorChosen(9, 2, 90),
 				Event::ValidatorChosen(9, 3, 80),
 				Event::ValidatorChosen(9, 4, 70),
 				Event::ValidatorChosen(9, 5, 60),
 				Event::NewSession(40, 9, 5, 400),
+				Event::StakeReward(5, 200000),
+				Event::StakeReward(3, 200000),
+				Event::StakeReward(4, 200000),
+				Event::StakeReward(1, 200000),
+				Event::StakeReward(2, 200000),
 			];
 			expected.append(&mut new3);
 			assert_eq!(events(), expected);
@@ -1204,7 +1216,7 @@ fn payout_distribution_to_solo_validators() {
 			assert_eq!(Balances::total_balance(&4), 201000);
 
 			assert_eq!(
-				NodleStaking::stake_rewards(&5),
+				NodleStaking::stake_rewards(&5).into_inner(),
 				[StakeReward {
 					session_idx: 7,
 					value: 200000,
@@ -1213,7 +1225,7 @@ fn payout_distribution_to_solo_validators() {
 
 			assert_ok!(NodleStaking::withdraw_staking_rewards(RuntimeOrigin::signed(5)));
 
-			assert_eq!(NodleStaking::stake_rewards(&5), []);
+			assert_eq!(NodleStaking::stake_rewards(&5).into_inner(), []);
 
 			let mut new8 = vec![Event::Rewarded(5, 200000)];
 			expected.append(&mut new8);
@@ -1222,7 +1234,7 @@ fn payout_distribution_to_solo_validators() {
 			assert_eq!(mock::balances(&5), (201000, 60));
 			assert_eq!(Balances::total_balance(&5), 201000);
 
-			assert_eq!(NodleStaking::stake_rewards(&6), []);
+			assert_eq!(NodleStaking::stake_rewards(&6).into_inner(), []);
 
 			assert_noop!(
 				NodleStaking::withdraw_staking_rewards(RuntimeOrigin::signed(6)),
@@ -1230,14 +1242,16 @@ fn payout_distribution_to_solo_validators() {
 			);
 
 			<NodleStaking as Store>::StakeRewards::mutate(6, |rewards| {
-				rewards.push(StakeReward {
-					session_idx: 7,
-					value: 1,
-				})
+				rewards
+					.try_push(StakeReward {
+						session_idx: 7,
+						value: 1,
+					})
+					.unwrap()
 			});
 
 			assert_eq!(
-				NodleStaking::stake_rewards(6),
+				NodleStaking::stake_rewards(6).into_inner(),
 				[StakeReward {
 					session_idx: 7,
 					value: 1,
@@ -1251,7 +1265,7 @@ fn payout_distribution_to_solo_validators() {
 			assert_eq!(events(), expected);
 
 			assert_eq!(
-				NodleStaking::stake_rewards(&6),
+				NodleStaking::stake_rewards(&6).into_inner(),
 				[StakeReward {
 					session_idx: 7,
 					value: 1,
@@ -1261,10 +1275,12 @@ fn payout_distribution_to_solo_validators() {
 			mock::start_active_session(8);
 
 			<NodleStaking as Store>::StakeRewards::mutate(6, |rewards| {
-				rewards.push(StakeReward {
-					session_idx: 8,
-					value: 2,
-				})
+				rewards
+					.try_push(StakeReward {
+						session_idx: 8,
+						value: 2,
+					})
+					.unwrap()
 			});
 
 			assert_ok!(NodleStaking::withdraw_staking_rewards(RuntimeOrigin::signed(6)),);
@@ -1274,7 +1290,7 @@ fn payout_distribution_to_solo_validators() {
 			assert_eq!(events(), expected);
 
 			assert_eq!(
-				NodleStaking::stake_rewards(&6),
+				NodleStaking::stake_rewards(&6).into_inner(),
 				[
 					StakeReward {
 						session_idx: 7,
@@ -1290,10 +1306,12 @@ fn payout_distribution_to_solo_validators() {
 			mock::start_active_session(9);
 
 			<NodleStaking as Store>::StakeRewards::mutate(6, |rewards| {
-				rewards.push(StakeReward {
-					session_idx: 9,
-					value: 1,
-				})
+				rewards
+					.try_push(StakeReward {
+						session_idx: 9,
+						value: 1,
+					})
+					.unwrap()
 			});
 
 			assert_ok!(NodleStaking::withdraw_staking_rewards(RuntimeOrigin::signed(6)),);
@@ -1310,7 +1328,7 @@ fn payout_distribution_to_solo_validators() {
 			expected.append(&mut new11);
 			assert_eq!(events(), expected);
 
-			assert_eq!(NodleStaking::stake_rewards(&6), []);
+			assert_eq!(NodleStaking::stake_rewards(&6).into_inner(), []);
 
 			assert_eq!(mock::balances(&6), (1004, 50));
 			assert_eq!(Balances::total_balance(&6), 1004);
@@ -1321,6 +1339,35 @@ fn payout_distribution_to_solo_validators() {
 		});
 }
 
+#[test]
+fn stake_rewards_over_the_bound_add_up_to_the_latest() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 1000), (2, 1000), (3, 1000), (4, 1000), (5, 1000)])
+		.with_validators(vec![(1, 100), (2, 90), (3, 80), (4, 70), (5, 60)])
+		.tst_staking_build()
+		.execute_with(|| {
+			mock::start_active_session(5);
+
+			let pending = (0..MaxStakeRewards::get())
+				.map(|session_idx| StakeReward { session_idx, value: 1 })
+				.collect::<Vec<_>>();
+			<NodleStaking as Store>::StakeRewards::insert(1, BoundedVec::truncate_from(pending.clone()));
+
+			mock::set_author(5, 1, 100);
+			mock::mint_rewards(1_000_000);
+
+			mock::start_active_session(6);
+			mock::payout_stakers(5);
+
+			assert!(events().contains(&Event::StakeReward(1, 1_000_000)));
+
+			let rewards = NodleStaking::stake_rewards(1);
+			assert_eq!(rewards.len(), MaxStakeRewards::get() as usize);
+			assert_eq!(rewards[..rewards.len() - 1], pending[..pending.len() - 1]);
+			assert_eq!(rewards.last().unwrap().value, 1_000_001);
+		});
+}
+
 #[test]
 fn validator_commission() {
 	ExtBuilder::default()
@@ -1410,20 +1457,21 @@ fn validator_commission() {
 			mock::mint_rewards(1_000_000);
 
 			mock::start_active_session(8);
+			mock::payout_stakers(7);
 
 			let mut new4 = vec![
 				Event::ValidatorChosen(8, 1, 40),
 				Event::ValidatorChosen(8, 4, 40),
 				Event::NewSession(35, 8, 2, 80),
+				Event::ValidatorChosen(9, 1, 40),
+				Event::ValidatorChosen(9, 4, 40),
+				Event::NewSession(40, 9, 2, 80),
 				Event::StakeReward(4, 300000),
 				Event::StakeReward(5, 100000),
 				Event::StakeReward(6, 100000),
 				Event::StakeReward(1, 300000),
 				Event::StakeReward(2, 100000),
 				Event::StakeReward(3, 100000),
-				Event::ValidatorChosen(9, 1, 40),
-				Event::ValidatorChosen(9, 4, 40),
-				Event::NewSession(40, 9, 2, 80),
 			];
 			expected.append(&mut new4);
 			assert_eq!(events(), expected);
@@ -1678,7 +1726,7 @@ fn multiple_nominations() {
 			assert_eq!(NodleStaking::nominator_state(6).unwrap().nominations.0.len(), 4usize);
 
 			assert_eq!(
-				NodleStaking::validator_state(&2).unwrap().nominators.0,
+				mock::nominators_of(2),
 				vec![
 					Bond { owner: 6, amount: 10 },
 					Bond { owner: 7, amount: 80 },
@@ -1900,7 +1948,7 @@ fn switch_nomination_works() {
 
 			assert_eq!(
 				NodleStaking::nominator_state(6).unwrap().nominations,
-				OrderedSet::from([Bond { owner: 1, amount: 10 }, Bond { owner: 2, amount: 10 },].to_vec()),
+				OrderedSet::try_from([Bond { owner: 1, amount: 10 }, Bond { owner: 2, amount: 10 },].to_vec()).unwrap(),
 			);
 			assert_eq!(NodleStaking::nominator_state(6).unwrap().total, 20);
 			assert_eq!(NodleStaking::nominator_state(6).unwrap().active_bond, 20);
@@ -1945,7 +1993,7 @@ fn switch_nomination_works() {
 
 			assert_eq!(
 				NodleStaking::nominator_state(6).unwrap().nominations,
-				OrderedSet::from([Bond { owner: 1, amount: 20 }].to_vec()),
+				OrderedSet::try_from([Bond { owner: 1, amount: 20 }].to_vec()).unwrap(),
 			);
 			assert_eq!(NodleStaking::nominator_state(6).unwrap().total, 20);
 			assert_eq!(NodleStaking::nominator_state(6).unwrap().active_bond, 20);
@@ -1966,14 +2014,15 @@ fn switch_nomination_works() {
 
 			assert_eq!(
 				NodleStaking::nominator_state(6).unwrap().nominations,
-				OrderedSet::from(
+				OrderedSet::try_from(
 					[
 						Bond { owner: 1, amount: 20 },
 						Bond { owner: 2, amount: 10 },
 						Bond { owner: 3, amount: 10 },
 					]
 					.to_vec()
-				),
+				)
+				.unwrap(),
 			);
 			assert_eq!(NodleStaking::nominator_state(6).unwrap().total, 40);
 			assert_eq!(NodleStaking::nominator_state(6).unwrap().active_bond, 40);
@@ -2012,14 +2061,15 @@ fn switch_nomination_works() {
 
 			assert_eq!(
 				NodleStaking::nominator_state(6).unwrap().nominations,
-				OrderedSet::from(
+				OrderedSet::try_from(
 					[
 						Bond { owner: 1, amount: 20 },
 						Bond { owner: 4, amount: 15 },
 						Bond { owner: 5, amount: 20 },
 					]
 					.to_vec()
-				),
+				)
+				.unwrap(),
 			);
 			assert_eq!(NodleStaking::nominator_state(6).unwrap().total, 55);
 			assert_eq!(NodleStaking::nominator_state(6).unwrap().active_bond, 55);
@@ -2161,7 +2211,7 @@ fn reconciliation_basics_works() {
 
 			assert_eq!(
 				NodleStaking::nominator_state(6).unwrap().nominations,
-				OrderedSet::from([Bond { owner: 1, amount: 10 }, Bond { owner: 2, amount: 40 },].to_vec()),
+				OrderedSet::try_from([Bond { owner: 1, amount: 10 }, Bond { owner: 2, amount: 40 },].to_vec()).unwrap(),
 			);
 			assert_eq!(NodleStaking::nominator_state(6).unwrap().total, 50);
 			assert_eq!(NodleStaking::nominator_state(6).unwrap().active_bond, 50);
@@ -2188,6 +2238,18 @@ fn reconciliation_basics_works() {
 				Error::<Test>::InvalidArguments,
 			);
 
+			assert_noop!(
+				NodleStaking::set_staking_limits(
+					RuntimeOrigin::signed(CancelOrigin::get()),
+					MaxValidators::get() + 1,
+					s1_min_stake,
+					s1_min_validator_bond,
+					s1_new_min_nomination_total_bond,
+					s1_new_min_nomination_chill_threshold,
+				),
+				Error::<Test>::InvalidArguments,
+			);
+
 			assert_noop!(
 				NodleStaking::set_staking_limits(
 					RuntimeOrigin::signed(CancelOrigin::get()),
@@ -2271,7 +2333,7 @@ fn reconciliation_basics_works() {
 
 			assert_eq!(
 				NodleStaking::nominator_state(6).unwrap().nominations,
-				OrderedSet::from([Bond { owner: 2, amount: 40 },].to_vec()),
+				OrderedSet::try_from([Bond { owner: 2, amount: 40 },].to_vec()).unwrap(),
 			);
 			assert_eq!(NodleStaking::nominator_state(6).unwrap().total, 50);
 			assert_eq!(NodleStaking::nominator_state(6).unwrap().active_bond, 40);
@@ -2279,7 +2341,7 @@ fn reconciliation_basics_works() {
 
 			assert_eq!(
 				NodleStaking::nominator_state(7).unwrap().nominations,
-				OrderedSet::from([].to_vec()),
+				OrderedSet::try_from([].to_vec()).unwrap(),
 			);
 			assert_eq!(NodleStaking::nominator_state(7).unwrap().total, 10);
 			assert_eq!(NodleStaking::nominator_state(7).unwrap().active_bond, 0);
@@ -2287,7 +2349,7 @@ fn reconciliation_basics_works() {
 
 			assert_eq!(
 				NodleStaking::nominator_state(10).unwrap().nominations,
-				OrderedSet::from([].to_vec()),
+				OrderedSet::try_from([].to_vec()).unwrap(),
 			);
 			assert_eq!(NodleStaking::nominator_state(10).unwrap().total, 10);
 			assert_eq!(NodleStaking::nominator_state(10).unwrap().active_bond, 0);
@@ -2295,7 +2357,7 @@ fn reconciliation_basics_works() {
 
 			assert_eq!(
 				NodleStaking::nominator_state(8).unwrap().nominations,
-				OrderedSet::from([].to_vec()),
+				OrderedSet::try_from([].to_vec()).unwrap(),
 			);
 			assert_eq!(NodleStaking::nominator_state(8).unwrap().total, 10);
 			assert_eq!(NodleStaking::nominator_state(8).unwrap().active_bond, 0);
@@ -2303,7 +2365,7 @@ fn reconciliation_basics_works() {
 
 			assert_eq!(
 				NodleStaking::nominator_state(9).unwrap().nominations,
-				OrderedSet::from([].to_vec()),
+				OrderedSet::try_from([].to_vec()).unwrap(),
 			);
 			assert_eq!(NodleStaking::nominator_state(9).unwrap().total, 10);
 			assert_eq!(NodleStaking::nominator_state(9).unwrap().active_bond, 0);
@@ -2483,15 +2545,12 @@ fn unfreeze_bond_arg_flag_works() {
 			assert_eq!(System::consumers(&10), 1);
 
 			assert_eq!(
-				NodleStaking::validator_state(1).unwrap().nominators,
-				OrderedSet::from(
-					[
-						Bond { owner: 6, amount: 10 },
-						Bond { owner: 7, amount: 10 },
-						Bond { owner: 10, amount: 25 },
-					]
-					.to_vec()
-				),
+				mock::nominators_of(1),
+				vec![
+					Bond { owner: 6, amount: 10 },
+					Bond { owner: 7, amount: 10 },
+					Bond { owner: 10, amount: 25 },
+				],
 			);
 
 			assert_ok!(NodleStaking::nominator_nominate(RuntimeOrigin::signed(6), 3, 20, false));
@@ -2504,14 +2563,11 @@ fn unfreeze_bond_arg_flag_works() {
 			expected.append(&mut new1);
 			assert_eq!(events(), expected);
 
-			assert_eq!(
-				NodleStaking::validator_state(3).unwrap().nominators,
-				OrderedSet::from([Bond { owner: 6, amount: 20 },].to_vec()),
-			);
+			assert_eq!(mock::nominators_of(3), vec![Bond { owner: 6, amount: 20 }]);
 
 			assert_eq!(
 				NodleStaking::nominator_state(6).unwrap().nominations,
-				OrderedSet::from([Bond { owner: 1, amount: 10 }, Bond { owner: 3, amount: 20 },].to_vec()),
+				OrderedSet::try_from([Bond { owner: 1, amount: 10 }, Bond { owner: 3, amount: 20 },].to_vec()).unwrap(),
 			);
 			assert_eq!(NodleStaking::nominator_state(6).unwrap().total, 30);
 			assert_eq!(NodleStaking::nominator_state(6).unwrap().active_bond, 30);
@@ -2529,7 +2585,7 @@ fn unfreeze_bond_arg_flag_works() {
 
 			assert_eq!(
 				NodleStaking::nominator_state(7).unwrap().nominations,
-				OrderedSet::from([Bond { owner: 1, amount: 10 }, Bond { owner: 3, amount: 20 },].to_vec()),
+				OrderedSet::try_from([Bond { owner: 1, amount: 10 }, Bond { owner: 3, amount: 20 },].to_vec()).unwrap(),
 			);
 			assert_eq!(NodleStaking::nominator_state(7).unwrap().total, 30);
 			assert_eq!(NodleStaking::nominator_state(7).unwrap().active_bond, 30);
@@ -2547,7 +2603,7 @@ fn unfreeze_bond_arg_flag_works() {
 
 			assert_eq!(
 				NodleStaking::nominator_state(8).unwrap().nominations,
-				OrderedSet::from([Bond { owner: 2, amount: 10 }, Bond { owner: 4, amount: 20 },].to_vec()),
+				OrderedSet::try_from([Bond { owner: 2, amount: 10 }, Bond { owner: 4, amount: 20 },].to_vec()).unwrap(),
 			);
 			assert_eq!(NodleStaking::nominator_state(8).unwrap().total, 30);
 			assert_eq!(NodleStaking::nominator_state(8).unwrap().active_bond, 30);
@@ -2592,14 +2648,11 @@ fn unfreeze_bond_arg_flag_works() {
 			expected.append(&mut new1);
 			assert_eq!(events(), expected);
 
-			assert_eq!(
-				NodleStaking::validator_state(1).unwrap().nominators,
-				OrderedSet::from([Bond { owner: 10, amount: 25 },].to_vec()),
-			);
+			assert_eq!(mock::nominators_of(1), vec![Bond { owner: 10, amount: 25 }]);
 
 			assert_eq!(
 				NodleStaking::nominator_state(6).unwrap().nominations,
-				OrderedSet::from([Bond { owner: 3, amount: 20 },].to_vec()),
+				OrderedSet::try_from([Bond { owner: 3, amount: 20 },].to_vec()).unwrap(),
 			);
 			assert_eq!(NodleStaking::nominator_state(6).unwrap().total, 30);
 			assert_eq!(NodleStaking::nominator_state(6).unwrap().active_bond, 20);
@@ -2607,7 +2660,7 @@ fn unfreeze_bond_arg_flag_works() {
 
 			assert_eq!(
 				NodleStaking::nominator_state(7).unwrap().nominations,
-				OrderedSet::from([Bond { owner: 3, amount: 20 },].to_vec()),
+				OrderedSet::try_from([Bond { owner: 3, amount: 20 },].to_vec()).unwrap(),
 			);
 			assert_eq!(NodleStaking::nominator_state(7).unwrap().total, 30);
 			assert_eq!(NodleStaking::nominator_state(7).unwrap().active_bond, 20);
@@ -2615,7 +2668,7 @@ fn unfreeze_bond_arg_flag_works() {
 
 			assert_eq!(
 				NodleStaking::nominator_state(8).unwrap().nominations,
-				OrderedSet::from([Bond { owner: 4, amount: 20 },].to_vec()),
+				OrderedSet::try_from([Bond { owner: 4, amount: 20 },].to_vec()).unwrap(),
 			);
 			assert_eq!(NodleStaking::nominator_state(8).unwrap().total, 30);
 			assert_eq!(NodleStaking::nominator_state(8).unwrap().active_bond, 20);
@@ -2640,8 +2693,8 @@ fn unfreeze_bond_arg_flag_works() {
 			assert_eq!(events(), expected);
 
 			assert_eq!(
-				NodleStaking::validator_state(1).unwrap().nominators,
-				OrderedSet::from([Bond { owner: 6, amount: 30 }, Bond { owner: 10, amount: 25 }].to_vec()),
+				mock::nominators_of(1),
+				vec![Bond { owner: 6, amount: 30 }, Bond { owner: 10, amount: 25 }],
 			);
 
 			assert_eq!(mock::balances(&1), (100, 20));
@@ -3367,18 +3420,19 @@ fn payouts_follow_nomination_changes() {
 			set_author(4, 1, 100);
 			mock::mint_rewards(1_000_000);
 			mock::start_active_session(5);
+			mock::payout_stakers(4);
 
 			let mut new1 = vec![
-				Event::StakeReward(1, 520000),
-				Event::StakeReward(6, 160000),
-				Event::StakeReward(7, 160000),
-				Event::StakeReward(10, 160000),
 				Event::ValidatorChosen(6, 1, 50),
 				Event::ValidatorChosen(6, 2, 40),
 				Event::ValidatorChosen(6, 3, 20),
 				Event::ValidatorChosen(6, 4, 20),
 				Event::ValidatorChosen(6, 5, 10),
 				Event::NewSession(25, 6, 5, 140),
+				Event::StakeReward(1, 520000),
+				Event::StakeReward(6, 160000),
+				Event::StakeReward(7, 160000),
+				Event::StakeReward(10, 160000),
 			];
 
 			expected.append(&mut new1);
@@ -3397,19 +3451,20 @@ fn payouts_follow_nomination_changes() {
 			assert_ok!(NodleStaking::nominator_denominate_all(RuntimeOrigin::signed(6)));
 
 			mock::start_active_session(6);
+			mock::payout_stakers(5);
 
 			let mut new2 = vec![
 				Event::NominatorLeftValidator(6, 1, 10, 40),
-				Event::StakeReward(1, 520000),
-				Event::StakeReward(6, 160000),
-				Event::StakeReward(7, 160000),
-				Event::StakeReward(10, 160000),
 				Event::ValidatorChosen(7, 1, 40),
 				Event::ValidatorChosen(7, 2, 40),
 				Event::ValidatorChosen(7, 3, 20),
 				Event::ValidatorChosen(7, 4, 20),
 				Event::ValidatorChosen(7, 5, 10),
 				Event::NewSession(30, 7, 5, 130),
+				Event::StakeReward(1, 520000),
+				Event::StakeReward(6, 160000),
+				Event::StakeReward(7, 160000),
+				Event::StakeReward(10, 160000),
 			];
 
 			expected.append(&mut new2);
@@ -3418,18 +3473,19 @@ fn payouts_follow_nomination_changes() {
 			set_author(6, 1, 100);
 			mock::mint_rewards(1_000_000);
 			mock::start_active_session(7);
+			mock::payout_stakers(6);
 
 			let mut new3 = vec![
-				Event::StakeReward(1, 520000),
-				Event::StakeReward(6, 160000),
-				Event::StakeReward(7, 160000),
-				Event::StakeReward(10, 160000),
 				Event::ValidatorChosen(8, 1, 40),
 				Event::ValidatorChosen(8, 2, 40),
 				Event::ValidatorChosen(8, 3, 20),
 				Event::ValidatorChosen(8, 4, 20),
 				Event::ValidatorChosen(8, 5, 10),
 				Event::NewSession(35, 8, 5, 130),
+				Event::StakeReward(1, 520000),
+				Event::StakeReward(6, 160000),
+				Event::StakeReward(7, 160000),
+				Event::StakeReward(10, 160000),
 			];
 			expected.append(&mut new3);
 			assert_eq!(events(), expected);
@@ -3454,11 +3510,9 @@ fn payouts_follow_nomination_changes() {
 			mock::mint_rewards(1_000_000);
 
 			mock::start_active_session(10);
+			mock::payout_stakers(8);
 
 			let mut new5 = vec![
-				Event::StakeReward(1, 600000),
-				Event::StakeReward(7, 200000),
-				Event::StakeReward(10, 200000),
 				Event::ValidatorChosen(10, 1, 50),
 				Event::ValidatorChosen(10, 2, 40),
 				Event::ValidatorChosen(10, 3, 20),
@@ -3471,6 +3525,9 @@ fn payouts_follow_nomination_changes() {
 				Event::ValidatorChosen(11, 4, 20),
 				Event::ValidatorChosen(11, 5, 10),
 				Event::NewSession(50, 11, 5, 140),
+				Event::StakeReward(1, 600000),
+				Event::StakeReward(7, 200000),
+				Event::StakeReward(10, 200000),
 			];
 			expected.append(&mut new5);
 			assert_eq!(events(), expected);
@@ -3479,18 +3536,19 @@ fn payouts_follow_nomination_changes() {
 			mock::mint_rewards(1_000_000);
 
 			mock::start_active_session(11);
+			mock::payout_stakers(10);
 
 			let mut new6 = vec![
-				Event::StakeReward(1, 520000),
-				Event::StakeReward(7, 160000),
-				Event::StakeReward(8, 160000),
-				Event::StakeReward(10, 160000),
 				Event::ValidatorChosen(12, 1, 50),
 				Event::ValidatorChosen(12, 2, 40),
 				Event::ValidatorChosen(12, 3, 20),
 				Event::ValidatorChosen(12, 4, 20),
 				Event::ValidatorChosen(12, 5, 10),
 				Event::NewSession(55, 12, 5, 140),
+				Event::StakeReward(1, 520000),
+				Event::StakeReward(7, 160000),
+				Event::StakeReward(8, 160000),
+				Event::StakeReward(10, 160000),
 			];
 			expected.append(&mut new6);
 			assert_eq!(events(), expected);
@@ -3543,6 +3601,13 @@ fn set_invulnerables_works() {
 			NodleStaking::set_invulnerables(RuntimeOrigin::signed(1), new_set2),
 			BadOrigin
 		);
+
+		// cannot set more than the validators bound.
+		let too_many = (1..=u64::from(MaxValidators::get()) + 1).collect::<Vec<_>>();
+		assert_noop!(
+			NodleStaking::set_invulnerables(RuntimeOrigin::root(), too_many),
+			Error::<Test>::TooManyInvulnerables
+		);
 	});
 }
 
@@ -3749,6 +3814,15 @@ fn reward_from_authorship_event_handler_works() {
 
 		// Total rewarded points
 		assert_eq!(NodleStaking::points(NodleStaking::active_session()), 26);
+
+		// only the authored block is counted for performance
+		assert_eq!(
+			NodleStaking::performance(NodleStaking::active_session(), 11).authored,
+			1
+		);
+		assert!(NodleStaking::performance(NodleStaking::active_session(), 21)
+			.authored
+			.is_zero());
 	})
 }
 
@@ -3809,8 +3883,9 @@ fn reward_validator_slashing_validator_does_not_overflow() {
 			assert_eq!(NodleStaking::total(), 36893488147419106730);
 
 			// --- Session 4:
-			// Trigger payout for Session 3
+			// Payout for Session 3
 			start_session(4);
+			mock::payout_stakers(3);
 
 			let mut expected = vec![
 				Event::ValidatorChosen(2, 11, 1500),
@@ -3827,12 +3902,12 @@ fn reward_validator_slashing_validator_does_not_overflow() {
 				Event::ValidatorChosen(4, 41, 1000),
 				Event::ValidatorChosen(4, 81, 36893488147419103230),
 				Event::NewSession(15, 4, 4, 36893488147419106730),
-				Event::StakeReward(81, 36893488147419103230),
 				Event::ValidatorChosen(5, 11, 1500),
 				Event::ValidatorChosen(5, 21, 1000),
 				Event::ValidatorChosen(5, 41, 1000),
 				Event::ValidatorChosen(5, 81, 36893488147419103230),
 				Event::NewSession(20, 5, 4, 36893488147419106730),
+				Event::StakeReward(81, 36893488147419103230),
 			];
 			assert_eq!(events(), expected);
 
@@ -3866,19 +3941,24 @@ fn reward_validator_slashing_validator_does_not_overflow() {
 				NodleStaking::at_stake(6, 81),
 				ValidatorSnapshot {
 					bond: 36893488147419103230,
-					nominators: vec![
-						Bond {
-							owner: 201,
-							amount: 36893488147419103229
-						},
-						Bond {
-							owner: 301,
-							amount: 36893488147419103229
-						},
-					],
 					total: 110680464442257309688,
+					commission: Perbill::from_percent(20),
+					nominator_count: 2,
 				}
 			);
+			assert_eq!(
+				NodleStaking::exposed_nominators(6, &81, 2),
+				vec![
+					Bond {
+						owner: 201,
+						amount: 36893488147419103229
+					},
+					Bond {
+						owner: 301,
+						amount: 36893488147419103229
+					},
+				]
+			);
 
 			log::trace!(
 				"reward_validator_slashing_validator_does_not_overflow:[{:#?}] - Bonded Sess - {:#?}",
@@ -3924,8 +4004,11 @@ fn nominators_also_get_slashed_pro_rata() {
 		let slash_percent = Perbill::from_percent(5);
 		let initial_exposure = NodleStaking::at_stake(NodleStaking::active_session(), 11);
 
+		let initial_nominators =
+			NodleStaking::exposed_nominators(NodleStaking::active_session(), &11, initial_exposure.nominator_count);
+
 		// 101 is a nominator for 11
-		assert_eq!(initial_exposure.nominators.first().unwrap().owner, 101);
+		assert_eq!(initial_nominators.first().unwrap().owner, 101);
 
 		assert_eq!(mock::balances(&11), (2000, 1000));
 		assert_eq!(mock::balances(&101), (2000, 500));
@@ -3936,7 +4019,7 @@ fn nominators_also_get_slashed_pro_rata() {
 		let validator_stake = NodleStaking::validator_state(11).unwrap().bond;
 		let exposed_stake = initial_exposure.total;
 		let exposed_validator = initial_exposure.bond;
-		let exposed_nominator = initial_exposure.nominators.first().unwrap().amount;
+		let exposed_nominator = initial_nominators.first().unwrap().amount;
 
 		mock::on_offence_now(
 			&[OffenceDetails {
@@ -4013,7 +4096,8 @@ fn slashing_performed_according_exposure() {
 					ValidatorSnapshot {
 						total: 500,
 						bond: 500,
-						nominators: vec![],
+						commission: Perbill::from_percent(20),
+						nominator_count: 0,
 					},
 				),
 				reporters: vec![],
@@ -4408,8 +4492,9 @@ fn invulnerables_are_not_slashed() {
 
 			let valid21_exposure = NodleStaking::at_stake(NodleStaking::active_session(), 21);
 			let validator21_initial_bond = valid21_exposure.bond;
-			let validator21_nominator_initial_bond: Vec<_> =
-				valid21_exposure.nominators.iter().map(|nom| nom.amount).collect();
+			let valid21_nominators =
+				NodleStaking::exposed_nominators(NodleStaking::active_session(), &21, valid21_exposure.nominator_count);
+			let validator21_nominator_initial_bond: Vec<_> = valid21_nominators.iter().map(|nom| nom.amount).collect();
 
 			on_offence_now(
 				&[
@@ -4443,10 +4528,7 @@ fn invulnerables_are_not_slashed() {
 			);
 
 			// ensure that nominators were slashed as well.
-			for (initial_bond, nominator) in validator21_nominator_initial_bond
-				.into_iter()
-				.zip(valid21_exposure.nominators)
-			{
+			for (initial_bond, nominator) in validator21_nominator_initial_bond.into_iter().zip(valid21_nominators) {
 				assert_eq!(
 					mock::balances(&nominator.owner),
 					(900, initial_bond - (2 * initial_bond / 10)),
@@ -4591,8 +4673,11 @@ fn garbage_collection_after_slashing() {
 			let slash_percent = Perbill::from_percent(10);
 			let initial_exposure = NodleStaking::at_stake(NodleStaking::active_session(), 11);
 
+			let initial_nominators =
+				NodleStaking::exposed_nominators(NodleStaking::active_session(), &11, initial_exposure.nominator_count);
+
 			// 101 is a nominator for 11
-			assert_eq!(initial_exposure.nominators.first().unwrap().owner, 101);
+			assert_eq!(initial_nominators.first().unwrap().owner, 101);
 
 			assert_eq!(NodleStaking::total(), 641000);
 
@@ -4872,8 +4957,18 @@ fn slashing_nominators_by_span_max() {
 
 		let exposure_11 = NodleStaking::at_stake(NodleStaking::active_session(), 11);
 		let exposure_21 = NodleStaking::at_stake(NodleStaking::active_session(), 21);
-		let nominated_value_11 = exposure_11.nominators.iter().find(|o| o.owner == 101).unwrap().amount;
-		let nominated_value_21 = exposure_21.nominators.iter().find(|o| o.owner == 101).unwrap().amount;
+		let nominated_value_11 =
+			NodleStaking::exposed_nominators(NodleStaking::active_session(), &11, exposure_11.nominator_count)
+				.into_iter()
+				.find(|o| o.owner == 101)
+				.unwrap()
+				.amount;
+		let nominated_value_21 =
+			NodleStaking::exposed_nominators(NodleStaking::active_session(), &21, exposure_21.nominator_count)
+				.into_iter()
+				.find(|o| o.owner == 101)
+				.unwrap()
+				.amount;
 
 		// Check slashing
 		on_offence_in_session(
@@ -5451,3 +5546,1296 @@ fn remove_multi_deferred() {
 			// );
 		})
 }
+
+#[test]
+fn validator_set_commission_works() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 1000), (2, 1000), (3, 100)])
+		.with_validators(vec![(1, 500), (2, 500)])
+		.with_nominators(vec![(3, 1, 100)])
+		.tst_staking_build()
+		.execute_with(|| {
+			start_session(2);
+
+			// Validators start with the default fee
+			assert_eq!(
+				NodleStaking::validator_state(1).unwrap().commission,
+				Perbill::from_percent(20)
+			);
+
+			assert_noop!(
+				NodleStaking::validator_set_commission(RuntimeOrigin::signed(3), Perbill::from_percent(10)),
+				Error::<Test>::ValidatorDNE
+			);
+			assert_noop!(
+				NodleStaking::validator_set_commission(RuntimeOrigin::signed(1), Perbill::from_percent(4)),
+				Error::<Test>::CommissionOutOfBounds
+			);
+			assert_noop!(
+				NodleStaking::validator_set_commission(RuntimeOrigin::signed(1), Perbill::from_percent(51)),
+				Error::<Test>::CommissionOutOfBounds
+			);
+			assert_noop!(
+				NodleStaking::validator_set_commission(RuntimeOrigin::signed(1), Perbill::from_percent(23)),
+				Error::<Test>::CommissionIncreaseTooLarge
+			);
+
+			assert_ok!(NodleStaking::validator_set_commission(
+				RuntimeOrigin::signed(1),
+				Perbill::from_percent(22)
+			));
+			assert_eq!(
+				last_event(),
+				MetaEvent::NodleStaking(Event::ValidatorCommissionSet(
+					1,
+					Perbill::from_percent(20),
+					Perbill::from_percent(22)
+				))
+			);
+			assert_eq!(
+				NodleStaking::validator_state(1).unwrap().commission,
+				Perbill::from_percent(22)
+			);
+
+			// Only one raise per session, lowering is always possible
+			assert_noop!(
+				NodleStaking::validator_set_commission(RuntimeOrigin::signed(1), Perbill::from_percent(23)),
+				Error::<Test>::CommissionRaisedThisSession
+			);
+			assert_ok!(NodleStaking::validator_set_commission(
+				RuntimeOrigin::signed(1),
+				Perbill::from_percent(5)
+			));
+			assert_ok!(NodleStaking::validator_set_commission(
+				RuntimeOrigin::signed(1),
+				Perbill::from_percent(6)
+			));
+			assert_noop!(
+				NodleStaking::validator_set_commission(RuntimeOrigin::signed(1), Perbill::from_percent(7)),
+				Error::<Test>::CommissionRaisedThisSession
+			);
+
+			start_session(3);
+
+			assert_ok!(NodleStaking::validator_set_commission(
+				RuntimeOrigin::signed(1),
+				Perbill::from_percent(7)
+			));
+			assert_eq!(
+				NodleStaking::validator_state(1).unwrap().commission,
+				Perbill::from_percent(7)
+			);
+			assert_eq!(
+				NodleStaking::validator_state(2).unwrap().commission,
+				Perbill::from_percent(20)
+			);
+		});
+}
+
+#[test]
+fn set_commission_limits_works() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_noop!(
+			NodleStaking::set_commission_limits(
+				RuntimeOrigin::signed(1),
+				Perbill::from_percent(1),
+				Perbill::from_percent(10),
+				Perbill::from_percent(5),
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			NodleStaking::set_commission_limits(
+				RuntimeOrigin::root(),
+				Perbill::from_percent(10),
+				Perbill::from_percent(1),
+				Perbill::from_percent(5),
+			),
+			Error::<Test>::InvalidArguments
+		);
+
+		assert_ok!(NodleStaking::set_commission_limits(
+			RuntimeOrigin::signed(CancelOrigin::get()),
+			Perbill::from_percent(1),
+			Perbill::from_percent(10),
+			Perbill::from_percent(5),
+		));
+		assert_eq!(
+			events(),
+			vec![Event::CommissionLimitsSet(
+				Perbill::from_percent(1),
+				Perbill::from_percent(10),
+				Perbill::from_percent(5),
+			)]
+		);
+
+		assert_eq!(NodleStaking::min_validator_commission(), Perbill::from_percent(1));
+		assert_eq!(NodleStaking::max_validator_commission(), Perbill::from_percent(10));
+		assert_eq!(NodleStaking::max_commission_increase(), Perbill::from_percent(5));
+	});
+}
+
+#[test]
+fn migrate_from_collator_selection_works() {
+	use frame_support::{
+		migration, parameter_types,
+		traits::{OnRuntimeUpgrade, ReservableCurrency},
+	};
+
+	parameter_types! {
+		pub const CollatorSelectionPot: u64 = 42;
+	}
+
+	const MODULE: &[u8] = b"CollatorSelection";
+
+	ExtBuilder::default()
+		.with_balances(vec![(1, 100), (2, 100), (3, 100), (42, 50)])
+		.tst_staking_build()
+		.execute_with(|| {
+			assert_ok!(Balances::reserve(&2, 20));
+			assert_ok!(Balances::reserve(&3, 30));
+			migration::put_storage_value(MODULE, b"Invulnerables", &[], vec![1u64]);
+			migration::put_storage_value(MODULE, b"Candidates", &[], vec![(2u64, 20u128), (3u64, 30u128)]);
+			migration::put_storage_value(MODULE, b"DesiredCandidates", &[], 7u32);
+			migration::put_storage_value(MODULE, b"CandidacyBond", &[], 20u128);
+
+			migrations::MigrateFromCollatorSelection::<Test, CollatorSelectionPot>::on_runtime_upgrade();
+
+			assert!(!migration::have_storage_value(MODULE, b"Invulnerables", &[]));
+			assert!(!migration::have_storage_value(MODULE, b"Candidates", &[]));
+			assert!(!migration::have_storage_value(MODULE, b"DesiredCandidates", &[]));
+			assert!(!migration::have_storage_value(MODULE, b"CandidacyBond", &[]));
+
+			assert_eq!(NodleStaking::invulnerables(), vec![1]);
+			assert_eq!(NodleStaking::validator_state(1).unwrap().bond, 0);
+			assert_eq!(balances(&1), (100, 0));
+
+			assert_eq!(NodleStaking::validator_state(2).unwrap().bond, 20);
+			assert_eq!(NodleStaking::validator_state(3).unwrap().bond, 30);
+			assert_eq!(Balances::reserved_balance(2), 0);
+			assert_eq!(Balances::reserved_balance(3), 0);
+			assert_eq!(balances(&2), (100, 20));
+			assert_eq!(balances(&3), (100, 30));
+			assert_eq!(
+				NodleStaking::validator_pool(),
+				vec![Bond { owner: 3, amount: 30 }, Bond { owner: 2, amount: 20 }]
+			);
+			assert_eq!(NodleStaking::total(), 50);
+			assert_eq!(NodleStaking::total_selected(), 7);
+			assert_eq!(NodleStaking::active_session(), Session::current_index());
+
+			assert_eq!(Balances::free_balance(42), 0);
+			assert_eq!(
+				NodleStaking::session_accumulated_balance(NodleStaking::active_session()),
+				50
+			);
+
+			// Running it again is a no-op
+			migrations::MigrateFromCollatorSelection::<Test, CollatorSelectionPot>::on_runtime_upgrade();
+			assert_eq!(NodleStaking::total(), 50);
+			assert_eq!(balances(&2), (100, 20));
+		});
+}
+
+#[test]
+fn migrate_to_validator_commission_works() {
+	use codec::Encode;
+	use frame_support::{storage::unhashed, traits::OnRuntimeUpgrade};
+
+	ExtBuilder::default().build_and_execute(|| {
+		let legacy_validator = migrations::legacy::Validator::<AccountId, Balance> {
+			id: 11,
+			bond: 1000,
+			nomi_bond_total: 0,
+			nominators: vec![],
+			total: 1000,
+			state: ValidatorStatus::Active,
+			unlocking: vec![],
+		};
+		let legacy_snapshot = migrations::legacy::ValidatorSnapshot::<AccountId, Balance> {
+			bond: 1000,
+			nominators: vec![],
+			total: 1000,
+		};
+		unhashed::put_raw(&<ValidatorState<Test>>::hashed_key_for(11), &legacy_validator.encode());
+		unhashed::put_raw(&<AtStake<Test>>::hashed_key_for(3, 11), &legacy_snapshot.encode());
+		<MinValidatorCommission<Test>>::kill();
+		<MaxValidatorCommission<Test>>::kill();
+		<MaxCommissionIncrease<Test>>::kill();
+		<ValidatorFee<Test>>::put(Perbill::from_percent(60));
+		<ActiveSession<Test>>::put(3);
+		crate::pallet::StorageVersion::<Test>::put(Releases::V1);
+
+		migrations::MigrateToValidatorCommission::<Test>::on_runtime_upgrade();
+
+		assert_eq!(crate::pallet::StorageVersion::<Test>::get(), Releases::V2);
+		assert_eq!(NodleStaking::min_validator_commission(), Perbill::from_percent(5));
+		assert_eq!(NodleStaking::max_validator_commission(), Perbill::from_percent(50));
+		assert_eq!(NodleStaking::max_commission_increase(), Perbill::from_percent(2));
+
+		let validator =
+			unhashed::get::<migrations::legacy::UnboundedValidator<AccountId, Balance>>(
+				&<ValidatorState<Test>>::hashed_key_for(11),
+			)
+			.unwrap();
+		assert_eq!(validator.bond, 1000);
+		assert_eq!(validator.commission, Perbill::from_percent(50));
+		assert_eq!(validator.commission_raised_at, 3);
+
+		// Snapshots keep paying out the fee they were taken with
+		let snapshot = NodleStaking::at_stake(3, 11);
+		assert_eq!(snapshot.bond, 1000);
+		assert_eq!(snapshot.commission, Perbill::from_percent(60));
+
+		// Running it again is a no-op
+		<ValidatorFee<Test>>::put(Perbill::from_percent(10));
+		migrations::MigrateToValidatorCommission::<Test>::on_runtime_upgrade();
+		assert_eq!(
+			unhashed::get::<migrations::legacy::UnboundedValidator<AccountId, Balance>>(
+				&<ValidatorState<Test>>::hashed_key_for(11),
+			)
+			.unwrap()
+			.commission,
+			Perbill::from_percent(50)
+		);
+	});
+}
+
+#[test]
+fn migrate_to_bounded_storage_works() {
+	use codec::Encode;
+	use frame_support::{
+		storage::unhashed,
+		traits::{Get, OnRuntimeUpgrade},
+		weights::Weight,
+	};
+
+	ExtBuilder::default().build_and_execute(|| {
+		let legacy_nominator = migrations::legacy::UnboundedNominator::<AccountId, Balance> {
+			nominations: (1..=MaxValidatorPerNominator::get() as u64 + 1)
+				.map(|owner| Bond { owner, amount: 10 })
+				.collect(),
+			total: 50,
+			active_bond: 50,
+			frozen_bond: 0,
+			unlocking: (1..=MaxChunkUnlock::get() + 2)
+				.map(|session_idx| StakeReward { session_idx, value: 1 })
+				.collect(),
+		};
+		let legacy_rewards = (1..=MaxStakeRewards::get() + 1)
+			.map(|session_idx| StakeReward {
+				session_idx,
+				value: 2u128,
+			})
+			.collect::<Vec<_>>();
+		let legacy_snapshot = migrations::legacy::UnboundedValidatorSnapshot::<AccountId, Balance> {
+			bond: 1000,
+			nominators: (1..=MaxNominatorsPerValidator::get() as u64 + 1)
+				.map(|owner| Bond { owner, amount: 10 })
+				.collect(),
+			total: 1050,
+			commission: Perbill::from_percent(20),
+		};
+		unhashed::put_raw(&<NominatorState<Test>>::hashed_key_for(101), &legacy_nominator.encode());
+		unhashed::put_raw(&<StakeRewards<Test>>::hashed_key_for(101), &legacy_rewards.encode());
+		let legacy_slashes = (1..=u64::from(MaxValidators::get()) + 2)
+			.map(
+				|validator| migrations::legacy::UnboundedUnappliedSlash::<AccountId, Balance> {
+					validator,
+					own: 10,
+					others: vec![],
+					reporters: vec![],
+					payout: 0,
+				},
+			)
+			.collect::<Vec<_>>();
+		unhashed::put_raw(&<AtStake<Test>>::hashed_key_for(3, 11), &legacy_snapshot.encode());
+		unhashed::put_raw(&<UnappliedSlashes<Test>>::hashed_key_for(20), &legacy_slashes.encode());
+		unhashed::put_raw(
+			&<BondedSessions<Test>>::hashed_key(),
+			&(0..10u32).collect::<Vec<_>>().encode(),
+		);
+		crate::pallet::StorageVersion::<Test>::put(Releases::V2);
+
+		migrations::MigrateToBoundedStorage::<Test>::on_runtime_upgrade();
+
+		assert_eq!(crate::pallet::StorageVersion::<Test>::get(), Releases::V3);
+		assert_eq!(
+			NodleStaking::bonded_sessions(),
+			(10 - MaxBondedSessions::<Test>::get()..10).collect::<Vec<_>>()
+		);
+		assert!(<MigrationCursor<Test>>::exists());
+		// the maps are only migrated on idle
+		assert!(<NominatorState<Test>>::try_get(101).is_err());
+
+		// nothing happens without enough weight for an entry
+		assert_eq!(
+			migrations::MigrateToBoundedStorage::<Test>::step(Weight::from_ref_time(0)),
+			<Test as frame_system::Config>::DbWeight::get().reads(1)
+		);
+		assert!(<NominatorState<Test>>::try_get(101).is_err());
+
+		while <MigrationCursor<Test>>::exists() {
+			migrations::MigrateToBoundedStorage::<Test>::step(Weight::from_ref_time(u64::MAX));
+		}
+
+		let nominator = NodleStaking::nominator_state(101).unwrap();
+		assert_eq!(nominator.nominations.0.len(), MaxValidatorPerNominator::get() as usize);
+		assert_eq!(nominator.unlocking.len(), MaxChunkUnlock::get() as usize);
+		// the chunks over the bound are due with the latest one
+		assert_eq!(
+			nominator.unlocking.last(),
+			Some(&StakeReward {
+				session_idx: MaxChunkUnlock::get() + 2,
+				value: 3,
+			})
+		);
+
+		let rewards = NodleStaking::stake_rewards(101);
+		assert_eq!(rewards.len(), MaxStakeRewards::get() as usize);
+		assert_eq!(
+			rewards.iter().map(|reward| reward.value).sum::<u128>(),
+			legacy_rewards.len() as u128 * 2
+		);
+
+		// the exposed nominators over the bound are dropped and the rest paged
+		let snapshot = NodleStaking::at_stake(3, 11);
+		assert_eq!((snapshot.bond, snapshot.total), (1000, 1050));
+		assert_eq!(snapshot.nominator_count, MaxNominatorsPerValidator::get());
+		assert_eq!(
+			NodleStaking::exposed_nominators(3, &11, snapshot.nominator_count),
+			(1..=MaxNominatorsPerValidator::get() as u64)
+				.map(|owner| Bond { owner, amount: 10 })
+				.collect::<Vec<_>>()
+		);
+		assert_eq!(
+			NodleStaking::at_stake_page(3, (11, 0)).len(),
+			MaxNominatorRewardedPerPage::get() as usize
+		);
+
+		// the slashes over the bound are deferred by another session rather than applied
+		assert_eq!(<UnappliedSlashes<Test>>::get(20).len(), MaxValidators::get() as usize);
+		assert_eq!(
+			<UnappliedSlashes<Test>>::get(21)
+				.iter()
+				.map(|slash| slash.validator)
+				.collect::<Vec<_>>(),
+			vec![u64::from(MaxValidators::get()) + 1, u64::from(MaxValidators::get()) + 2]
+		);
+
+		// validators set up at genesis are already bounded and left as is.
+		assert_eq!(NodleStaking::validator_state(11).unwrap().bond, 1000);
+	});
+}
+
+#[test]
+fn validators_pool_is_sorted_by_stake() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 1000), (2, 1000), (3, 1000), (4, 1000)])
+		.with_validators(vec![(1, 500), (2, 200)])
+		.tst_staking_build()
+		.execute_with(|| {
+			assert_eq!(
+				NodleStaking::validator_pool(),
+				vec![Bond { owner: 1, amount: 500 }, Bond { owner: 2, amount: 200 }]
+			);
+
+			assert_ok!(NodleStaking::validator_join_pool(RuntimeOrigin::signed(3), 300));
+			assert_eq!(
+				NodleStaking::validator_pool(),
+				vec![
+					Bond { owner: 1, amount: 500 },
+					Bond { owner: 3, amount: 300 },
+					Bond { owner: 2, amount: 200 },
+				]
+			);
+
+			assert_ok!(NodleStaking::validator_bond_more(RuntimeOrigin::signed(2), 400));
+			assert_eq!(NodleStaking::validator_pool_head(), Some(2));
+
+			assert_ok!(NodleStaking::nominator_nominate(
+				RuntimeOrigin::signed(4),
+				3,
+				250,
+				false
+			));
+			assert_eq!(
+				NodleStaking::validator_pool(),
+				vec![
+					Bond { owner: 2, amount: 600 },
+					Bond { owner: 3, amount: 550 },
+					Bond { owner: 1, amount: 500 },
+				]
+			);
+
+			// a stake that still fits between its neighbours stays in place
+			assert_ok!(NodleStaking::nominator_bond_less(RuntimeOrigin::signed(4), 3, 30));
+			assert_eq!(
+				NodleStaking::validator_pool(),
+				vec![
+					Bond { owner: 2, amount: 600 },
+					Bond { owner: 3, amount: 520 },
+					Bond { owner: 1, amount: 500 },
+				]
+			);
+
+			assert_ok!(NodleStaking::validator_exit_pool(RuntimeOrigin::signed(2)));
+			assert_eq!(
+				NodleStaking::validator_pool(),
+				vec![Bond { owner: 3, amount: 520 }, Bond { owner: 1, amount: 500 }]
+			);
+			assert_eq!(NodleStaking::validator_pool_head(), Some(3));
+			assert_eq!(mock::validators_in_pool(), vec![1, 3]);
+		});
+}
+
+#[test]
+fn nominator_bonds_are_kept_per_validator() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 1000), (2, 1000), (3, 1000), (4, 1000)])
+		.with_validators(vec![(1, 500), (2, 200)])
+		.tst_staking_build()
+		.execute_with(|| {
+			assert_ok!(NodleStaking::nominator_nominate(
+				RuntimeOrigin::signed(3),
+				1,
+				100,
+				false
+			));
+			assert_ok!(NodleStaking::nominator_nominate(RuntimeOrigin::signed(4), 1, 50, false));
+			assert_noop!(
+				NodleStaking::nominator_nominate(RuntimeOrigin::signed(3), 1, 10, false),
+				Error::<Test>::AlreadyNominatedValidator,
+			);
+
+			let state = NodleStaking::validator_state(1).unwrap();
+			assert_eq!(state.nominator_count, 2);
+			assert_eq!(state.nomi_bond_total, 150);
+			assert_eq!(state.total, 650);
+			assert_eq!(
+				mock::nominators_of(1),
+				vec![Bond { owner: 3, amount: 100 }, Bond { owner: 4, amount: 50 }]
+			);
+
+			assert_ok!(NodleStaking::nominator_bond_more(
+				RuntimeOrigin::signed(3),
+				1,
+				20,
+				false
+			));
+			assert_eq!(NodleStaking::nominator_bond(1, 3), Some(120));
+
+			assert_ok!(NodleStaking::nominator_move_nomination(
+				RuntimeOrigin::signed(4),
+				1,
+				2,
+				0,
+				false
+			));
+			assert_eq!(NodleStaking::nominator_bond(1, 4), None);
+			assert_eq!(NodleStaking::nominator_bond(2, 4), Some(50));
+
+			let state = NodleStaking::validator_state(1).unwrap();
+			assert_eq!(state.nominator_count, 1);
+			assert_eq!(state.nomi_bond_total, 120);
+			assert_eq!(state.total, 620);
+			let state = NodleStaking::validator_state(2).unwrap();
+			assert_eq!(state.nominator_count, 1);
+			assert_eq!(state.total, 250);
+
+			assert_ok!(NodleStaking::nominator_denominate(RuntimeOrigin::signed(3), 1));
+			assert_eq!(NodleStaking::validator_state(1).unwrap().nominator_count, 0);
+			assert!(mock::nominators_of(1).is_empty());
+		});
+}
+
+#[test]
+fn migrate_to_map_based_pool_works() {
+	use codec::Encode;
+	use frame_support::{storage::unhashed, traits::OnRuntimeUpgrade};
+
+	ExtBuilder::default().tst_staking_build().execute_with(|| {
+		let legacy_validator = |id, bond, nominators: Vec<Bond<AccountId, Balance>>| {
+			let nomi_bond_total = nominators.iter().map(|bond| bond.amount).sum::<Balance>();
+			migrations::legacy::UnboundedValidator::<AccountId, Balance> {
+				id,
+				bond,
+				nomi_bond_total,
+				nominators,
+				total: bond + nomi_bond_total,
+				state: ValidatorStatus::Active,
+				unlocking: vec![],
+				commission: Perbill::from_percent(10),
+				commission_raised_at: 0,
+			}
+		};
+		unhashed::put_raw(
+			&<ValidatorState<Test>>::hashed_key_for(1),
+			&legacy_validator(
+				1,
+				100,
+				vec![Bond { owner: 3, amount: 20 }, Bond { owner: 4, amount: 30 }],
+			)
+			.encode(),
+		);
+		unhashed::put_raw(
+			&<ValidatorState<Test>>::hashed_key_for(2),
+			&legacy_validator(2, 200, vec![]).encode(),
+		);
+		<migrations::legacy::ValidatorPool<Test>>::put(vec![
+			Bond { owner: 1, amount: 150 },
+			Bond { owner: 2, amount: 200 },
+		]);
+		crate::pallet::StorageVersion::<Test>::put(Releases::V3);
+
+		// the validators have to be bounded first
+		<MigrationCursor<Test>>::put(migrations::BoundedStorageCursor {
+			stage: migrations::BoundedStorageStage::SlashingSpans,
+			last_key: None,
+		});
+		migrations::MigrateToMapBasedPool::<Test>::on_runtime_upgrade();
+		assert_eq!(crate::pallet::StorageVersion::<Test>::get(), Releases::V3);
+		assert!(<migrations::legacy::ValidatorPool<Test>>::exists());
+
+		<MigrationCursor<Test>>::kill();
+		migrations::MigrateToMapBasedPool::<Test>::on_runtime_upgrade();
+
+		assert_eq!(crate::pallet::StorageVersion::<Test>::get(), Releases::V4);
+		assert!(!<migrations::legacy::ValidatorPool<Test>>::exists());
+		assert_eq!(
+			NodleStaking::validator_pool(),
+			vec![Bond { owner: 2, amount: 200 }, Bond { owner: 1, amount: 150 }]
+		);
+		assert_eq!(NodleStaking::validator_pool_head(), Some(2));
+
+		let validator = NodleStaking::validator_state(1).unwrap();
+		assert_eq!(validator.nominator_count, 2);
+		assert_eq!(validator.total, 150);
+		assert_eq!(validator.commission, Perbill::from_percent(10));
+		assert_eq!(
+			mock::nominators_of(1),
+			vec![Bond { owner: 3, amount: 20 }, Bond { owner: 4, amount: 30 }]
+		);
+		assert_eq!(NodleStaking::validator_state(2).unwrap().nominator_count, 0);
+
+		// Running it again is a no-op
+		migrations::MigrateToMapBasedPool::<Test>::on_runtime_upgrade();
+		assert_eq!(NodleStaking::validator_state(1).unwrap().nominator_count, 2);
+		assert_eq!(
+			NodleStaking::validator_pool(),
+			vec![Bond { owner: 2, amount: 200 }, Bond { owner: 1, amount: 150 }]
+		);
+	});
+}
+
+#[test]
+fn set_auto_compound_works() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 1000), (2, 1000), (3, 1000)])
+		.with_validators(vec![(1, 100)])
+		.with_nominators(vec![(2, 1, 100)])
+		.tst_staking_build()
+		.execute_with(|| {
+			assert_noop!(
+				NodleStaking::set_auto_compound(RuntimeOrigin::signed(3), Perbill::from_percent(50)),
+				Error::<Test>::NotStaker
+			);
+
+			assert_ok!(NodleStaking::set_auto_compound(
+				RuntimeOrigin::signed(2),
+				Perbill::from_percent(50)
+			));
+			assert_eq!(
+				last_event(),
+				MetaEvent::NodleStaking(Event::AutoCompoundSet(2, Perbill::from_percent(50)))
+			);
+			assert_eq!(NodleStaking::auto_compound(2), Perbill::from_percent(50));
+
+			assert_ok!(NodleStaking::set_auto_compound(
+				RuntimeOrigin::signed(2),
+				Perbill::zero()
+			));
+			assert!(!<AutoCompound<Test>>::contains_key(2));
+		});
+}
+
+#[test]
+fn rewards_are_compounded_as_they_are_paid() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 1000), (2, 1000)])
+		.with_validators(vec![(1, 100)])
+		.with_nominators(vec![(2, 1, 100)])
+		.tst_staking_build()
+		.execute_with(|| {
+			assert_ok!(NodleStaking::set_auto_compound(
+				RuntimeOrigin::signed(1),
+				Perbill::one()
+			));
+			assert_ok!(NodleStaking::set_auto_compound(
+				RuntimeOrigin::signed(2),
+				Perbill::from_percent(50)
+			));
+
+			mock::start_active_session(5);
+			mock::set_author(5, 1, 100);
+			mock::mint_rewards(1_000_000);
+			mock::start_active_session(6);
+			mock::payout_stakers(5);
+
+			let compounded = |who: AccountId| {
+				events()
+					.into_iter()
+					.find_map(|event| match event {
+						Event::RewardCompounded(staker, 1, value) if staker == who => Some(value),
+						_ => None,
+					})
+					.unwrap()
+			};
+			let validator_restaked = compounded(1);
+			let nominator_restaked = compounded(2);
+			assert!(!validator_restaked.is_zero() && !nominator_restaked.is_zero());
+
+			// the validator restakes everything
+			assert!(NodleStaking::stake_rewards(1).is_empty());
+			let state = NodleStaking::validator_state(1).unwrap();
+			assert_eq!(state.bond, 100 + validator_restaked);
+			assert_eq!(
+				mock::balances(&1),
+				(1000 + validator_restaked, 100 + validator_restaked)
+			);
+
+			// the nominator keeps the other half to withdraw
+			let kept = NodleStaking::stake_rewards(2).last().unwrap().value;
+			assert!(kept >= nominator_restaked && kept <= nominator_restaked + 1);
+			assert_eq!(NodleStaking::nominator_bond(1, 2), Some(100 + nominator_restaked));
+			assert_eq!(
+				NodleStaking::nominator_state(2).unwrap().total,
+				100 + nominator_restaked
+			);
+			assert_eq!(
+				mock::balances(&2),
+				(1000 + nominator_restaked, 100 + nominator_restaked)
+			);
+
+			assert_eq!(state.total, 200 + validator_restaked + nominator_restaked);
+			assert_eq!(NodleStaking::total(), 200 + validator_restaked + nominator_restaked);
+			assert_eq!(
+				NodleStaking::validator_pool(),
+				vec![Bond {
+					owner: 1,
+					amount: state.total
+				}]
+			);
+		});
+}
+
+#[test]
+fn rewards_that_cannot_be_compounded_are_kept() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 1000), (2, 1000)])
+		.with_validators(vec![(1, 100), (2, 100)])
+		.tst_staking_build()
+		.execute_with(|| {
+			assert_ok!(NodleStaking::set_auto_compound(
+				RuntimeOrigin::signed(1),
+				Perbill::one()
+			));
+
+			mock::start_active_session(5);
+			mock::set_author(5, 1, 100);
+			mock::mint_rewards(1_000_000);
+			// a leaving validator cannot bond more
+			assert_ok!(NodleStaking::validator_exit_pool(RuntimeOrigin::signed(1)));
+			mock::start_active_session(6);
+			mock::payout_stakers(5);
+
+			assert!(!events()
+				.iter()
+				.any(|event| matches!(event, Event::RewardCompounded(..))));
+			let reward = NodleStaking::stake_rewards(1).last().unwrap().value;
+			assert!(events().contains(&Event::StakeReward(1, reward)));
+			assert_eq!(NodleStaking::validator_state(1).unwrap().bond, 100);
+			assert_eq!(mock::balances(&1), (1000, 100));
+			assert_eq!(NodleStaking::total(), 200);
+		});
+}
+
+#[test]
+fn payout_stakers_pays_nominators_in_pages() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 1000), (2, 1000), (3, 1000), (4, 1000)])
+		.with_validators(vec![(1, 100)])
+		.with_nominators(vec![(2, 1, 100), (3, 1, 100), (4, 1, 100)])
+		.tst_staking_build()
+		.execute_with(|| {
+			mock::start_active_session(5);
+			mock::set_author(5, 1, 100);
+			mock::mint_rewards(1_000_000);
+			mock::start_active_session(6);
+
+			// nothing is paid with the session change
+			assert!(NodleStaking::stake_rewards(1).is_empty());
+			assert_eq!(NodleStaking::session_validator_reward(5), 1_000_000);
+
+			// the first page pays the validator along with its first nominators
+			assert_ok!(NodleStaking::payout_stakers(RuntimeOrigin::signed(99), 1, 5, 0));
+			let mut expected = events();
+			assert_eq!(
+				expected[expected.len() - 3..],
+				[
+					Event::StakeReward(1, 400000),
+					Event::StakeReward(2, 200000),
+					Event::StakeReward(3, 200000),
+				]
+			);
+			assert!(NodleStaking::stake_rewards(4).is_empty());
+
+			assert_noop!(
+				NodleStaking::payout_stakers(RuntimeOrigin::signed(99), 1, 5, 0),
+				Error::<Test>::AlreadyClaimed
+			);
+
+			assert_ok!(NodleStaking::payout_stakers(RuntimeOrigin::signed(99), 1, 5, 1));
+			expected.push(Event::StakeReward(4, 200000));
+			assert_eq!(events(), expected);
+			assert_eq!(NodleStaking::claimed_rewards(5, 1).into_inner(), vec![0, 1]);
+
+			assert_noop!(
+				NodleStaking::payout_stakers(RuntimeOrigin::signed(99), 1, 5, 2),
+				Error::<Test>::InvalidPage
+			);
+		});
+}
+
+#[test]
+fn payout_stakers_is_limited_to_ended_bonded_sessions() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 1000), (2, 1000)])
+		.with_validators(vec![(1, 100), (2, 100)])
+		.tst_staking_build()
+		.execute_with(|| {
+			mock::start_active_session(5);
+			mock::set_author(5, 1, 100);
+			mock::mint_rewards(1_000_000);
+
+			// the session is not over yet
+			assert_noop!(
+				NodleStaking::payout_stakers(RuntimeOrigin::signed(99), 1, 5, 0),
+				Error::<Test>::InvalidSessionIndex
+			);
+
+			mock::start_active_session(6);
+
+			assert_noop!(
+				NodleStaking::payout_stakers(RuntimeOrigin::signed(99), 2, 5, 0),
+				Error::<Test>::RewardsDNE
+			);
+
+			// past `BondedDuration` the rewards can't be claimed anymore
+			mock::start_active_session(8);
+
+			assert_noop!(
+				NodleStaking::payout_stakers(RuntimeOrigin::signed(99), 1, 5, 0),
+				Error::<Test>::InvalidSessionIndex
+			);
+			assert!(NodleStaking::claimed_rewards(5, 1).is_empty());
+			assert!(NodleStaking::stake_rewards(1).is_empty());
+		});
+}
+
+#[test]
+fn staking_api_reports_stakers_and_rewards() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 1000), (2, 1000), (3, 1000), (4, 1000)])
+		.with_validators(vec![(1, 100)])
+		.with_nominators(vec![(2, 1, 100), (3, 1, 100), (4, 1, 100)])
+		.tst_staking_build()
+		.execute_with(|| {
+			assert!(NodleStaking::validator_info(&2).is_none());
+			assert!(NodleStaking::nominator_info(&1).is_none());
+
+			mock::start_active_session(5);
+			mock::set_author(5, 1, 100);
+			mock::mint_rewards(100);
+			mock::start_active_session(6);
+
+			let info = NodleStaking::validator_info(&1).unwrap();
+			assert_eq!(info.bond, 100);
+			assert_eq!(info.total, 400);
+			assert_eq!(info.commission, Perbill::from_percent(20));
+			assert_eq!(info.status, ValidatorStatus::Active);
+			assert!(info.selected);
+			assert_eq!(info.nominators, mock::nominators_of(1));
+			let exposure = info.exposure.unwrap();
+			assert_eq!((exposure.bond, exposure.total), (100, 400));
+			assert_eq!(exposure.nominators, mock::nominators_of(1));
+
+			let info = NodleStaking::nominator_info(&2).unwrap();
+			assert_eq!(info.nominations, vec![Bond { owner: 1, amount: 100 }]);
+			assert_eq!(info.total, 100);
+			assert!(info.frozen_bond.is_zero());
+			assert!(info.unlocking.is_empty());
+
+			assert_eq!(NodleStaking::unclaimed_payouts(&1), vec![(5, vec![0, 1])]);
+			assert_ok!(NodleStaking::payout_stakers(RuntimeOrigin::signed(99), 1, 5, 0));
+			assert_eq!(NodleStaking::unclaimed_payouts(&1), vec![(5, vec![1])]);
+			assert_ok!(NodleStaking::payout_stakers(RuntimeOrigin::signed(99), 1, 5, 1));
+			assert!(NodleStaking::unclaimed_payouts(&1).is_empty());
+
+			// 20 of commission and a quarter of the other 80
+			assert_eq!(NodleStaking::claimable_rewards(&1), 40);
+			assert_eq!(NodleStaking::claimable_rewards(&4), 20);
+
+			// the validator earned 40 on 100 in session 5 and nothing in session 4
+			assert_eq!(
+				NodleStaking::reward_rate(&1),
+				Some(RewardRate {
+					validator: Perbill::from_percent(20),
+					nominator: Perbill::from_percent(10),
+					sessions: 2,
+				})
+			);
+			assert!(NodleStaking::reward_rate(&2).is_none());
+		});
+}
+
+#[test]
+fn validator_rebond_takes_the_newest_chunks_first() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 1000), (2, 1000), (3, 1000)])
+		.with_validators(vec![(1, 100), (2, 80)])
+		.tst_staking_build()
+		.execute_with(|| {
+			mock::start_active_session(1);
+			assert_ok!(NodleStaking::validator_bond_less(RuntimeOrigin::signed(1), 20));
+			mock::start_active_session(2);
+			assert_ok!(NodleStaking::validator_bond_less(RuntimeOrigin::signed(1), 30));
+			assert_eq!(NodleStaking::total(), 130);
+			assert_eq!(
+				NodleStaking::validator_pool(),
+				vec![Bond { owner: 2, amount: 80 }, Bond { owner: 1, amount: 50 }]
+			);
+
+			assert_ok!(NodleStaking::validator_rebond(RuntimeOrigin::signed(1), 40));
+			assert_eq!(
+				last_event(),
+				MetaEvent::NodleStaking(Event::ValidatorBondedMore(1, 50, 90))
+			);
+
+			let state = NodleStaking::validator_state(&1).unwrap();
+			assert_eq!(state.bond, 90);
+			assert_eq!(state.total, 100);
+			assert_eq!(
+				state.unlocking.into_inner(),
+				vec![UnlockChunk {
+					value: 10,
+					session_idx: 3
+				}]
+			);
+			assert_eq!(NodleStaking::total(), 170);
+			assert_eq!(
+				NodleStaking::validator_pool(),
+				vec![Bond { owner: 1, amount: 90 }, Bond { owner: 2, amount: 80 }]
+			);
+			assert_eq!(mock::balances(&1), (1000, 100));
+
+			// only the funds still unbonding are rebonded
+			assert_ok!(NodleStaking::validator_rebond(RuntimeOrigin::signed(1), 100));
+			assert_eq!(
+				last_event(),
+				MetaEvent::NodleStaking(Event::ValidatorBondedMore(1, 90, 100))
+			);
+			assert!(NodleStaking::validator_state(&1).unwrap().unlocking.is_empty());
+			assert_eq!(NodleStaking::total(), 180);
+
+			assert_noop!(
+				NodleStaking::validator_rebond(RuntimeOrigin::signed(1), 1),
+				Error::<Test>::NoUnlockingChunk
+			);
+			assert_noop!(
+				NodleStaking::validator_rebond(RuntimeOrigin::signed(3), 1),
+				Error::<Test>::ValidatorDNE
+			);
+
+			assert_ok!(NodleStaking::validator_bond_less(RuntimeOrigin::signed(2), 10));
+			assert_ok!(NodleStaking::validator_exit_pool(RuntimeOrigin::signed(2)));
+			assert_noop!(
+				NodleStaking::validator_rebond(RuntimeOrigin::signed(2), 10),
+				Error::<Test>::CannotActivateIfLeaving
+			);
+		});
+}
+
+#[test]
+fn nominator_rebond_takes_the_newest_chunks_first() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 1000), (2, 1000), (3, 1000), (4, 1000)])
+		.with_validators(vec![(1, 100), (2, 100)])
+		.with_nominators(vec![(3, 1, 50)])
+		.tst_staking_build()
+		.execute_with(|| {
+			mock::start_active_session(1);
+			assert_ok!(NodleStaking::nominator_bond_less(RuntimeOrigin::signed(3), 1, 10));
+			mock::start_active_session(2);
+			assert_ok!(NodleStaking::nominator_bond_less(RuntimeOrigin::signed(3), 1, 20));
+			assert_eq!(NodleStaking::total(), 220);
+
+			assert_ok!(NodleStaking::nominator_rebond(RuntimeOrigin::signed(3), 1, 25));
+			assert_eq!(
+				last_event(),
+				MetaEvent::NodleStaking(Event::NominationIncreased(3, 45, 1, 120, 145))
+			);
+
+			let state = NodleStaking::nominator_state(&3).unwrap();
+			assert_eq!(state.active_bond, 45);
+			assert_eq!(state.total, 50);
+			assert_eq!(
+				state.unlocking.into_inner(),
+				vec![UnlockChunk {
+					value: 5,
+					session_idx: 3
+				}]
+			);
+			assert_eq!(mock::nominators_of(1), vec![Bond { owner: 3, amount: 45 }]);
+			assert_eq!(NodleStaking::total(), 245);
+			assert_eq!(
+				NodleStaking::validator_pool(),
+				vec![Bond { owner: 1, amount: 145 }, Bond { owner: 2, amount: 100 }]
+			);
+			assert_eq!(mock::balances(&3), (1000, 50));
+
+			assert_noop!(
+				NodleStaking::nominator_rebond(RuntimeOrigin::signed(3), 2, 5),
+				Error::<Test>::NominationDNE
+			);
+			assert_noop!(
+				NodleStaking::nominator_rebond(RuntimeOrigin::signed(4), 1, 5),
+				Error::<Test>::NominatorDNE
+			);
+
+			assert_ok!(NodleStaking::nominator_rebond(RuntimeOrigin::signed(3), 1, 10));
+			assert_eq!(mock::nominators_of(1), vec![Bond { owner: 3, amount: 50 }]);
+			assert_noop!(
+				NodleStaking::nominator_rebond(RuntimeOrigin::signed(3), 1, 5),
+				Error::<Test>::NoUnlockingChunk
+			);
+		});
+}
+
+#[test]
+fn poorly_performing_validators_are_chilled() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 1000), (2, 1000), (3, 1000)])
+		.with_validators(vec![(1, 100), (2, 100), (3, 100)])
+		.tst_staking_build()
+		.execute_with(|| {
+			mock::PoorPerformanceThreshold::set(Perbill::from_percent(50));
+
+			mock::start_active_session(1);
+			mock::author_blocks(1, 4);
+			mock::author_blocks(2, 4);
+			mock::start_active_session(2);
+
+			// the session lasted 10 slots, shared by 3 validators
+			assert_eq!(NodleStaking::session_start_slot(1), Some(Slot::from(10)));
+			assert_eq!(NodleStaking::session_start_slot(2), Some(Slot::from(20)));
+			assert_eq!(
+				NodleStaking::performance(1, 1),
+				SessionPerformance {
+					authored: 4,
+					expected: 3
+				}
+			);
+			assert_eq!(NodleStaking::performance(1, 3).missed(), 3);
+			assert!(NodleStaking::poor_sessions(1).is_zero());
+			assert_eq!(NodleStaking::poor_sessions(3), 1);
+			assert!(events().contains(&Event::ValidatorPerformedPoorly(1, 3, 0, 3, 1)));
+			assert!(NodleStaking::validator_state(&3).unwrap().is_active());
+			// scoring is accounted for in the block weight
+			assert!(System::block_weight().get(DispatchClass::Mandatory).all_gte(
+				<Test as frame_system::Config>::DbWeight::get()
+					.reads_writes(2, 2)
+					.saturating_mul(3)
+			));
+
+			// performing well again resets the count
+			mock::author_blocks(1, 2);
+			mock::author_blocks(2, 2);
+			mock::author_blocks(3, 2);
+			mock::start_active_session(3);
+			assert!(NodleStaking::poor_sessions(3).is_zero());
+
+			mock::author_blocks(1, 4);
+			mock::author_blocks(2, 4);
+			mock::start_active_session(4);
+			assert_eq!(NodleStaking::poor_sessions(3), 1);
+
+			mock::author_blocks(1, 4);
+			mock::author_blocks(2, 4);
+			mock::start_active_session(5);
+			assert!(events().contains(&Event::ValidatorChilled(4, 3)));
+			assert!(NodleStaking::poor_sessions(3).is_zero());
+			assert_eq!(NodleStaking::validator_state(&3).unwrap().state, ValidatorStatus::Idle);
+			assert_eq!(mock::validators_in_pool(), vec![1, 2]);
+			// no slash by default
+			assert_eq!(NodleStaking::validator_state(&3).unwrap().bond, 100);
+			assert_eq!(mock::balances(&3), (1000, 100));
+		});
+}
+
+#[test]
+fn chilled_validators_are_slashed_through_offences() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 1000), (2, 1000), (3, 1000), (4, 1000)])
+		.with_validators(vec![(1, 100), (2, 100), (3, 100)])
+		.with_nominators(vec![(4, 3, 50)])
+		.tst_staking_build()
+		.execute_with(|| {
+			mock::PoorPerformanceThreshold::set(Perbill::from_percent(50));
+			mock::PoorPerformanceSlash::set(Perbill::from_percent(10));
+
+			for session in 1..=2 {
+				mock::start_active_session(session);
+				mock::author_blocks(1, 4);
+				mock::author_blocks(2, 4);
+			}
+			mock::start_active_session(3);
+
+			assert!(events().contains(&Event::ValidatorChilled(2, 3)));
+			assert!(events().contains(&Event::Slash(3, 10)));
+			assert!(events().contains(&Event::Slash(4, 5)));
+			assert_eq!(NodleStaking::validator_state(&3).unwrap().bond, 90);
+			assert!(!NodleStaking::validator_state(&3).unwrap().is_active());
+		});
+}
+
+#[test]
+fn report_equivocation_slashes_the_offender() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 1000), (2, 1000), (3, 1000), (4, 1000)])
+		.with_validators(vec![(1, 100), (2, 100)])
+		.with_nominators(vec![(3, 1, 50)])
+		.tst_staking_build()
+		.execute_with(|| {
+			mock::start_active_session(1);
+			// session 1 started at slot 10, blocks are built every other slot
+			mock::run_to_block(7);
+
+			let key_owner_proof = |who: u64| {
+				<Historical as KeyOwnerProofSystem<_>>::prove((
+					<UintAuthorityId as RuntimeAppPublic>::ID,
+					UintAuthorityId(who),
+				))
+				.unwrap()
+			};
+			let equivocation_proof = |offender: u64, first_header: Header, second_header| {
+				Box::new(EquivocationProof {
+					offender: UintAuthorityId(offender),
+					slot: first_header
+						.digest()
+						.logs()
+						.iter()
+						.find_map(CompatibleDigestItem::<TestSignature>::as_aura_pre_digest)
+						.unwrap(),
+					first_header,
+					second_header,
+				})
+			};
+			let first_header = mock::aura_header(1, 12, H256::repeat_byte(1));
+			let second_header = mock::aura_header(1, 12, H256::repeat_byte(2));
+
+			// the same block twice
+			assert_noop!(
+				NodleStaking::report_equivocation(
+					RuntimeOrigin::signed(4),
+					equivocation_proof(1, first_header.clone(), first_header.clone()),
+					key_owner_proof(1),
+				),
+				Error::<Test>::InvalidEquivocationProof
+			);
+			// sealed by another authority
+			assert_noop!(
+				NodleStaking::report_equivocation(
+					RuntimeOrigin::signed(4),
+					equivocation_proof(1, first_header.clone(), mock::aura_header(2, 12, H256::repeat_byte(2))),
+					key_owner_proof(1),
+				),
+				Error::<Test>::InvalidEquivocationProof
+			);
+			// for another slot
+			assert_noop!(
+				NodleStaking::report_equivocation(
+					RuntimeOrigin::signed(4),
+					equivocation_proof(1, first_header.clone(), mock::aura_header(1, 13, H256::repeat_byte(2))),
+					key_owner_proof(1),
+				),
+				Error::<Test>::InvalidEquivocationProof
+			);
+			assert_noop!(
+				NodleStaking::report_equivocation(
+					RuntimeOrigin::signed(4),
+					equivocation_proof(1, first_header.clone(), second_header.clone()),
+					key_owner_proof(2),
+				),
+				Error::<Test>::InvalidKeyOwnershipProof
+			);
+
+			// the first slot of the session is sealed by an authority of the previous one
+			assert_noop!(
+				NodleStaking::report_equivocation(
+					RuntimeOrigin::signed(4),
+					equivocation_proof(
+						1,
+						mock::aura_header(1, 10, H256::repeat_byte(1)),
+						mock::aura_header(1, 10, H256::repeat_byte(2))
+					),
+					key_owner_proof(1),
+				),
+				Error::<Test>::InvalidEquivocationSlot
+			);
+			// slots to come
+			assert_noop!(
+				NodleStaking::report_equivocation(
+					RuntimeOrigin::signed(4),
+					equivocation_proof(
+						1,
+						mock::aura_header(1, 16, H256::repeat_byte(1)),
+						mock::aura_header(1, 16, H256::repeat_byte(2))
+					),
+					key_owner_proof(1),
+				),
+				Error::<Test>::InvalidEquivocationSlot
+			);
+
+			let info = NodleStaking::report_equivocation(
+				RuntimeOrigin::signed(4),
+				equivocation_proof(1, first_header.clone(), second_header.clone()),
+				key_owner_proof(1),
+			)
+			.unwrap();
+			assert_eq!(info.pays_fee, Pays::No);
+
+			let events = events();
+			assert!(events.contains(&Event::EquivocationReported(1, 1, 4)));
+			assert!(events.contains(&Event::Slash(1, 10)));
+			assert!(events.contains(&Event::Slash(3, 5)));
+			assert_eq!(NodleStaking::validator_state(&1).unwrap().bond, 90);
+			assert!(!NodleStaking::validator_state(&1).unwrap().is_active());
+			assert!(is_disabled(1));
+
+			assert_noop!(
+				NodleStaking::report_equivocation(
+					RuntimeOrigin::signed(4),
+					equivocation_proof(1, first_header.clone(), second_header.clone()),
+					key_owner_proof(1),
+				),
+				Error::<Test>::DuplicateEquivocationReport
+			);
+
+			// past `BondedDuration` the session can't be reported anymore
+			let key_owner_proof = key_owner_proof(2);
+			mock::start_active_session(4);
+			assert_noop!(
+				NodleStaking::report_equivocation(
+					RuntimeOrigin::signed(4),
+					equivocation_proof(
+						2,
+						mock::aura_header(2, 12, H256::repeat_byte(1)),
+						mock::aura_header(2, 12, H256::repeat_byte(2))
+					),
+					key_owner_proof,
+				),
+				Error::<Test>::InvalidSessionIndex
+			);
+		});
+}
+
+#[test]
+fn reduce_deferred_slash() {
+	ExtBuilder::default()
+		.slash_defer_duration(2)
+		.num_validators(4)
+		.build_and_execute(|| {
+			mock::start_active_session(1);
+
+			on_offence_now(
+				&[OffenceDetails {
+					offender: (11, NodleStaking::at_stake(NodleStaking::active_session(), 11)),
+					reporters: vec![],
+				}],
+				&[Perbill::from_percent(10)],
+				DisableStrategy::Always,
+			);
+
+			let pending = NodleStaking::pending_slashes();
+			assert_eq!(pending.len(), 1);
+			assert_eq!(
+				(
+					pending[0].validator,
+					pending[0].own,
+					pending[0].slash_session,
+					pending[0].apply_at
+				),
+				(11, 100, 1, 3)
+			);
+			assert_eq!(pending[0].others, vec![(101, 50)]);
+			let payout = pending[0].payout;
+
+			assert_noop!(
+				NodleStaking::slash_reduce_deferred(RuntimeOrigin::signed(1), 3, 0, 11, Perbill::from_percent(40)),
+				BadOrigin
+			);
+			assert_noop!(
+				NodleStaking::slash_reduce_deferred(RuntimeOrigin::root(), 3, 0, 21, Perbill::from_percent(40)),
+				Error::<Test>::UnappliedSlashDNE
+			);
+			assert_noop!(
+				NodleStaking::slash_reduce_deferred(RuntimeOrigin::root(), 3, 1, 11, Perbill::from_percent(40)),
+				Error::<Test>::UnappliedSlashDNE
+			);
+			assert_noop!(
+				NodleStaking::slash_reduce_deferred(RuntimeOrigin::root(), 4, 0, 11, Perbill::from_percent(40)),
+				Error::<Test>::UnappliedSlashDNE
+			);
+
+			assert_ok!(NodleStaking::slash_reduce_deferred(
+				RuntimeOrigin::signed(CancelOrigin::get()),
+				3,
+				0,
+				11,
+				Perbill::from_percent(40)
+			));
+			assert_eq!(
+				last_event(),
+				MetaEvent::NodleStaking(Event::DeferredSlashReduced(
+					3,
+					11,
+					Perbill::from_percent(40),
+					(100, 60),
+					(50, 30),
+					(payout, payout - Perbill::from_percent(40) * payout)
+				))
+			);
+
+			let pending = NodleStaking::pending_slashes();
+			assert_eq!(pending[0].own, 60);
+			assert_eq!(pending[0].others, vec![(101, 30)]);
+			assert_eq!(pending[0].payout, payout - Perbill::from_percent(40) * payout);
+
+			// a later offence is measured against the reduced slash
+			assert_eq!(
+				NodleStaking::validator_slash_in_session(1, 11),
+				Some((Perbill::from_percent(6), 60))
+			);
+			assert_eq!(NodleStaking::nominator_slash_in_session(1, 101), Some(30));
+			assert_eq!(<SpanSlash<Test>>::get((11, 0)).amount_slashed(), &60);
+			assert_eq!(<SpanSlash<Test>>::get((101, 0)).amount_slashed(), &30);
+
+			mock::start_active_session(3);
+
+			assert!(NodleStaking::pending_slashes().is_empty());
+			let events = mock::events();
+			assert!(events.contains(&Event::Slash(11, 60)));
+			assert!(events.contains(&Event::Slash(101, 30)));
+		});
+}
@@ -16,15 +16,16 @@
  * along with this program.  If not, see <http://www.gnu.org/licenses/>.
  */
 
-use super::{ActiveSession, AtStake, BalanceOf, Config, Pallet};
+use super::{ActiveSession, AtStake, Config, Pallet, SnapshotOf};
 use crate::set::OrderedSet;
-use codec::{Decode, Encode, HasCompact};
+use codec::{Decode, Encode, HasCompact, MaxEncodedLen};
+use frame_support::{traits::Get, BoundedVec, CloneNoBound, RuntimeDebugNoBound};
 use sp_runtime::{
 	traits::{AtLeast32BitUnsigned, Convert, Saturating, Zero},
-	RuntimeDebug,
+	Perbill, RuntimeDebug,
 };
 use sp_staking::SessionIndex;
-use sp_std::{cmp::Ordering, convert::From, prelude::*};
+use sp_std::{cmp::Ordering, fmt::Debug, prelude::*};
 
 /// The index of a slashing span - unique to each controller.
 pub(crate) type SpanIndex = u32;
@@ -32,7 +33,8 @@ pub(crate) type SpanIndex = u32;
 /// The type define for validators reward
 pub(crate) type RewardPoint = u32;
 
-#[derive(Clone, Encode, Decode, RuntimeDebug, scale_info::TypeInfo)]
+#[derive(Clone, Encode, Decode, RuntimeDebug, MaxEncodedLen, scale_info::TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub struct Bond<AccountId, Balance> {
 	pub owner: AccountId,
 	pub amount: Balance,
@@ -68,7 +70,8 @@ impl<AccountId: Ord, Balance> PartialOrd for Bond<AccountId, Balance> {
 }
 
 /// Just a Balance/BlockNumber tuple to encode when a chunk of funds will be unlocked.
-#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, Copy, scale_info::TypeInfo)]
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, Copy, MaxEncodedLen, scale_info::TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub struct UnlockChunk<Balance> {
 	/// Amount of funds to be unlocked.
 	pub(crate) value: Balance,
@@ -78,7 +81,31 @@ pub struct UnlockChunk<Balance> {
 
 pub(crate) type StakeReward<Balance> = UnlockChunk<Balance>;
 
-#[derive(Copy, Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, scale_info::TypeInfo)]
+/// Take up to `value` out of the `unlocking` chunks, newest first. Returns the amount taken.
+fn rebond_unlocking<Balance, MaxUnlocking>(
+	unlocking: &mut BoundedVec<UnlockChunk<Balance>, MaxUnlocking>,
+	value: Balance,
+) -> Balance
+where
+	Balance: AtLeast32BitUnsigned + Saturating + Copy,
+	MaxUnlocking: Get<u32>,
+{
+	let mut rebonded = Balance::zero();
+	for chunk in unlocking.iter_mut().rev() {
+		let take = chunk.value.min(value.saturating_sub(rebonded));
+		chunk.value = chunk.value.saturating_sub(take);
+		rebonded = rebonded.saturating_add(take);
+		if rebonded >= value {
+			break;
+		}
+	}
+	// kill all drained chunks.
+	unlocking.retain(|chunk| !chunk.value.is_zero());
+	rebonded
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, MaxEncodedLen, scale_info::TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 /// The activity status of the validator
 pub enum ValidatorStatus {
 	/// Committed to be online and producing valid blocks
@@ -95,33 +122,59 @@ impl Default for ValidatorStatus {
 	}
 }
 
-#[derive(Encode, Decode, Clone, RuntimeDebug, scale_info::TypeInfo)]
+/// Blocks authored by a validator in a session, against its share of the blocks of the session.
+#[derive(Copy, Clone, Default, PartialEq, Eq, Encode, Decode, RuntimeDebug, MaxEncodedLen, scale_info::TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct SessionPerformance {
+	pub authored: u32,
+	/// Set once the session ended.
+	pub expected: u32,
+}
+
+impl SessionPerformance {
+	/// Aura slots the validator missed in the session.
+	pub fn missed(&self) -> u32 {
+		self.expected.saturating_sub(self.authored)
+	}
+}
+
+#[derive(Encode, Decode, CloneNoBound, RuntimeDebugNoBound, MaxEncodedLen, scale_info::TypeInfo)]
+#[codec(mel_bound(AccountId: MaxEncodedLen, Balance: MaxEncodedLen))]
+#[scale_info(skip_type_params(MaxUnlocking))]
 /// Global validator state with commission fee, bonded stake, and nominations
-pub struct Validator<AccountId, Balance> {
+pub struct Validator<AccountId: Clone + Debug, Balance: Clone + Debug, MaxUnlocking: Get<u32>> {
 	pub id: AccountId,
 	pub bond: Balance,
 	pub nomi_bond_total: Balance,
-	pub nominators: OrderedSet<Bond<AccountId, Balance>>,
+	/// Number of nominators, their bonds are kept in `NominatorBonds`.
+	pub nominator_count: u32,
 	pub total: Balance,
 	pub state: ValidatorStatus,
-	pub unlocking: Vec<UnlockChunk<Balance>>,
+	pub unlocking: BoundedVec<UnlockChunk<Balance>, MaxUnlocking>,
+	/// Commission taken off of the rewards before they are shared with the nominators.
+	pub commission: Perbill,
+	/// Session in which the commission was last raised.
+	pub commission_raised_at: SessionIndex,
 }
 
 impl<
-		A: Ord + Clone + sp_std::fmt::Debug,
-		B: AtLeast32BitUnsigned + Ord + Copy + sp_std::ops::AddAssign + sp_std::ops::SubAssign + Default,
-	> Validator<A, B>
+		A: Clone + Debug,
+		B: AtLeast32BitUnsigned + Ord + Copy + Debug + sp_std::ops::AddAssign + sp_std::ops::SubAssign + Default,
+		MaxUnlocking: Get<u32>,
+	> Validator<A, B, MaxUnlocking>
 {
-	pub fn new(id: A, bond: B) -> Self {
+	pub fn new(id: A, bond: B, commission: Perbill, session_idx: SessionIndex) -> Self {
 		let total = bond;
 		Validator {
 			id,
 			bond,
 			nomi_bond_total: Zero::zero(),
-			nominators: OrderedSet::new(),
+			nominator_count: 0,
 			total,
 			state: ValidatorStatus::default(), // default active
-			unlocking: Vec::new(),
+			unlocking: BoundedVec::default(),
+			commission,
+			commission_raised_at: session_idx,
 		}
 	}
 	pub fn is_active(&self) -> bool {
@@ -143,21 +196,21 @@ impl<
 			None
 		}
 	}
-	pub fn inc_nominator(&mut self, nominator: A, more: B) {
-		if let Ok(loc) = self.nominators.0.binary_search(&Bond::from_owner(nominator)) {
-			let nom_bond = &mut self.nominators.0[loc];
-			nom_bond.amount = nom_bond.amount.saturating_add(more);
-			self.nomi_bond_total = self.nomi_bond_total.saturating_add(more);
-			self.total = self.total.saturating_add(more);
-		};
+	pub fn add_nominator(&mut self, amount: B) {
+		self.nominator_count = self.nominator_count.saturating_add(1);
+		self.inc_nominator(amount);
 	}
-	pub fn dec_nominator(&mut self, nominator: A, less: B) {
-		if let Ok(loc) = self.nominators.0.binary_search(&Bond::from_owner(nominator)) {
-			let nom_bond = &mut self.nominators.0[loc];
-			nom_bond.amount = nom_bond.amount.saturating_sub(less);
-			self.nomi_bond_total = self.nomi_bond_total.saturating_sub(less);
-			self.total = self.total.saturating_sub(less);
-		};
+	pub fn rm_nominator(&mut self, amount: B) {
+		self.nominator_count = self.nominator_count.saturating_sub(1);
+		self.dec_nominator(amount);
+	}
+	pub fn inc_nominator(&mut self, more: B) {
+		self.nomi_bond_total = self.nomi_bond_total.saturating_add(more);
+		self.total = self.total.saturating_add(more);
+	}
+	pub fn dec_nominator(&mut self, less: B) {
+		self.nomi_bond_total = self.nomi_bond_total.saturating_sub(less);
+		self.total = self.total.saturating_sub(less);
 	}
 	pub fn go_offline(&mut self) {
 		self.state = ValidatorStatus::Idle;
@@ -170,9 +223,11 @@ impl<
 	}
 }
 
-impl<AccountId, Balance> Validator<AccountId, Balance>
+impl<AccountId, Balance, MaxUnlocking> Validator<AccountId, Balance, MaxUnlocking>
 where
-	Balance: AtLeast32BitUnsigned + Saturating + Copy + sp_std::ops::AddAssign + sp_std::ops::SubAssign,
+	AccountId: Clone + Debug,
+	Balance: AtLeast32BitUnsigned + Saturating + Copy + Debug + sp_std::ops::AddAssign + sp_std::ops::SubAssign,
+	MaxUnlocking: Get<u32>,
 {
 	/// Slash the validator for a given amount of balance. This can grow the value
 	/// of the slash in the case that the validator has less than `minimum_balance`
@@ -204,7 +259,7 @@ where
 
 		slash_out_of(total, active, &mut value);
 
-		let i = self
+		let _ = self
 			.unlocking
 			.iter_mut()
 			.map(|chunk| {
@@ -215,7 +270,7 @@ where
 			.count();
 
 		// kill all drained chunks.
-		let _ = self.unlocking.drain(..i);
+		self.unlocking.retain(|chunk| !chunk.value.is_zero());
 
 		pre_total.saturating_sub(*total)
 	}
@@ -235,57 +290,24 @@ where
 		self.total = total;
 		unlocked_val
 	}
+	/// Move up to `value` from the newest `unlocking` chunks back to the bond, the total is
+	/// left as is. Returns the amount rebonded.
+	pub fn rebond(&mut self, value: Balance) -> Balance {
+		let rebonded = rebond_unlocking(&mut self.unlocking, value);
+		self.bond = self.bond.saturating_add(rebonded);
+		rebonded
+	}
 }
 
-#[derive(Clone, Encode, Decode, RuntimeDebug, scale_info::TypeInfo)]
-/// Snapshot of validator state at the start of the round for which they are selected
-pub struct ValidatorSnapshot<AccountId, Balance> {
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, Default, RuntimeDebug, MaxEncodedLen, scale_info::TypeInfo)]
+/// Snapshot of validator state at the start of the round for which they are selected, its
+/// nominators are kept in the `AtStakePage` pages
+pub struct ValidatorSnapshot<Balance> {
 	pub bond: Balance,
-	pub nominators: Vec<Bond<AccountId, Balance>>,
 	pub total: Balance,
-}
-
-impl<A: Clone, B: Copy + sp_std::ops::AddAssign + sp_std::ops::Add<Output = B> + sp_std::ops::SubAssign>
-	From<Validator<A, B>> for ValidatorSnapshot<A, B>
-{
-	fn from(other: Validator<A, B>) -> ValidatorSnapshot<A, B> {
-		ValidatorSnapshot {
-			bond: other.bond,
-			nominators: other.nominators.0,
-			total: other.bond + other.nomi_bond_total,
-		}
-	}
-}
-
-impl<AccountId, Balance: Default + HasCompact> Default for ValidatorSnapshot<AccountId, Balance> {
-	fn default() -> Self {
-		Self {
-			bond: Default::default(),
-			nominators: vec![],
-			total: Default::default(),
-		}
-	}
-}
-
-impl<AccountId: Ord, Balance: Ord> Eq for ValidatorSnapshot<AccountId, Balance> {}
-
-impl<AccountId: Ord, Balance: Ord> PartialEq for ValidatorSnapshot<AccountId, Balance> {
-	fn eq(&self, other: &Self) -> bool {
-		self.nominators == other.nominators
-	}
-}
-
-impl<AccountId: Ord, Balance: Ord> Ord for ValidatorSnapshot<AccountId, Balance> {
-	fn cmp(&self, other: &Self) -> Ordering {
-		self.total.cmp(&other.total)
-	}
-}
-
-impl<AccountId: Ord, Balance: Ord> PartialOrd for ValidatorSnapshot<AccountId, Balance> {
-	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-		Some(self.cmp(other))
-		// Some(self.total.cmp(&other.total))
-	}
+	pub commission: Perbill,
+	/// Number of nominators across the pages
+	pub nominator_count: u32,
 }
 
 /// A typed conversion from stash account ID to the active exposure of nominators
@@ -295,10 +317,8 @@ impl<AccountId: Ord, Balance: Ord> PartialOrd for ValidatorSnapshot<AccountId, B
 /// `active_era`. It can differ from the latest planned exposure in `current_era`.
 pub struct ValidatorSnapshotOf<T>(sp_std::marker::PhantomData<T>);
 
-impl<T: Config> Convert<T::AccountId, Option<ValidatorSnapshot<T::AccountId, BalanceOf<T>>>>
-	for ValidatorSnapshotOf<T>
-{
-	fn convert(validator: T::AccountId) -> Option<ValidatorSnapshot<T::AccountId, BalanceOf<T>>> {
+impl<T: Config> Convert<T::AccountId, Option<SnapshotOf<T>>> for ValidatorSnapshotOf<T> {
+	fn convert(validator: T::AccountId) -> Option<SnapshotOf<T>> {
 		let now = <ActiveSession<T>>::get();
 		if <AtStake<T>>::contains_key(now, &validator) {
 			Some(<Pallet<T>>::at_stake(now, &validator))
@@ -308,18 +328,26 @@ impl<T: Config> Convert<T::AccountId, Option<ValidatorSnapshot<T::AccountId, Bal
 	}
 }
 
-#[derive(Encode, Decode, Clone, RuntimeDebug, scale_info::TypeInfo)]
-pub struct Nominator<AccountId, Balance> {
-	pub nominations: OrderedSet<Bond<AccountId, Balance>>,
+#[derive(Encode, Decode, CloneNoBound, RuntimeDebugNoBound, MaxEncodedLen, scale_info::TypeInfo)]
+#[codec(mel_bound(AccountId: MaxEncodedLen, Balance: MaxEncodedLen))]
+#[scale_info(skip_type_params(MaxNominations, MaxUnlocking))]
+pub struct Nominator<
+	AccountId: Ord + Clone + Debug,
+	Balance: Clone + Debug,
+	MaxNominations: Get<u32>,
+	MaxUnlocking: Get<u32>,
+> {
+	pub nominations: OrderedSet<Bond<AccountId, Balance>, MaxNominations>,
 	pub total: Balance,
 	pub active_bond: Balance,
 	pub frozen_bond: Balance,
-	pub unlocking: Vec<UnlockChunk<Balance>>,
+	pub unlocking: BoundedVec<UnlockChunk<Balance>, MaxUnlocking>,
 }
 
 impl<
-		AccountId: Ord + Clone,
+		AccountId: Ord + Clone + Debug,
 		Balance: Copy
+			+ Debug
 			+ AtLeast32BitUnsigned
 			+ Saturating
 			+ sp_std::ops::AddAssign
@@ -327,23 +355,26 @@ impl<
 			+ sp_std::ops::SubAssign
 			+ PartialOrd
 			+ Default,
-	> Nominator<AccountId, Balance>
+		MaxNominations: Get<u32>,
+		MaxUnlocking: Get<u32>,
+	> Nominator<AccountId, Balance, MaxNominations, MaxUnlocking>
 {
 	pub fn new(validator: AccountId, amount: Balance) -> Self {
 		Nominator {
-			nominations: OrderedSet::from(vec![Bond {
+			nominations: OrderedSet::from_sorted_set(BoundedVec::truncate_from(vec![Bond {
 				owner: validator,
 				amount,
-			}]),
+			}])),
 			total: amount,
 			active_bond: amount,
 			frozen_bond: Zero::zero(),
-			unlocking: Vec::new(),
+			unlocking: BoundedVec::default(),
 		}
 	}
+	// Returns false if the validator is already nominated or the nominations are full
 	pub fn add_nomination(&mut self, bond: Bond<AccountId, Balance>, unfreeze_bond: bool) -> bool {
 		let amt = bond.amount;
-		if self.nominations.insert(bond) {
+		if let Ok(true) = self.nominations.try_insert(bond) {
 			if unfreeze_bond {
 				self.frozen_bond = Zero::zero();
 			}
@@ -357,30 +388,13 @@ impl<
 	// Returns Some(remaining balance), must be more than MinNominatorStake
 	// Returns None if nomination not found
 	pub fn rm_nomination(&mut self, validator: AccountId, freeze_bond: bool) -> Option<Balance> {
-		let mut amt: Option<Balance> = None;
-		let nominations = self
-			.nominations
-			.0
-			.iter()
-			.filter_map(|x| {
-				if x.owner == validator {
-					amt = Some(x.amount);
-					None
-				} else {
-					Some(x.clone())
-				}
-			})
-			.collect();
-		if let Some(balance) = amt {
-			self.nominations = OrderedSet::from(nominations);
-			self.active_bond = self.active_bond.saturating_sub(balance);
-			if freeze_bond {
-				self.frozen_bond = self.frozen_bond.saturating_add(balance);
-			}
-			Some(self.active_bond)
-		} else {
-			None
+		let loc = self.nominations.contains(&Bond::from_owner(validator))?;
+		let balance = self.nominations.0.remove(loc).amount;
+		self.active_bond = self.active_bond.saturating_sub(balance);
+		if freeze_bond {
+			self.frozen_bond = self.frozen_bond.saturating_add(balance);
 		}
+		Some(self.active_bond)
 	}
 
 	pub fn unbond_frozen(&mut self) -> Option<Balance> {
@@ -396,9 +410,8 @@ impl<
 
 	// Returns None if nomination not found
 	pub fn inc_nomination(&mut self, validator: AccountId, more: Balance, unfreeze_bond: bool) -> Option<Balance> {
-		match self.nominations.0.binary_search(&Bond::from_owner(validator)) {
-			Ok(loc) => {
-				let nom_bond = &mut self.nominations.0[loc];
+		match self.nominations.get_mut(&Bond::from_owner(validator)) {
+			Some(nom_bond) => {
 				nom_bond.amount = nom_bond.amount.saturating_add(more);
 				self.total = self.total.saturating_add(more);
 				self.active_bond = self.active_bond.saturating_add(more);
@@ -407,13 +420,12 @@ impl<
 				}
 				Some(nom_bond.amount)
 			}
-			Err(_) => None,
+			None => None,
 		}
 	}
 	pub fn dec_nomination(&mut self, validator: AccountId, less: Balance) -> Result<Balance, &str> {
-		match self.nominations.0.binary_search(&Bond::from_owner(validator)) {
-			Ok(loc) => {
-				let nom_bond = &mut self.nominations.0[loc];
+		match self.nominations.get_mut(&Bond::from_owner(validator)) {
+			Some(nom_bond) => {
 				if nom_bond.amount > less {
 					nom_bond.amount = nom_bond.amount.saturating_sub(less);
 					self.active_bond = self.active_bond.saturating_sub(less);
@@ -422,15 +434,18 @@ impl<
 					Err("Underflow")
 				}
 			}
-			Err(_) => Err("NominationDNE"),
+			None => Err("NominationDNE"),
 		}
 	}
 }
 
-impl<AccountId, Balance> Nominator<AccountId, Balance>
+impl<AccountId, Balance, MaxNominations, MaxUnlocking> Nominator<AccountId, Balance, MaxNominations, MaxUnlocking>
 where
-	AccountId: Ord + Clone,
-	Balance: AtLeast32BitUnsigned + Saturating + Copy + sp_std::ops::AddAssign + sp_std::ops::SubAssign + Default,
+	AccountId: Ord + Clone + Debug,
+	Balance:
+		AtLeast32BitUnsigned + Saturating + Copy + Debug + sp_std::ops::AddAssign + sp_std::ops::SubAssign + Default,
+	MaxNominations: Get<u32>,
+	MaxUnlocking: Get<u32>,
 {
 	/// Slash the validator for a given amount of balance. This can grow the value
 	/// of the slash in the case that the validator has less than `minimum_balance`
@@ -467,14 +482,13 @@ where
 			}
 		};
 
-		if let Ok(loc) = self.nominations.0.binary_search(&Bond::from_owner(validator)) {
-			let nom_bond = &mut self.nominations.0[loc];
+		if let Some(nom_bond) = self.nominations.get_mut(&Bond::from_owner(validator)) {
 			slash_out_of(active_bond, &mut nom_bond.amount, &mut value);
 		};
 
 		*total = total.saturating_sub(pre_active_bond.saturating_sub(*active_bond));
 
-		let i = self
+		let _ = self
 			.unlocking
 			.iter_mut()
 			.map(|chunk| {
@@ -485,7 +499,7 @@ where
 			.count();
 
 		// kill all drained chunks.
-		let _ = self.unlocking.drain(..i);
+		self.unlocking.retain(|chunk| !chunk.value.is_zero());
 		pre_total.saturating_sub(*total)
 	}
 	/// Remove entries from `unlocking` that are sufficiently old and reduce the
@@ -504,33 +518,60 @@ where
 		self.total = total;
 		unlocked_val
 	}
+	/// Move up to `value` from the newest `unlocking` chunks back to the nomination of
+	/// `validator`, the total is left as is. Returns the amount rebonded along with the new
+	/// nomination bond, None if the nomination is not found.
+	pub fn rebond(&mut self, validator: AccountId, value: Balance) -> Option<(Balance, Balance)> {
+		let nom_bond = self.nominations.get_mut(&Bond::from_owner(validator))?;
+		let rebonded = rebond_unlocking(&mut self.unlocking, value);
+		nom_bond.amount = nom_bond.amount.saturating_add(rebonded);
+		self.active_bond = self.active_bond.saturating_add(rebonded);
+		Some((rebonded, nom_bond.amount))
+	}
 }
 
 /// A pending slash record. The value of the slash has been computed but not applied yet,
 /// rather deferred for several eras.
-#[derive(Encode, Decode, RuntimeDebug, Clone, scale_info::TypeInfo)]
-pub struct UnappliedSlash<AccountId, Balance: HasCompact> {
+#[derive(Encode, Decode, RuntimeDebugNoBound, CloneNoBound, MaxEncodedLen, scale_info::TypeInfo)]
+#[codec(mel_bound(AccountId: MaxEncodedLen, Balance: MaxEncodedLen))]
+#[scale_info(skip_type_params(MaxNominators, MaxReporters))]
+pub struct UnappliedSlash<
+	AccountId: Clone + Debug,
+	Balance: HasCompact + Clone + Debug,
+	MaxNominators: Get<u32>,
+	MaxReporters: Get<u32>,
+> {
 	/// The stash ID of the offending validator.
 	pub(crate) validator: AccountId,
 	/// The validator's own slash.
 	pub(crate) own: Balance,
-	/// All other slashed stakers and amounts.
-	pub(crate) others: Vec<(AccountId, Balance)>,
+	/// All other slashed stakers and amounts, at most one per nominator of the validator.
+	pub(crate) others: BoundedVec<(AccountId, Balance), MaxNominators>,
 	/// Reporters of the offence; bounty payout recipients.
-	pub(crate) reporters: Vec<AccountId>,
+	pub(crate) reporters: BoundedVec<AccountId, MaxReporters>,
 	/// The amount of payout.
 	pub(crate) payout: Balance,
+	/// The session the offence occurred in, the slashing records of the offence are kept
+	/// under it.
+	pub(crate) slash_session: SessionIndex,
 }
 
 #[allow(dead_code)]
-impl<AccountId, Balance: Default + HasCompact> UnappliedSlash<AccountId, Balance> {
+impl<
+		AccountId: Clone + Debug,
+		Balance: Default + HasCompact + Clone + Debug,
+		MaxNominators: Get<u32>,
+		MaxReporters: Get<u32>,
+	> UnappliedSlash<AccountId, Balance, MaxNominators, MaxReporters>
+{
 	pub(crate) fn from_default(validator: AccountId) -> Self {
 		Self {
 			validator,
 			own: Default::default(),
-			others: vec![],
-			reporters: vec![],
+			others: BoundedVec::default(),
+			reporters: BoundedVec::default(),
 			payout: Default::default(),
+			slash_session: Default::default(),
 		}
 	}
 }
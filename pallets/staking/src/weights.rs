@@ -21,6 +21,11 @@
 //! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 3.0.0
 //! DATE: 2021-07-18, STEPS: `[50, ]`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
 //! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 128
+//!
+//! The weights of `validator_set_commission`, `set_commission_limits`, `set_auto_compound`,
+//! `payout_stakers`, `validator_rebond`, `nominator_rebond`, `report_equivocation` and
+//! `slash_reduce_deferred` are hand-written estimates, not benchmark output. Regenerate this
+//! file with `scripts/run_benchmarks.sh` to replace them with measured weights.
 
 // Executed Command:
 // target/release/nodle-chain
@@ -64,6 +69,14 @@ pub trait WeightInfo {
 	fn unbond_frozen() -> Weight;
 	fn slash_cancel_deferred(s: u32, c: u32) -> Weight;
 	fn withdraw_staking_rewards() -> Weight;
+	fn validator_set_commission() -> Weight;
+	fn set_commission_limits() -> Weight;
+	fn set_auto_compound() -> Weight;
+	fn payout_stakers(n: u32) -> Weight;
+	fn validator_rebond() -> Weight;
+	fn nominator_rebond() -> Weight;
+	fn report_equivocation(n: u32) -> Weight;
+	fn slash_reduce_deferred(n: u32) -> Weight;
 }
 
 /// Weights for pallet_nodle_staking using the Substrate node and recommended hardware.
@@ -160,6 +173,61 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(5_u64))
 			.saturating_add(T::DbWeight::get().writes(3_u64))
 	}
+	fn validator_set_commission() -> Weight {
+		// Estimate, see the module docs.
+		(Weight::from_ref_time(32_417_000_u64))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn set_commission_limits() -> Weight {
+		// Estimate, see the module docs.
+		(Weight::from_ref_time(18_906_000_u64)).saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	fn set_auto_compound() -> Weight {
+		// Estimate, see the module docs.
+		(Weight::from_ref_time(21_534_000_u64))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn payout_stakers(n: u32) -> Weight {
+		// Estimate, see the module docs.
+		(Weight::from_ref_time(118_406_000_u64))
+			.saturating_add((Weight::from_ref_time(61_285_000_u64)).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads(16_u64))
+			.saturating_add(T::DbWeight::get().reads((8_u64).saturating_mul(n as u64)))
+			.saturating_add(T::DbWeight::get().writes(8_u64))
+			.saturating_add(T::DbWeight::get().writes((7_u64).saturating_mul(n as u64)))
+	}
+	fn validator_rebond() -> Weight {
+		// Estimate, see the module docs.
+		(Weight::from_ref_time(58_914_000_u64))
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	fn nominator_rebond() -> Weight {
+		// Estimate, see the module docs.
+		(Weight::from_ref_time(79_502_000_u64))
+			.saturating_add(T::DbWeight::get().reads(9_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	fn report_equivocation(n: u32) -> Weight {
+		// Estimate, see the module docs.
+		(Weight::from_ref_time(152_370_000_u64))
+			.saturating_add((Weight::from_ref_time(24_816_000_u64)).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads(14_u64))
+			.saturating_add(T::DbWeight::get().reads((4_u64).saturating_mul(n as u64)))
+			.saturating_add(T::DbWeight::get().writes(10_u64))
+			.saturating_add(T::DbWeight::get().writes((4_u64).saturating_mul(n as u64)))
+	}
+	fn slash_reduce_deferred(n: u32) -> Weight {
+		// Estimate, see the module docs.
+		(Weight::from_ref_time(64_208_000_u64))
+			.saturating_add((Weight::from_ref_time(9_870_000_u64)).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(n as u64)))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(n as u64)))
+	}
 }
 
 // For backwards compatibility and tests
@@ -255,4 +323,59 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(5_u64))
 			.saturating_add(RocksDbWeight::get().writes(3_u64))
 	}
+	fn validator_set_commission() -> Weight {
+		// Estimate, see the module docs.
+		(Weight::from_ref_time(32_417_000_u64))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn set_commission_limits() -> Weight {
+		// Estimate, see the module docs.
+		(Weight::from_ref_time(18_906_000_u64)).saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	fn set_auto_compound() -> Weight {
+		// Estimate, see the module docs.
+		(Weight::from_ref_time(21_534_000_u64))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn payout_stakers(n: u32) -> Weight {
+		// Estimate, see the module docs.
+		(Weight::from_ref_time(118_406_000_u64))
+			.saturating_add((Weight::from_ref_time(61_285_000_u64)).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads(16_u64))
+			.saturating_add(RocksDbWeight::get().reads((8_u64).saturating_mul(n as u64)))
+			.saturating_add(RocksDbWeight::get().writes(8_u64))
+			.saturating_add(RocksDbWeight::get().writes((7_u64).saturating_mul(n as u64)))
+	}
+	fn validator_rebond() -> Weight {
+		// Estimate, see the module docs.
+		(Weight::from_ref_time(58_914_000_u64))
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	fn nominator_rebond() -> Weight {
+		// Estimate, see the module docs.
+		(Weight::from_ref_time(79_502_000_u64))
+			.saturating_add(RocksDbWeight::get().reads(9_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	fn report_equivocation(n: u32) -> Weight {
+		// Estimate, see the module docs.
+		(Weight::from_ref_time(152_370_000_u64))
+			.saturating_add((Weight::from_ref_time(24_816_000_u64)).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads(14_u64))
+			.saturating_add(RocksDbWeight::get().reads((4_u64).saturating_mul(n as u64)))
+			.saturating_add(RocksDbWeight::get().writes(10_u64))
+			.saturating_add(RocksDbWeight::get().writes((4_u64).saturating_mul(n as u64)))
+	}
+	fn slash_reduce_deferred(n: u32) -> Weight {
+		// Estimate, see the module docs.
+		(Weight::from_ref_time(64_208_000_u64))
+			.saturating_add((Weight::from_ref_time(9_870_000_u64)).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(n as u64)))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(n as u64)))
+	}
 }
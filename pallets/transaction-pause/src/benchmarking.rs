@@ -0,0 +1,62 @@
+/*
+ * This file is part of the Nodle Chain distributed at https://github.com/NodleCode/chain
+ * Copyright (C) 2020-2022  Nodle International
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Transaction pause pallet benchmarks
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite};
+use frame_support::traits::{EnsureOrigin, UnfilteredDispatchable};
+use sp_std::prelude::*;
+
+#[cfg(test)]
+use crate::Pallet as TransactionPause;
+
+// Long enough names, unlikely to be exempt from pausing.
+const PALLET_NAME: [u8; 64] = [b'p'; 64];
+const CALL_NAME: [u8; 64] = [b'c'; 64];
+
+benchmarks! {
+	pause {
+		let call = Call::<T>::pause{
+			pallet_name: PALLET_NAME.to_vec(),
+			call_name: CALL_NAME.to_vec()
+		};
+		let origin = T::PauseOrigin::successful_origin();
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert!(Pallet::<T>::is_paused(&PALLET_NAME, &CALL_NAME));
+	}
+
+	unpause {
+		Pallet::<T>::pause(T::PauseOrigin::successful_origin(), PALLET_NAME.to_vec(), CALL_NAME.to_vec())?;
+
+		let call = Call::<T>::unpause{
+			pallet_name: PALLET_NAME.to_vec(),
+			call_name: CALL_NAME.to_vec()
+		};
+		let origin = T::PauseOrigin::successful_origin();
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert!(!Pallet::<T>::is_paused(&PALLET_NAME, &CALL_NAME));
+	}
+
+	impl_benchmark_test_suite!(TransactionPause, crate::tests::new_test_ext(), crate::tests::Test,);
+}
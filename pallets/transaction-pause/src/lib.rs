@@ -0,0 +1,159 @@
+/*
+ * This file is part of the Nodle Chain distributed at https://github.com/NodleCode/chain
+ * Copyright (C) 2020-2022  Nodle International
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Let governance pause individual calls, identified by their pallet and call names, without a
+//! runtime upgrade. The runtime consults the pallet from its `BaseCallFilter`.
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod tests;
+
+pub mod weights;
+pub use weights::WeightInfo;
+
+use frame_support::traits::{Contains, GetCallMetadata};
+
+pub use pallet::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use sp_std::prelude::*;
+
+	pub type MaxNameLength = ConstU32<64>;
+	pub type Name = BoundedVec<u8, MaxNameLength>;
+	/// A call, identified by its pallet and call names.
+	pub type FullName = (Name, Name);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// Origin that can pause and unpause calls.
+		type PauseOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Calls that can never be paused, typically those needed for governance and recovery.
+		type ExemptCalls: Contains<FullName>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Prevent `call_name` of `pallet_name` from being dispatched.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::pause())]
+		pub fn pause(origin: OriginFor<T>, pallet_name: Vec<u8>, call_name: Vec<u8>) -> DispatchResultWithPostInfo {
+			T::PauseOrigin::ensure_origin(origin)?;
+
+			let full_name = Self::full_name(pallet_name, call_name)?;
+			ensure!(!T::ExemptCalls::contains(&full_name), Error::<T>::CannotPause);
+			ensure!(!PausedCalls::<T>::contains_key(&full_name), Error::<T>::AlreadyPaused);
+
+			PausedCalls::<T>::insert(&full_name, ());
+
+			Self::deposit_event(Event::CallPaused(full_name.0.into_inner(), full_name.1.into_inner()));
+
+			Ok(().into())
+		}
+
+		/// Let `call_name` of `pallet_name` be dispatched again.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::unpause())]
+		pub fn unpause(origin: OriginFor<T>, pallet_name: Vec<u8>, call_name: Vec<u8>) -> DispatchResultWithPostInfo {
+			T::PauseOrigin::ensure_origin(origin)?;
+
+			let full_name = Self::full_name(pallet_name, call_name)?;
+			PausedCalls::<T>::take(&full_name).ok_or(Error::<T>::NotPaused)?;
+
+			Self::deposit_event(Event::CallUnpaused(full_name.0.into_inner(), full_name.1.into_inner()));
+
+			Ok(().into())
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A call was paused \[pallet name, call name\]
+		CallPaused(Vec<u8>, Vec<u8>),
+		/// A call was unpaused \[pallet name, call name\]
+		CallUnpaused(Vec<u8>, Vec<u8>),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The call is exempt from pausing
+		CannotPause,
+		/// The call is already paused
+		AlreadyPaused,
+		/// The call is not paused
+		NotPaused,
+		/// Pallet or call name is longer than `MaxNameLength`
+		NameTooLong,
+	}
+
+	/// Calls that cannot be dispatched, by pallet and call name.
+	#[pallet::storage]
+	pub type PausedCalls<T: Config> = StorageMap<_, Blake2_128Concat, FullName, (), OptionQuery>;
+
+	impl<T: Config> Pallet<T> {
+		fn full_name(pallet_name: Vec<u8>, call_name: Vec<u8>) -> Result<FullName, Error<T>> {
+			let pallet_name: Name = pallet_name.try_into().map_err(|_| Error::<T>::NameTooLong)?;
+			let call_name: Name = call_name.try_into().map_err(|_| Error::<T>::NameTooLong)?;
+			Ok((pallet_name, call_name))
+		}
+
+		/// Whether `call_name` of `pallet_name` is paused.
+		pub fn is_paused(pallet_name: &[u8], call_name: &[u8]) -> bool {
+			match Self::full_name(pallet_name.to_vec(), call_name.to_vec()) {
+				Ok(full_name) => PausedCalls::<T>::contains_key(full_name),
+				// Names too long to be stored cannot have been paused
+				Err(_) => false,
+			}
+		}
+
+		/// All the paused calls, as pallet and call names.
+		pub fn paused_calls() -> Vec<(Vec<u8>, Vec<u8>)> {
+			PausedCalls::<T>::iter_keys()
+				.map(|(pallet_name, call_name)| (pallet_name.into_inner(), call_name.into_inner()))
+				.collect()
+		}
+	}
+}
+
+/// Lets through the calls that are not paused, to be used in the runtime's `BaseCallFilter`.
+impl<T: Config, Call: GetCallMetadata> Contains<Call> for Pallet<T> {
+	fn contains(call: &Call) -> bool {
+		let metadata = call.get_call_metadata();
+		!Self::is_paused(metadata.pallet_name.as_bytes(), metadata.function_name.as_bytes())
+	}
+}
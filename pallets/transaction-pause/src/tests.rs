@@ -0,0 +1,190 @@
+/*
+ * This file is part of the Nodle Chain distributed at https://github.com/NodleCode/chain
+ * Copyright (C) 2020-2022  Nodle International
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+#![cfg(test)]
+
+use super::*;
+use crate::{self as pallet_transaction_pause};
+use frame_support::{assert_noop, assert_ok, ord_parameter_types, parameter_types, traits::Contains};
+use frame_system::{Call as SystemCall, EnsureSignedBy};
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BadOrigin, BlakeTwo256, Dispatchable, IdentityLookup},
+	DispatchError,
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		TransactionPause: pallet_transaction_pause::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+impl frame_system::Config for Test {
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type SS58Prefix = ();
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type DbWeight = ();
+	type BaseCallFilter = TransactionPause;
+	type OnSetCode = ();
+	type SystemWeightInfo = ();
+	type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+
+/// The pallet can never pause itself.
+pub struct ExemptCalls;
+impl Contains<FullName> for ExemptCalls {
+	fn contains(full_name: &FullName) -> bool {
+		full_name.0.as_slice() == b"TransactionPause"
+	}
+}
+
+ord_parameter_types! {
+	pub const Admin: u64 = 1;
+}
+impl Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type PauseOrigin = EnsureSignedBy<Admin, u64>;
+	type ExemptCalls = ExemptCalls;
+	type WeightInfo = ();
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+fn remark() -> RuntimeCall {
+	RuntimeCall::System(SystemCall::remark { remark: vec![] })
+}
+
+#[test]
+fn paused_calls_are_filtered() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(remark().dispatch(RuntimeOrigin::signed(2)));
+
+		assert_ok!(TransactionPause::pause(
+			RuntimeOrigin::signed(Admin::get()),
+			b"System".to_vec(),
+			b"remark".to_vec()
+		));
+		System::assert_last_event(Event::CallPaused(b"System".to_vec(), b"remark".to_vec()).into());
+		assert!(!TransactionPause::contains(&remark()));
+		assert_eq!(
+			remark().dispatch(RuntimeOrigin::signed(2)).map_err(|e| e.error),
+			Err(DispatchError::from(frame_system::Error::<Test>::CallFiltered))
+		);
+		assert_eq!(
+			TransactionPause::paused_calls(),
+			vec![(b"System".to_vec(), b"remark".to_vec())]
+		);
+
+		// Other calls of the pallet are left alone
+		assert!(TransactionPause::contains(&RuntimeCall::System(
+			SystemCall::remark_with_event { remark: vec![] }
+		)));
+
+		assert_ok!(TransactionPause::unpause(
+			RuntimeOrigin::signed(Admin::get()),
+			b"System".to_vec(),
+			b"remark".to_vec()
+		));
+		System::assert_last_event(Event::CallUnpaused(b"System".to_vec(), b"remark".to_vec()).into());
+		assert_ok!(remark().dispatch(RuntimeOrigin::signed(2)));
+		assert!(TransactionPause::paused_calls().is_empty());
+	})
+}
+
+#[test]
+fn exempt_calls_cannot_be_paused() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TransactionPause::pause(
+				RuntimeOrigin::signed(Admin::get()),
+				b"TransactionPause".to_vec(),
+				b"unpause".to_vec()
+			),
+			Error::<Test>::CannotPause
+		);
+	})
+}
+
+#[test]
+fn pause_and_unpause_errors() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TransactionPause::pause(RuntimeOrigin::signed(2), b"System".to_vec(), b"remark".to_vec()),
+			BadOrigin
+		);
+		assert_noop!(
+			TransactionPause::unpause(
+				RuntimeOrigin::signed(Admin::get()),
+				b"System".to_vec(),
+				b"remark".to_vec()
+			),
+			Error::<Test>::NotPaused
+		);
+		assert_noop!(
+			TransactionPause::pause(RuntimeOrigin::signed(Admin::get()), vec![0; 65], b"remark".to_vec()),
+			Error::<Test>::NameTooLong
+		);
+
+		assert_ok!(TransactionPause::pause(
+			RuntimeOrigin::signed(Admin::get()),
+			b"System".to_vec(),
+			b"remark".to_vec()
+		));
+		assert_noop!(
+			TransactionPause::pause(
+				RuntimeOrigin::signed(Admin::get()),
+				b"System".to_vec(),
+				b"remark".to_vec()
+			),
+			Error::<Test>::AlreadyPaused
+		);
+	})
+}
@@ -18,21 +18,23 @@
 
 //! Auxillary struct/enums for polkadot runtime.
 
-use crate::{Balances, CollatorSelection, CompanyReserve};
+use crate::{Balances, Runtime, Staking};
 use frame_support::traits::{Currency, Imbalance, OnUnbalanced};
+use pallet_reserve::{DustSource, FeesSource, InflowFrom, Instance1, TipsSource};
 use primitives::{AccountId, BlockNumber};
 use sp_runtime::traits::BlockNumberProvider;
 
 type NegativeImbalance = <Balances as Currency<AccountId>>::NegativeImbalance;
 
-/// Implementation of `OnUnbalanced` that deposits the fees into a staking pot for later payout.
-pub struct ToStakingPot;
-impl OnUnbalanced<NegativeImbalance> for ToStakingPot {
-	fn on_nonzero_unbalanced(amount: NegativeImbalance) {
-		let staking_pot = CollatorSelection::account_id();
-		Balances::resolve_creating(&staking_pot, amount);
-	}
-}
+/// Deposits the fees into the staking rewards of the ongoing session for later payout.
+pub type ToStakingPot = Staking;
+
+/// Credits the company reserve with its share of the transaction fees.
+pub type CompanyReserveFees = InflowFrom<Runtime, Instance1, FeesSource>;
+/// Credits the company reserve with its share of the transaction tips.
+pub type CompanyReserveTips = InflowFrom<Runtime, Instance1, TipsSource>;
+/// Credits the company reserve with the dust of reaped accounts.
+pub type CompanyReserveDust = InflowFrom<Runtime, Instance1, DustSource>;
 
 /// Splits fees 20/80 between reserve and block author.
 pub struct DealWithFees;
@@ -40,13 +42,15 @@ impl OnUnbalanced<NegativeImbalance> for DealWithFees {
 	fn on_unbalanceds<B>(mut fees_then_tips: impl Iterator<Item = NegativeImbalance>) {
 		if let Some(fees) = fees_then_tips.next() {
 			// for fees, 20% to treasury, 80% to author
-			let mut split = fees.ration(20, 80);
+			let (to_reserve, to_author) = fees.ration(20, 80);
+			CompanyReserveFees::on_unbalanced(to_reserve);
+			ToStakingPot::on_unbalanced(to_author);
 			if let Some(tips) = fees_then_tips.next() {
 				// for tips, if any, 20% to treasury, 80% to author (though this can be anything)
-				tips.ration_merge_into(20, 80, &mut split);
+				let (to_reserve, to_author) = tips.ration(20, 80);
+				CompanyReserveTips::on_unbalanced(to_reserve);
+				ToStakingPot::on_unbalanced(to_author);
 			}
-			CompanyReserve::on_unbalanced(split.0);
-			ToStakingPot::on_unbalanced(split.1);
 		}
 	}
 }
@@ -88,13 +88,15 @@ construct_runtime! {
 		InternationalReserve: pallet_reserve::<Instance2> = 12,
 		UsaReserve: pallet_reserve::<Instance3> = 13,
 		Vesting: pallet_grants = 14,
-		Mandate: pallet_mandate::{Pallet, Call, Storage, Event<T>} = 15,
+		Mandate: pallet_mandate::{Pallet, Call, Storage, Event<T>, Error<T>} = 15,
 		TechnicalCommittee: pallet_collective::<Instance1> = 16,
 		TechnicalMembership: pallet_membership::<Instance3> = 17,
+		TransactionPause: pallet_transaction_pause = 18,
 
 		// Consensus
-		CollatorSelection: pallet_collator_selection::{Pallet, Call, Storage, Event<T>, Config<T>} = 19,
 		Authorship: pallet_authorship = 20,
+		Staking: pallet_staking = 21,
+		Historical: pallet_session::historical::{Pallet, Storage} = 22,
 		Session: pallet_session::{Pallet, Call, Storage, Event, Config<T>} = 23,
 		Aura: pallet_aura::{Pallet, Config<T>, Storage} = 24,
 		AuraExt: cumulus_pallet_aura_ext::{Pallet, Config, Storage} = 25,
@@ -113,9 +115,10 @@ construct_runtime! {
 		Multisig: pallet_multisig = 41,
 		Uniques: pallet_uniques = 42,
 		Preimage: pallet_preimage::{Pallet, Call, Storage, Event<T>} = 43,
+		ForeignAssets: pallet_assets = 44,
 
 		// Nodle Stack
-		// EmergencyShutdown: pallet_emergency_shutdown = 50,
+		EmergencyShutdown: pallet_emergency_shutdown = 50,
 		Allocations: pallet_allocations = 51,
 		AllocationsOracles: pallet_membership::<Instance2> = 52,
 
@@ -126,6 +129,30 @@ construct_runtime! {
 		Contracts: pallet_contracts = 62,
 	}
 }
+/// Evaluates `$body` with `$reserve_pallet` bound to the reserve instance owning the account
+/// `$reserve`, `None` if no reserve owns it.
+macro_rules! for_reserve {
+	($reserve:expr, |$reserve_pallet:ident| $body:expr) => {{
+		use support::WithAccountId;
+		let reserve = $reserve;
+		if reserve == CompanyReserve::account_id() {
+			type $reserve_pallet = CompanyReserve;
+			Some($body)
+		} else if reserve == InternationalReserve::account_id() {
+			type $reserve_pallet = InternationalReserve;
+			Some($body)
+		} else if reserve == UsaReserve::account_id() {
+			type $reserve_pallet = UsaReserve;
+			Some($body)
+		} else if reserve == DaoReserve::account_id() {
+			type $reserve_pallet = DaoReserve;
+			Some($body)
+		} else {
+			None
+		}
+	}};
+}
+
 /// The address format for describing accounts.
 pub type Address = sp_runtime::MultiAddress<AccountId, ()>;
 /// Block header type as expected by this runtime.
@@ -160,7 +187,11 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
-	migrations::MoveValidatorsSetToInvulnerables,
+	(
+		pallet_staking::migrations::MigrateFromCollatorSelection<Runtime, migrations::CollatorSelectionPot>,
+		pallet_grants::migrations::MigrateToStorageVersion<Runtime>,
+		pallet_grants::migrations::MigrateToGrantSchedules<Runtime>,
+	),
 >;
 
 sp_api::impl_runtime_apis! {
@@ -331,6 +362,113 @@ sp_api::impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_grants_runtime_api::GrantsApi<Block, AccountId, Balance, pallet_grants::GrantScheduleOf<Runtime>>
+		for Runtime
+	{
+		fn vesting_schedules(who: AccountId) -> Vec<pallet_grants::GrantScheduleOf<Runtime>> {
+			Vesting::vesting_schedules(who).into_inner()
+		}
+
+		fn locked_balance(who: AccountId) -> Balance {
+			Vesting::locked_balance(&who)
+		}
+	}
+
+	impl
+		pallet_reserve_runtime_api::ReserveApi<
+			Block,
+			AccountId,
+			Balance,
+			BlockNumber,
+			pallet_reserve::CategoryReport<Balance>,
+			pallet_reserve::InflowSource,
+		> for Runtime
+	{
+		fn current_period(reserve: AccountId) -> Option<BlockNumber> {
+			for_reserve!(reserve, |Reserve| Reserve::current_period())
+		}
+
+		fn budget_report(
+			reserve: AccountId,
+			period: Option<BlockNumber>,
+		) -> Option<Vec<pallet_reserve::CategoryReport<Balance>>> {
+			for_reserve!(reserve, |Reserve| Reserve::budget_report(
+				period.unwrap_or_else(Reserve::current_period)
+			))
+		}
+
+		fn inflow_report(
+			reserve: AccountId,
+			period: Option<BlockNumber>,
+		) -> Option<Vec<(pallet_reserve::InflowSource, Balance)>> {
+			for_reserve!(reserve, |Reserve| Reserve::inflow_report(
+				period.unwrap_or_else(Reserve::current_period)
+			))
+		}
+	}
+
+	impl pallet_mandate_runtime_api::MandateApi<Block, pallet_mandate::MandateRecordOf<Runtime>> for Runtime {
+		fn recent_mandates(count: u32) -> Vec<pallet_mandate::MandateRecordOf<Runtime>> {
+			Mandate::recent_mandates(count)
+		}
+	}
+
+	impl pallet_transaction_pause_runtime_api::TransactionPauseApi<Block> for Runtime {
+		fn paused_calls() -> Vec<(Vec<u8>, Vec<u8>)> {
+			TransactionPause::paused_calls()
+		}
+	}
+
+	impl
+		pallet_staking_runtime_api::StakingApi<
+			Block,
+			AccountId,
+			Balance,
+			pallet_staking::ValidatorInfoOf<Runtime>,
+			pallet_staking::NominatorInfoOf<Runtime>,
+			pallet_staking::RewardRate,
+			pallet_staking::PendingSlashOf<Runtime>,
+		> for Runtime
+	{
+		fn selected_validators() -> Vec<AccountId> {
+			Staking::selected_validators().into_inner()
+		}
+
+		fn validator_info(validator: AccountId) -> Option<pallet_staking::ValidatorInfoOf<Runtime>> {
+			Staking::validator_info(&validator)
+		}
+
+		fn nominator_info(nominator: AccountId) -> Option<pallet_staking::NominatorInfoOf<Runtime>> {
+			Staking::nominator_info(&nominator)
+		}
+
+		fn claimable_rewards(who: AccountId) -> Balance {
+			Staking::claimable_rewards(&who)
+		}
+
+		fn unclaimed_payouts(validator: AccountId) -> Vec<(u32, Vec<u32>)> {
+			Staking::unclaimed_payouts(&validator)
+		}
+
+		fn reward_rate(validator: AccountId) -> Option<pallet_staking::RewardRate> {
+			Staking::reward_rate(&validator)
+		}
+
+		fn pending_slashes() -> Vec<pallet_staking::PendingSlashOf<Runtime>> {
+			Staking::pending_slashes()
+		}
+	}
+
+	impl pallet_staking_runtime_api::EquivocationApi<Block, AuraId> for Runtime {
+		fn generate_key_ownership_proof(authority_id: AuraId) -> Option<Vec<u8>> {
+			use codec::Encode;
+			use frame_support::traits::KeyOwnerProofSystem;
+			use sp_runtime::RuntimeAppPublic;
+
+			Historical::prove((<AuraId as RuntimeAppPublic>::ID, authority_id)).map(|proof| proof.encode())
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (
@@ -359,7 +497,10 @@ sp_api::impl_runtime_apis! {
 			list_benchmark!(list, extra, pallet_uniques, Uniques);
 			list_benchmark!(list, extra, pallet_utility, Utility);
 			list_benchmark!(list, extra, pallet_allocations, Allocations);
-			list_benchmark!(list, extra, pallet_collator_selection, CollatorSelection);
+			list_benchmark!(list, extra, pallet_emergency_shutdown, EmergencyShutdown);
+			list_benchmark!(list, extra, pallet_mandate, Mandate);
+			list_benchmark!(list, extra, pallet_transaction_pause, TransactionPause);
+			list_benchmark!(list, extra, pallet_staking, Staking);
 			list_benchmark!(list, extra, pallet_contracts, Contracts);
 			list_benchmark!(list, extra, pallet_membership, TechnicalMembership);
 
@@ -396,7 +537,10 @@ sp_api::impl_runtime_apis! {
 			add_benchmark!(params, batches, pallet_uniques, Uniques);
 			add_benchmark!(params, batches, pallet_utility, Utility);
 			add_benchmark!(params, batches, pallet_allocations, Allocations);
-			add_benchmark!(params, batches, pallet_collator_selection, CollatorSelection);
+			add_benchmark!(params, batches, pallet_emergency_shutdown, EmergencyShutdown);
+			add_benchmark!(params, batches, pallet_mandate, Mandate);
+			add_benchmark!(params, batches, pallet_transaction_pause, TransactionPause);
+			add_benchmark!(params, batches, pallet_staking, Staking);
 			add_benchmark!(params, batches, pallet_contracts, Contracts);
 			add_benchmark!(params, batches, pallet_membership, TechnicalMembership);
 
@@ -466,6 +610,21 @@ mod tests {
 		is_submit_signed_transaction::<Runtime>();
 	}
 
+	#[test]
+	fn offenders_nominators_are_slashed_within_a_block() {
+		use frame_support::{dispatch::DispatchClass, traits::Get};
+		use pallet_staking::WeightInfo;
+
+		let max_extrinsic = RuntimeBlockWeights::get()
+			.get(DispatchClass::Normal)
+			.max_extrinsic
+			.expect("normal extrinsics are bounded");
+		let report = pallet_staking::weights::SubstrateWeight::<Runtime>::report_equivocation(
+			pallets_consensus::MaxNominatorsPerValidator::get(),
+		);
+		assert!(report.all_lte(max_extrinsic));
+	}
+
 	#[test]
 	#[ignore = "failing due to preimage depency"]
 	fn check_pallet_storage_sizes() {
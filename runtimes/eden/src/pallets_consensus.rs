@@ -16,11 +16,19 @@
  * along with this program.  If not, see <http://www.gnu.org/licenses/>.
  */
 
-use crate::{constants, Aura, Balances, CollatorSelection, Runtime, RuntimeEvent, Session};
-use frame_support::{parameter_types, PalletId};
-use frame_system::EnsureRoot;
-use primitives::{AccountId, AuraId};
-use sp_runtime::impl_opaque_keys;
+use crate::{
+	constants, pallets_governance::MoreThanHalfOfTechComm, Aura, Balances, CompanyReserve, Historical, Runtime,
+	RuntimeEvent, Session, Staking, System,
+};
+use frame_support::{
+	parameter_types,
+	traits::{Get, KeyOwnerProofSystem, LockIdentifier},
+	PalletId,
+};
+use primitives::{AccountId, AuraId, Balance};
+use sp_consensus_aura::{Slot, AURA_ENGINE_ID};
+use sp_runtime::{impl_opaque_keys, KeyTypeId, Perbill};
+use sp_staking::SessionIndex;
 use sp_std::prelude::*;
 
 impl_opaque_keys! {
@@ -37,7 +45,7 @@ impl pallet_authorship::Config for Runtime {
 	type FindAuthor = pallet_session::FindAccountFromAuthorIndex<Self, Aura>;
 	type UncleGenerations = UncleGenerations;
 	type FilterUncle = ();
-	type EventHandler = (CollatorSelection,);
+	type EventHandler = (Staking,);
 }
 
 parameter_types! {
@@ -46,17 +54,22 @@ parameter_types! {
 }
 
 impl pallet_session::Config for Runtime {
-	type SessionManager = CollatorSelection;
+	type SessionManager = pallet_session::historical::NoteHistoricalRoot<Self, Staking>;
 	type ShouldEndSession = pallet_session::PeriodicSessions<Period, Offset>;
 	type NextSessionRotation = pallet_session::PeriodicSessions<Period, Offset>;
 	type RuntimeEvent = RuntimeEvent;
 	type SessionHandler = <SessionKeys as sp_runtime::traits::OpaqueKeys>::KeyTypeIdProviders;
 	type Keys = SessionKeys;
 	type ValidatorId = AccountId;
-	type ValidatorIdOf = pallet_collator_selection::IdentityCollator;
+	type ValidatorIdOf = pallet_staking::StashOf<Self>;
 	type WeightInfo = pallet_session::weights::SubstrateWeight<Runtime>;
 }
 
+impl pallet_session::historical::Config for Runtime {
+	type FullIdentification = pallet_staking::ValidatorSnapshot<Balance>;
+	type FullIdentificationOf = pallet_staking::ValidatorSnapshotOf<Runtime>;
+}
+
 parameter_types! {
 	pub const MaxAuthorities: u32 = 100_000;
 }
@@ -70,26 +83,126 @@ impl pallet_aura::Config for Runtime {
 impl cumulus_pallet_aura_ext::Config for Runtime {}
 
 parameter_types! {
-	pub const PotId: PalletId = PalletId(*b"PotStake");
-	pub const MaxCandidates: u32 = 1000;
-	pub const MinCandidates: u32 = 5;
-	pub const MaxInvulnerables: u32 = 50;
+	pub const BondedDuration: SessionIndex = 28;
+	pub const SlashDeferDuration: SessionIndex = 27;
+	pub const MinSelectedValidators: u32 = 5;
+	/// The nominators exposed with an offender are all slashed in the block reporting the
+	/// offence and kept in the deferred slashes of the session. 512 keeps `report_equivocation`
+	/// within a block and `UnappliedSlashes` under 4MB, twice as many fits neither.
+	pub const MaxNominatorsPerValidator: u32 = 512;
+	pub const MaxValidatorPerNominator: u32 = 16;
+	pub const DefaultValidatorFee: Perbill = Perbill::from_percent(20);
+	pub const DefaultMinValidatorCommission: Perbill = Perbill::from_percent(5);
+	pub const DefaultMaxValidatorCommission: Perbill = Perbill::from_percent(50);
+	pub const DefaultMaxCommissionIncrease: Perbill = Perbill::from_percent(1);
+	pub const DefaultSlashRewardProportion: Perbill = Perbill::from_percent(10);
+	pub const DefaultSlashRewardFraction: Perbill = Perbill::from_percent(50);
+	pub const DefaultStakingMaxValidators: u32 = 50;
+	pub const DefaultStakingMinStakeSessionSelection: Balance = 10 * constants::NODL;
+	pub const DefaultStakingMinValidatorBond: Balance = 10 * constants::NODL;
+	pub const DefaultStakingMinNominatorTotalBond: Balance = 10 * constants::NODL;
+	pub const DefaultStakingMinNominationChillThreshold: Balance = 3 * constants::NODL;
+	pub const MaxChunkUnlock: u32 = 32;
+	pub const MaxValidators: u32 = 100;
+	pub const MaxStakeRewards: u32 = 64;
+	pub const MaxNominatorRewardedPerPage: u32 = 64;
+	pub const MaxSlashReporters: u32 = 16;
+	pub const MaxSlashingSpans: u32 = 32;
+	pub const PoorPerformanceThreshold: Perbill = Perbill::from_percent(50);
+	pub const MaxPoorSessions: u32 = 3;
+	pub const PoorPerformanceSlash: Perbill = Perbill::from_perthousand(1);
+	pub const EquivocationSlash: Perbill = Perbill::from_percent(5);
+	pub const StakingPalletId: PalletId = PalletId(*b"py/stake");
+	pub const StakingLockId: LockIdentifier = *b"staking ";
+}
+
+/// Aura slot of the block being built. Read from the digest since `Session` rotates before
+/// `Aura` stores the slot of the block.
+pub struct AuraSlot;
+impl Get<Slot> for AuraSlot {
+	fn get() -> Slot {
+		System::digest()
+			.logs()
+			.iter()
+			.find_map(|item| item.pre_runtime_try_to::<Slot>(&AURA_ENGINE_ID))
+			.unwrap_or_else(Aura::current_slot)
+	}
 }
 
-// We allow root only to execute privileged collator selection operations.
-pub type CollatorSelectionUpdateOrigin = EnsureRoot<AccountId>;
+/// Sets the session keys of the validators reported in the benchmarks
+#[cfg(feature = "runtime-benchmarks")]
+pub struct StakingBenchmarkHelper;
+#[cfg(feature = "runtime-benchmarks")]
+impl
+	pallet_staking::benchmarking::BenchmarkHelper<
+		AccountId,
+		AuraId,
+		<Historical as KeyOwnerProofSystem<(KeyTypeId, AuraId)>>::Proof,
+	> for StakingBenchmarkHelper
+{
+	fn key_owner_proof(
+		validator: &AccountId,
+		authority_id: AuraId,
+	) -> <Historical as KeyOwnerProofSystem<(KeyTypeId, AuraId)>>::Proof {
+		use crate::RuntimeOrigin;
+		use sp_runtime::RuntimeAppPublic;
 
-impl pallet_collator_selection::Config for Runtime {
+		Session::set_keys(
+			RuntimeOrigin::signed(validator.clone()),
+			SessionKeys {
+				aura: authority_id.clone(),
+			},
+			Vec::new(),
+		)
+		.expect("the validator joined the pool");
+		// the keys are queued by the first rotation and used from the second one
+		Session::rotate_session();
+		Session::rotate_session();
+		Historical::prove((<AuraId as RuntimeAppPublic>::ID, authority_id)).expect("the validator is selected")
+	}
+}
+
+impl pallet_staking::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Currency = Balances;
-	type UpdateOrigin = CollatorSelectionUpdateOrigin;
-	type PotId = PotId;
-	type MaxCandidates = MaxCandidates;
-	type MinCandidates = MinCandidates;
-	type MaxInvulnerables = MaxInvulnerables;
-	type KickThreshold = Period;
-	type ValidatorId = AccountId;
-	type ValidatorIdOf = pallet_collator_selection::IdentityCollator;
+	type BondedDuration = BondedDuration;
+	type MinSelectedValidators = MinSelectedValidators;
+	type MaxNominatorsPerValidator = MaxNominatorsPerValidator;
+	type MaxValidatorPerNominator = MaxValidatorPerNominator;
+	type DefaultValidatorFee = DefaultValidatorFee;
+	type DefaultMinValidatorCommission = DefaultMinValidatorCommission;
+	type DefaultMaxValidatorCommission = DefaultMaxValidatorCommission;
+	type DefaultMaxCommissionIncrease = DefaultMaxCommissionIncrease;
+	type DefaultSlashRewardProportion = DefaultSlashRewardProportion;
+	type DefaultSlashRewardFraction = DefaultSlashRewardFraction;
+	type DefaultStakingMaxValidators = DefaultStakingMaxValidators;
+	type DefaultStakingMinStakeSessionSelection = DefaultStakingMinStakeSessionSelection;
+	type DefaultStakingMinValidatorBond = DefaultStakingMinValidatorBond;
+	type DefaultStakingMinNominatorTotalBond = DefaultStakingMinNominatorTotalBond;
+	type DefaultStakingMinNominationChillThreshold = DefaultStakingMinNominationChillThreshold;
+	type RewardRemainder = CompanyReserve;
+	type MaxChunkUnlock = MaxChunkUnlock;
+	type MaxValidators = MaxValidators;
+	type MaxStakeRewards = MaxStakeRewards;
+	type MaxNominatorRewardedPerPage = MaxNominatorRewardedPerPage;
+	type MaxSlashReporters = MaxSlashReporters;
+	type MaxSlashingSpans = MaxSlashingSpans;
+	type CurrentSlot = AuraSlot;
+	type PoorPerformanceThreshold = PoorPerformanceThreshold;
+	type MaxPoorSessions = MaxPoorSessions;
+	type PoorPerformanceSlash = PoorPerformanceSlash;
+	type AuthorityId = AuraId;
+	type KeyOwnerProof = <Historical as KeyOwnerProofSystem<(KeyTypeId, AuraId)>>::Proof;
+	type KeyOwnerProofSystem = Historical;
+	type EquivocationSlash = EquivocationSlash;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = StakingBenchmarkHelper;
+	type PalletId = StakingPalletId;
+	type StakingLockId = StakingLockId;
+	type Slash = CompanyReserve;
+	type SlashDeferDuration = SlashDeferDuration;
+	type SessionInterface = Self;
 	type ValidatorRegistration = Session;
-	type WeightInfo = crate::weights::pallet_collator_selection::WeightInfo<Runtime>;
+	type CancelOrigin = MoreThanHalfOfTechComm;
+	type WeightInfo = pallet_staking::weights::SubstrateWeight<Runtime>;
 }
@@ -16,11 +16,40 @@
  * along with this program.  If not, see <http://www.gnu.org/licenses/>.
  */
 
-use crate::{constants, Runtime, RuntimeCall, RuntimeEvent, RuntimeOrigin, TechnicalCommittee};
-use frame_support::{parameter_types, traits::EitherOfDiverse, PalletId};
+use crate::{
+	constants,
+	xcm_config::{AccountIdToMultiLocation, NodlLocation, RelayLocation, XcmConfig, XcmLocationInverter, XcmWeigher},
+	EmergencyShutdown, ForeignAssets, OriginCaller, Runtime, RuntimeCall, RuntimeEvent, RuntimeOrigin,
+	TechnicalCommittee,
+};
+use frame_support::{
+	parameter_types,
+	traits::{Contains, EitherOfDiverse, Everything},
+	PalletId,
+};
 use frame_system::{EnsureNever, EnsureRoot};
 use primitives::{AccountId, BlockNumber};
 pub use sp_runtime::{Perbill, Perquintill};
+use xcm_executor::XcmExecutor;
+
+parameter_types! {
+	pub const ReserveSpendPeriod: BlockNumber = 30 * constants::DAYS;
+	pub const ReserveSpendDelay: BlockNumber = 2 * constants::DAYS;
+	pub const ReserveMaxStreamsPerBlock: u32 = 16;
+	pub const ReserveStreamRetryDelay: BlockNumber = constants::HOURS;
+}
+
+/// Calls the USA and International reserves may dispatch through `apply_as`, they only need to
+/// move funds around.
+pub struct RestrictedReserveCalls;
+impl Contains<RuntimeCall> for RestrictedReserveCalls {
+	fn contains(call: &RuntimeCall) -> bool {
+		matches!(
+			call,
+			RuntimeCall::Balances(_) | RuntimeCall::Vesting(_) | RuntimeCall::Utility(_)
+		)
+	}
+}
 
 parameter_types! {
 	pub const CompanyReservePalletId: PalletId = PalletId(*b"py/resrv"); // 5EYCAe5ijiYfha9GzQDgPVtUCYDY9B8ZgcyiANL2L34crMoR
@@ -29,9 +58,24 @@ parameter_types! {
 impl pallet_reserve::Config<pallet_reserve::Instance1> for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Currency = pallet_balances::Pallet<Runtime>;
+	type Assets = ForeignAssets;
 	type ExternalOrigin = MoreThanHalfOfTechComm;
 	type RuntimeCall = RuntimeCall;
+	type CallFilter = Everything;
 	type PalletId = CompanyReservePalletId;
+	type SpendPeriod = ReserveSpendPeriod;
+	type SpendDelay = ReserveSpendDelay;
+	type VetoOrigin = AtLeastAThirdOfTechComm;
+	type MaxStreamsPerBlock = ReserveMaxStreamsPerBlock;
+	type StreamRetryDelay = ReserveStreamRetryDelay;
+	type XcmExecutor = XcmExecutor<XcmConfig>;
+	type Weigher = XcmWeigher;
+	type Trader = <XcmConfig as xcm_executor::Config>::Trader;
+	type NativeAssetLocation = NodlLocation;
+	type FeeAssetLocation = RelayLocation;
+	type LocationInverter = XcmLocationInverter;
+	type AccountIdToMultiLocation = AccountIdToMultiLocation;
+	type EmergencyShutdown = EmergencyShutdown;
 	type WeightInfo = pallet_reserve::weights::SubstrateWeight<Runtime>;
 }
 
@@ -42,9 +86,24 @@ parameter_types! {
 impl pallet_reserve::Config<pallet_reserve::Instance2> for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Currency = pallet_balances::Pallet<Runtime>;
+	type Assets = ForeignAssets;
 	type ExternalOrigin = MoreThanHalfOfTechComm;
 	type RuntimeCall = RuntimeCall;
+	type CallFilter = RestrictedReserveCalls;
 	type PalletId = InternationalReservePalletId;
+	type SpendPeriod = ReserveSpendPeriod;
+	type SpendDelay = ReserveSpendDelay;
+	type VetoOrigin = AtLeastAThirdOfTechComm;
+	type MaxStreamsPerBlock = ReserveMaxStreamsPerBlock;
+	type StreamRetryDelay = ReserveStreamRetryDelay;
+	type XcmExecutor = XcmExecutor<XcmConfig>;
+	type Weigher = XcmWeigher;
+	type Trader = <XcmConfig as xcm_executor::Config>::Trader;
+	type NativeAssetLocation = NodlLocation;
+	type FeeAssetLocation = RelayLocation;
+	type LocationInverter = XcmLocationInverter;
+	type AccountIdToMultiLocation = AccountIdToMultiLocation;
+	type EmergencyShutdown = EmergencyShutdown;
 	type WeightInfo = pallet_reserve::weights::SubstrateWeight<Runtime>;
 }
 
@@ -55,9 +114,24 @@ parameter_types! {
 impl pallet_reserve::Config<pallet_reserve::Instance3> for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Currency = pallet_balances::Pallet<Runtime>;
+	type Assets = ForeignAssets;
 	type ExternalOrigin = MoreThanHalfOfTechComm;
 	type RuntimeCall = RuntimeCall;
+	type CallFilter = RestrictedReserveCalls;
 	type PalletId = UsaReservePalletId;
+	type SpendPeriod = ReserveSpendPeriod;
+	type SpendDelay = ReserveSpendDelay;
+	type VetoOrigin = AtLeastAThirdOfTechComm;
+	type MaxStreamsPerBlock = ReserveMaxStreamsPerBlock;
+	type StreamRetryDelay = ReserveStreamRetryDelay;
+	type XcmExecutor = XcmExecutor<XcmConfig>;
+	type Weigher = XcmWeigher;
+	type Trader = <XcmConfig as xcm_executor::Config>::Trader;
+	type NativeAssetLocation = NodlLocation;
+	type FeeAssetLocation = RelayLocation;
+	type LocationInverter = XcmLocationInverter;
+	type AccountIdToMultiLocation = AccountIdToMultiLocation;
+	type EmergencyShutdown = EmergencyShutdown;
 	type WeightInfo = pallet_reserve::weights::SubstrateWeight<Runtime>;
 }
 
@@ -68,11 +142,26 @@ parameter_types! {
 impl pallet_reserve::Config<pallet_reserve::Instance4> for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Currency = pallet_balances::Pallet<Runtime>;
+	type Assets = ForeignAssets;
 	// as of now nobody can spend this, later, we need to map this to the
 	// correct governance origin.
 	type ExternalOrigin = EnsureNever<AccountId>;
 	type RuntimeCall = RuntimeCall;
+	type CallFilter = Everything;
 	type PalletId = DaoReservePalletId;
+	type SpendPeriod = ReserveSpendPeriod;
+	type SpendDelay = ReserveSpendDelay;
+	type VetoOrigin = AtLeastAThirdOfTechComm;
+	type MaxStreamsPerBlock = ReserveMaxStreamsPerBlock;
+	type StreamRetryDelay = ReserveStreamRetryDelay;
+	type XcmExecutor = XcmExecutor<XcmConfig>;
+	type Weigher = XcmWeigher;
+	type Trader = <XcmConfig as xcm_executor::Config>::Trader;
+	type NativeAssetLocation = NodlLocation;
+	type FeeAssetLocation = RelayLocation;
+	type LocationInverter = XcmLocationInverter;
+	type AccountIdToMultiLocation = AccountIdToMultiLocation;
+	type EmergencyShutdown = EmergencyShutdown;
 	type WeightInfo = pallet_reserve::weights::SubstrateWeight<Runtime>;
 }
 
@@ -84,6 +173,8 @@ parameter_types! {
 
 pub type MoreThanHalfOfTechComm =
 	pallet_collective::EnsureProportionMoreThan<AccountId, pallet_collective::Instance1, 1, 2>;
+pub type AtLeastAThirdOfTechComm =
+	pallet_collective::EnsureProportionAtLeast<AccountId, pallet_collective::Instance1, 1, 3>;
 pub type EnsureRootOrMoreThanHalfOfTechComm = EitherOfDiverse<EnsureRoot<AccountId>, MoreThanHalfOfTechComm>;
 impl pallet_collective::Config<pallet_collective::Instance1> for Runtime {
 	type RuntimeOrigin = RuntimeOrigin;
@@ -109,8 +200,51 @@ impl pallet_membership::Config<pallet_membership::Instance3> for Runtime {
 	type WeightInfo = crate::weights::pallet_membership::WeightInfo<Runtime>;
 }
 
+parameter_types! {
+	pub const MandateDelay: BlockNumber = 2 * constants::DAYS;
+	pub const MandateMaxHistoryLength: u32 = 256;
+}
+
+/// Calls that must stay available to govern and recover the chain, or to keep collators
+/// producing blocks and XCM flowing, they can never be paused.
+pub struct UnpausableCalls;
+impl Contains<pallet_transaction_pause::FullName> for UnpausableCalls {
+	fn contains((pallet_name, _call_name): &pallet_transaction_pause::FullName) -> bool {
+		matches!(
+			pallet_name.as_slice(),
+			b"System"
+				| b"Timestamp"
+				| b"ParachainSystem"
+				| b"Scheduler"
+				| b"Mandate" | b"TechnicalCommittee"
+				| b"TechnicalMembership"
+				| b"TransactionPause"
+				| b"Preimage"
+				| b"EmergencyShutdown"
+				| b"Authorship"
+				| b"Session" | b"PolkadotXcm"
+				| b"XcmpQueue"
+				| b"DmpQueue"
+		)
+	}
+}
+
+impl pallet_transaction_pause::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type PauseOrigin = MoreThanHalfOfTechComm;
+	type ExemptCalls = UnpausableCalls;
+	type WeightInfo = pallet_transaction_pause::weights::SubstrateWeight<Runtime>;
+}
+
 impl pallet_mandate::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type RuntimeCall = RuntimeCall;
+	type PalletsOrigin = OriginCaller;
 	type ExternalOrigin = MoreThanHalfOfTechComm;
+	type VetoOrigin = AtLeastAThirdOfTechComm;
+	// Allow-listing goes through a delayed mandate itself
+	type AllowListOrigin = EnsureRoot<AccountId>;
+	type MandateDelay = MandateDelay;
+	type MaxHistoryLength = MandateMaxHistoryLength;
+	type WeightInfo = pallet_mandate::weights::SubstrateWeight<Runtime>;
 }
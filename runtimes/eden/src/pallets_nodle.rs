@@ -17,7 +17,7 @@
  */
 use crate::{
 	constants, implementations::RelayChainBlockNumberProvider, pallets_governance::MoreThanHalfOfTechComm,
-	AllocationsOracles, Balances, CompanyReserve, Runtime, RuntimeEvent,
+	AllocationsOracles, Balances, CompanyReserve, EmergencyShutdown, Runtime, RuntimeEvent,
 };
 use frame_support::{parameter_types, PalletId};
 use lazy_static::lazy_static;
@@ -97,9 +97,16 @@ impl pallet_allocations::Config for Runtime {
 	type MaxAllocs = MaxAllocs;
 	type OracleMembers = AllocationsOracles;
 	type BlockNumberProvider = RelayChainBlockNumberProvider<Runtime>;
+	type EmergencyShutdown = EmergencyShutdown;
 	type WeightInfo = pallet_allocations::weights::SubstrateWeight<Runtime>;
 }
 
+impl pallet_emergency_shutdown::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type ShutdownOrigin = MoreThanHalfOfTechComm;
+	type WeightInfo = pallet_emergency_shutdown::weights::SubstrateWeight<Runtime>;
+}
+
 parameter_types! {
 	pub const MaxMembers: u32 = 50;
 }
@@ -19,13 +19,16 @@
 #![allow(clippy::identity_op)]
 
 use crate::{
-	constants, implementations::DealWithFees, version::VERSION, Balances, CompanyReserve, PalletInfo, Runtime,
-	RuntimeCall, RuntimeEvent, RuntimeOrigin, SignedExtra, SignedPayload, System, UncheckedExtrinsic,
+	constants,
+	implementations::{CompanyReserveDust, DealWithFees},
+	version::VERSION,
+	Balances, EmergencyShutdown, PalletInfo, Runtime, RuntimeCall, RuntimeEvent, RuntimeOrigin, SignedExtra,
+	SignedPayload, System, TransactionPause, UncheckedExtrinsic,
 };
 use codec::Encode;
 use frame_support::{
 	parameter_types,
-	traits::Everything,
+	traits::Contains,
 	weights::{constants::RocksDbWeight, ConstantMultiplier, IdentityFee},
 };
 use frame_system::limits::BlockLength;
@@ -38,6 +41,7 @@ use sp_runtime::{
 	FixedPointNumber, Perquintill,
 };
 use sp_version::RuntimeVersion;
+use support::IsShutdown;
 
 parameter_types! {
 	pub const BlockHashCount: BlockNumber = 2400;
@@ -47,8 +51,38 @@ parameter_types! {
 	pub const SS58Prefix: u8 = 37;
 }
 
+/// Filters out the calls paused by governance and, during an emergency shutdown, the calls moving
+/// funds around between users. Governance and XCM-critical calls stay available.
+pub struct BaseFilter;
+impl Contains<RuntimeCall> for BaseFilter {
+	fn contains(call: &RuntimeCall) -> bool {
+		if !TransactionPause::contains(call) {
+			return false;
+		}
+		if !EmergencyShutdown::is_shutdown() {
+			return true;
+		}
+
+		!matches!(
+			call,
+			RuntimeCall::Balances(
+				pallet_balances::Call::transfer { .. }
+					| pallet_balances::Call::transfer_keep_alive { .. }
+					| pallet_balances::Call::transfer_all { .. }
+			) | RuntimeCall::ForeignAssets(
+				pallet_assets::Call::transfer { .. }
+					| pallet_assets::Call::transfer_keep_alive { .. }
+					| pallet_assets::Call::approve_transfer { .. }
+					| pallet_assets::Call::transfer_approved { .. }
+			) | RuntimeCall::Uniques(pallet_uniques::Call::transfer { .. })
+				| RuntimeCall::Allocations(_)
+				| RuntimeCall::Contracts(_)
+		)
+	}
+}
+
 impl frame_system::Config for Runtime {
-	type BaseCallFilter = Everything;
+	type BaseCallFilter = BaseFilter;
 	type BlockWeights = constants::RuntimeBlockWeights;
 	type BlockLength = RuntimeBlockLength;
 	type DbWeight = RocksDbWeight;
@@ -96,7 +130,7 @@ impl pallet_balances::Config for Runtime {
 	type MaxReserves = ();
 	type ReserveIdentifier = [u8; 8];
 	type Balance = Balance;
-	type DustRemoval = CompanyReserve;
+	type DustRemoval = CompanyReserveDust;
 	type RuntimeEvent = RuntimeEvent;
 	type ExistentialDeposit = ExistentialDeposit;
 	type AccountStore = frame_system::Pallet<Runtime>;
@@ -19,14 +19,15 @@
 
 use crate::{
 	constants, implementations::RelayChainBlockNumberProvider, pallets_governance::MoreThanHalfOfTechComm, Balances,
-	OriginCaller, Preimage, RandomnessCollectiveFlip, Runtime, RuntimeCall, RuntimeEvent, RuntimeOrigin, Timestamp,
+	EmergencyShutdown, OriginCaller, Preimage, RandomnessCollectiveFlip, Runtime, RuntimeCall, RuntimeEvent,
+	RuntimeOrigin, Timestamp,
 };
 use frame_support::{
 	parameter_types,
 	traits::{AsEnsureOriginWithArg, ConstBool, ConstU32, EqualPrivilegeOnly, Nothing},
 	weights::Weight,
 };
-use frame_system::{EnsureRoot, EnsureSigned};
+use frame_system::{EnsureNever, EnsureRoot, EnsureSigned};
 use pallet_contracts::{weights::WeightInfo, Frame, Schedule};
 
 use primitives::{AccountId, Balance};
@@ -34,6 +35,7 @@ use sp_runtime::Perbill;
 
 parameter_types! {
 	pub const MaxSchedule: u32 = 100;
+	pub const MaxBreakpoints: u32 = 16;
 }
 
 impl pallet_grants::Config for Runtime {
@@ -41,8 +43,10 @@ impl pallet_grants::Config for Runtime {
 	type Currency = Balances;
 	type CancelOrigin = MoreThanHalfOfTechComm;
 	type MaxSchedule = MaxSchedule;
+	type MaxBreakpoints = MaxBreakpoints;
 	type WeightInfo = pallet_grants::weights::SubstrateWeight<Runtime>;
 	type BlockNumberProvider = RelayChainBlockNumberProvider<Runtime>;
+	type EmergencyShutdown = EmergencyShutdown;
 }
 
 impl pallet_utility::Config for Runtime {
@@ -137,6 +141,36 @@ impl pallet_uniques::Config for Runtime {
 	type Locker = ();
 }
 
+parameter_types! {
+	pub const ForeignAssetDeposit: Balance = 100 * constants::NODL;
+	pub const ForeignAssetAccountDeposit: Balance = constants::deposit(1, 16);
+	pub const ForeignAssetApprovalDeposit: Balance = 1 * constants::NODL;
+}
+
+/// Assets reaching us over XCM, such as DOT or the stablecoins of Asset Hub. Those are only ever
+/// registered by governance, see `xcm_config::ForeignAssetIdConvert` for their ids.
+impl pallet_assets::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Balance = Balance;
+	type AssetId = u32;
+	type AssetIdParameter = codec::Compact<u32>;
+	type Currency = Balances;
+	type CreateOrigin = AsEnsureOriginWithArg<EnsureNever<AccountId>>;
+	type ForceOrigin = EnsureRoot<AccountId>;
+	type AssetDeposit = ForeignAssetDeposit;
+	type AssetAccountDeposit = ForeignAssetAccountDeposit;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type ApprovalDeposit = ForeignAssetApprovalDeposit;
+	type StringLimit = StringLimit;
+	type Freezer = ();
+	type Extra = ();
+	type RemoveItemsLimit = ConstU32<1000>;
+	type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
+}
+
 parameter_types! {
 	pub const DepositPerItem: Balance = constants::deposit(1, 0);
 	pub const DepositPerByte: Balance = constants::deposit(0, 1);
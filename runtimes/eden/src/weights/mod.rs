@@ -1,6 +1,5 @@
 pub mod frame_system;
 pub mod pallet_balances;
-pub mod pallet_collator_selection;
 pub mod pallet_contracts;
 pub mod pallet_membership;
 pub mod pallet_multisig;
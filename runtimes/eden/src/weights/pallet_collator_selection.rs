@@ -1,156 +0,0 @@
-/*
- * This file is part of the Nodle Chain distributed at https://github.com/NodleCode/chain
- * Copyright (C) 2020-2022  Nodle International
- *
- * This program is free software: you can redistribute it and/or modify
- * it under the terms of the GNU General Public License as published by
- * the Free Software Foundation, either version 3 of the License, or
- * (at your option) any later version.
- *
- * This program is distributed in the hope that it will be useful,
- * but WITHOUT ANY WARRANTY; without even the implied warranty of
- * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
- * GNU General Public License for more details.
- *
- * You should have received a copy of the GNU General Public License
- * along with this program.  If not, see <http://www.gnu.org/licenses/>.
- */
-
-//! Autogenerated weights for pallet_collator_selection
-//!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
-//! DATE: 2023-02-16, STEPS: `50`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
-//! HOSTNAME: `chain-bench-012bd056`, CPU: `AMD EPYC 7B13`
-//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
-
-// Executed Command:
-// ./target/release/nodle-parachain
-// benchmark
-// pallet
-// --chain=dev
-// --steps=50
-// --repeat=20
-// --pallet=pallet_collator_selection
-// --extrinsic=*
-// --execution=wasm
-// --wasm-execution=compiled
-// --template=./.maintain/external_pallet_weights.hbs
-// --output=runtimes/eden/src/weights
-
-#![cfg_attr(rustfmt, rustfmt_skip)]
-#![allow(unused_parens)]
-#![allow(unused_imports)]
-
-use frame_support::{traits::Get, weights::{Weight}};
-use sp_std::marker::PhantomData;
-
-/// Weight functions for `pallet_collator_selection`.
-pub struct WeightInfo<T>(PhantomData<T>);
-impl<T: frame_system::Config> pallet_collator_selection::WeightInfo for WeightInfo<T> {
-	// Storage: Session NextKeys (r:1 w:0)
-	// Storage: System Number (r:1 w:0)
-	// Storage: System ExecutionPhase (r:1 w:0)
-	// Storage: System EventCount (r:1 w:1)
-	// Storage: System Events (r:1 w:1)
-	// Storage: CollatorSelection Invulnerables (r:0 w:1)
-	/// The range of component `b` is `[1, 50]`.
-	fn set_invulnerables(b: u32, ) -> Weight {
-		// Minimum execution time: 27_500 nanoseconds.
-		Weight::from_ref_time(27_690_752_u64)
-			// Standard Error: 6_224
-			.saturating_add(Weight::from_ref_time(3_646_206_u64).saturating_mul(b as u64))
-			.saturating_add(T::DbWeight::get().reads(4_u64))
-			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(b as u64)))
-			.saturating_add(T::DbWeight::get().writes(3_u64))
-	}
-	// Storage: System Number (r:1 w:0)
-	// Storage: System ExecutionPhase (r:1 w:0)
-	// Storage: System EventCount (r:1 w:1)
-	// Storage: System Events (r:1 w:1)
-	// Storage: CollatorSelection DesiredCandidates (r:0 w:1)
-	fn set_desired_candidates() -> Weight {
-		// Minimum execution time: 17_980 nanoseconds.
-		Weight::from_ref_time(18_519_000_u64)
-			.saturating_add(T::DbWeight::get().reads(4_u64))
-			.saturating_add(T::DbWeight::get().writes(3_u64))
-	}
-	// Storage: System Number (r:1 w:0)
-	// Storage: System ExecutionPhase (r:1 w:0)
-	// Storage: System EventCount (r:1 w:1)
-	// Storage: System Events (r:1 w:1)
-	// Storage: CollatorSelection CandidacyBond (r:0 w:1)
-	fn set_candidacy_bond() -> Weight {
-		// Minimum execution time: 18_310 nanoseconds.
-		Weight::from_ref_time(18_690_000_u64)
-			.saturating_add(T::DbWeight::get().reads(4_u64))
-			.saturating_add(T::DbWeight::get().writes(3_u64))
-	}
-	// Storage: CollatorSelection Candidates (r:1 w:1)
-	// Storage: CollatorSelection DesiredCandidates (r:1 w:0)
-	// Storage: CollatorSelection Invulnerables (r:1 w:0)
-	// Storage: Session NextKeys (r:1 w:0)
-	// Storage: CollatorSelection CandidacyBond (r:1 w:0)
-	// Storage: System Number (r:1 w:0)
-	// Storage: System ExecutionPhase (r:1 w:0)
-	// Storage: System EventCount (r:1 w:1)
-	// Storage: System Events (r:1 w:1)
-	// Storage: CollatorSelection LastAuthoredBlock (r:0 w:1)
-	/// The range of component `c` is `[1, 999]`.
-	fn register_as_candidate(c: u32, ) -> Weight {
-		// Minimum execution time: 63_740 nanoseconds.
-		Weight::from_ref_time(70_915_519_u64)
-			// Standard Error: 1_045
-			.saturating_add(Weight::from_ref_time(98_293_u64).saturating_mul(c as u64))
-			.saturating_add(T::DbWeight::get().reads(9_u64))
-			.saturating_add(T::DbWeight::get().writes(4_u64))
-	}
-	// Storage: CollatorSelection Candidates (r:1 w:1)
-	// Storage: System Number (r:1 w:0)
-	// Storage: System ExecutionPhase (r:1 w:0)
-	// Storage: System EventCount (r:1 w:1)
-	// Storage: System Events (r:1 w:1)
-	// Storage: CollatorSelection LastAuthoredBlock (r:0 w:1)
-	/// The range of component `c` is `[6, 1000]`.
-	fn leave_intent(c: u32, ) -> Weight {
-		// Minimum execution time: 48_830 nanoseconds.
-		Weight::from_ref_time(50_443_394_u64)
-			// Standard Error: 1_094
-			.saturating_add(Weight::from_ref_time(98_703_u64).saturating_mul(c as u64))
-			.saturating_add(T::DbWeight::get().reads(5_u64))
-			.saturating_add(T::DbWeight::get().writes(4_u64))
-	}
-	// Storage: System Account (r:2 w:2)
-	// Storage: System Number (r:1 w:0)
-	// Storage: System ExecutionPhase (r:1 w:0)
-	// Storage: System EventCount (r:1 w:1)
-	// Storage: System Events (r:1 w:1)
-	// Storage: System BlockWeight (r:1 w:1)
-	// Storage: CollatorSelection LastAuthoredBlock (r:0 w:1)
-	fn note_author() -> Weight {
-		// Minimum execution time: 47_750 nanoseconds.
-		Weight::from_ref_time(49_210_000_u64)
-			.saturating_add(T::DbWeight::get().reads(7_u64))
-			.saturating_add(T::DbWeight::get().writes(6_u64))
-	}
-	// Storage: System Number (r:1 w:0)
-	// Storage: CollatorSelection Candidates (r:1 w:1)
-	// Storage: CollatorSelection LastAuthoredBlock (r:1000 w:1)
-	// Storage: System Account (r:1 w:1)
-	// Storage: System ExecutionPhase (r:1 w:0)
-	// Storage: System EventCount (r:1 w:1)
-	// Storage: System Events (r:1 w:1)
-	// Storage: CollatorSelection Invulnerables (r:1 w:0)
-	// Storage: System BlockWeight (r:1 w:1)
-	/// The range of component `r` is `[1, 1000]`.
-	/// The range of component `c` is `[1, 1000]`.
-	fn new_session(_r: u32, c: u32, ) -> Weight {
-		// Minimum execution time: 28_870 nanoseconds.
-		Weight::from_ref_time(29_680_000_u64)
-			// Standard Error: 929_982
-			.saturating_add(Weight::from_ref_time(32_997_978_u64).saturating_mul(c as u64))
-			.saturating_add(T::DbWeight::get().reads(5_u64))
-			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(c as u64)))
-			.saturating_add(T::DbWeight::get().writes(1_u64))
-			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(c as u64)))
-	}
-}
@@ -1,6 +1,6 @@
 use super::{
-	AccountId, Balance, Balances, ParachainInfo, ParachainSystem, PolkadotXcm, Runtime, RuntimeCall, RuntimeEvent,
-	RuntimeOrigin, XcmpQueue,
+	AccountId, Balance, Balances, ForeignAssets, ParachainInfo, ParachainSystem, PolkadotXcm, Runtime, RuntimeCall,
+	RuntimeEvent, RuntimeOrigin, XcmpQueue,
 };
 use crate::implementations::DealWithFees;
 use frame_support::{
@@ -11,14 +11,19 @@ use frame_support::{
 use frame_system::EnsureRoot;
 use pallet_xcm::XcmPassthrough;
 use polkadot_parachain::primitives::Sibling;
+use sp_std::borrow::Borrow;
 use xcm::{latest::NetworkId, prelude::*};
 use xcm_builder::{
 	AccountId32Aliases, AllowKnownQueryResponses, AllowSubscriptionsFrom, AllowTopLevelPaidExecutionFrom,
-	CurrencyAdapter, EnsureXcmOrigin, FixedWeightBounds, IsConcrete, LocationInverter, NativeAsset, ParentIsPreset,
-	RelayChainAsNative, SiblingParachainAsNative, SiblingParachainConvertsVia, SignedAccountId32AsNative,
-	SignedToAccountId32, SovereignSignedViaLocation, TakeWeightCredit, UsingComponents,
+	ConvertedConcreteAssetId, CurrencyAdapter, EnsureXcmOrigin, FixedWeightBounds, FungiblesAdapter, IsConcrete,
+	LocationInverter, NativeAsset, ParentIsPreset, RelayChainAsNative, SiblingParachainAsNative,
+	SiblingParachainConvertsVia, SignedAccountId32AsNative, SignedToAccountId32, SovereignSignedViaLocation,
+	TakeWeightCredit, UsingComponents,
+};
+use xcm_executor::{
+	traits::{Convert, FilterAssetLocation, JustTry},
+	XcmExecutor,
 };
-use xcm_executor::XcmExecutor;
 
 /// Type for specifying how a `MultiLocation` can be converted into an `AccountId`. This is used
 /// when determining ownership of accounts for asset transacting and when attempting to use XCM
@@ -81,7 +86,7 @@ pub type XcmOriginToTransactDispatchOrigin = (
 	XcmPassthrough<RuntimeOrigin>,
 );
 /// Means for transacting assets on this chain.
-pub type AssetTransactors = CurrencyTransactor;
+pub type AssetTransactors = (CurrencyTransactor, ForeignAssetsTransactor);
 
 /// Means for transacting the native currency on this chain.
 pub type CurrencyTransactor = CurrencyAdapter<
@@ -96,23 +101,114 @@ pub type CurrencyTransactor = CurrencyAdapter<
 	// We don't track any teleports of `Balances`.
 	(),
 >;
+parameter_types! {
+	pub const AssetHubParaId: u32 = 1000;
+	pub const AssetHubAssetsPalletIndex: u8 = 50;
+	/// The id under which the relay chain token is registered in `ForeignAssets`.
+	pub const RelayAssetId: u32 = u32::MAX;
+	pub CheckingAccount: AccountId = PolkadotXcm::check_account();
+}
+
+/// Maps the locations of the foreign assets we accept to their id in `ForeignAssets`. The relay
+/// chain token uses `RelayAssetId` while the assets of Asset Hub keep their own index.
+pub struct ForeignAssetIdConvert;
+impl Convert<MultiLocation, u32> for ForeignAssetIdConvert {
+	fn convert_ref(location: impl Borrow<MultiLocation>) -> Result<u32, ()> {
+		match location.borrow() {
+			MultiLocation {
+				parents: 1,
+				interior: Here,
+			} => Ok(RelayAssetId::get()),
+			MultiLocation {
+				parents: 1,
+				interior: X3(Parachain(para_id), PalletInstance(pallet_index), GeneralIndex(index)),
+			} if *para_id == AssetHubParaId::get() && *pallet_index == AssetHubAssetsPalletIndex::get() => u32::try_from(*index)
+				.ok()
+				.filter(|id| *id != RelayAssetId::get())
+				.ok_or(()),
+			_ => Err(()),
+		}
+	}
+
+	fn reverse_ref(id: impl Borrow<u32>) -> Result<MultiLocation, ()> {
+		let id = *id.borrow();
+		if id == RelayAssetId::get() {
+			Ok(MultiLocation::parent())
+		} else {
+			Ok(MultiLocation::new(
+				1,
+				X3(
+					Parachain(AssetHubParaId::get()),
+					PalletInstance(AssetHubAssetsPalletIndex::get()),
+					GeneralIndex(id.into()),
+				),
+			))
+		}
+	}
+}
+
+/// Accepts Asset Hub as the reserve of the assets it issues.
+pub struct AssetHubAssets;
+impl FilterAssetLocation for AssetHubAssets {
+	fn filter_asset_location(asset: &MultiAsset, origin: &MultiLocation) -> bool {
+		let asset_hub = MultiLocation::new(1, X1(Parachain(AssetHubParaId::get())));
+		match &asset.id {
+			Concrete(location) => origin == &asset_hub && location.starts_with(&asset_hub),
+			_ => false,
+		}
+	}
+}
+
+/// Means for transacting the foreign assets held in `ForeignAssets`.
+pub type ForeignAssetsTransactor = FungiblesAdapter<
+	// Use this fungibles implementation:
+	ForeignAssets,
+	// Use this currency when it is a fungible asset matching the given location or name:
+	ConvertedConcreteAssetId<u32, Balance, ForeignAssetIdConvert, JustTry>,
+	// Convert an XCM MultiLocation into a local account id:
+	LocationToAccountId,
+	// Our chain's account ID type (we can't get away without mentioning it explicitly):
+	AccountId,
+	// We don't track any teleports of foreign assets.
+	Nothing,
+	// The account to use for tracking teleports.
+	CheckingAccount,
+>;
+
 parameter_types! {
 		// One XCM operation is 1_000_000_000 weight - almost certainly a conservative estimate.
 		pub UnitWeightCost: u64 = 1_000_000_000;
 		pub const MaxInstructions: u32 = 100;
 }
 
+pub type XcmWeigher = FixedWeightBounds<UnitWeightCost, RuntimeCall, MaxInstructions>;
+
+pub type XcmLocationInverter = LocationInverter<Ancestry>;
+
+/// Converts a local account into the location of that account, as used by the reserves when they
+/// send funds to other chains.
+pub struct AccountIdToMultiLocation;
+impl sp_runtime::traits::Convert<AccountId, MultiLocation> for AccountIdToMultiLocation {
+	fn convert(account: AccountId) -> MultiLocation {
+		X1(AccountId32 {
+			network: NetworkId::Any,
+			id: account.into(),
+		})
+		.into()
+	}
+}
+
 pub struct XcmConfig;
 impl xcm_executor::Config for XcmConfig {
 	type RuntimeCall = RuntimeCall;
 	type XcmSender = XcmRouter;
 	type AssetTransactor = AssetTransactors;
 	type OriginConverter = XcmOriginToTransactDispatchOrigin;
-	type IsReserve = NativeAsset;
+	type IsReserve = (NativeAsset, AssetHubAssets);
 	type IsTeleporter = ();
-	type LocationInverter = LocationInverter<Ancestry>;
+	type LocationInverter = XcmLocationInverter;
 	type Barrier = Barrier;
-	type Weigher = FixedWeightBounds<UnitWeightCost, RuntimeCall, MaxInstructions>;
+	type Weigher = XcmWeigher;
 	type Trader = UsingComponents<IdentityFee<Balance>, RelayLocation, AccountId, Balances, DealWithFees>;
 	type ResponseHandler = (); // Don't handle responses for now.
 	type AssetTrap = PolkadotXcm;
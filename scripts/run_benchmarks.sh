@@ -1,7 +1,7 @@
 #!/bin/bash
 
-export external="frame_system pallet  pallet_balances pallet_collator_selection pallet_contracts  pallet_membership pallet_multisig pallet_preimage  pallet_scheduler pallet_timestamp pallet_uniques pallet_utility"
-export internal="pallet_allocations pallet_grants pallet_reserve"
+export external="frame_system pallet  pallet_balances pallet_contracts  pallet_membership pallet_multisig pallet_preimage  pallet_scheduler pallet_timestamp pallet_uniques pallet_utility"
+export internal="pallet_allocations pallet_grants pallet_mandate pallet_reserve pallet_staking"
 cargo build --profile release \
     --features=runtime-benchmarks \
     --manifest-path=node/Cargo.toml 
@@ -23,7 +23,9 @@ done
 
 mv weights/pallet_allocations.rs pallets/allocations/src/weights.rs
 mv weights/pallet_grants.rs pallets/grants/src/weights.rs 
+mv weights/pallet_mandate.rs pallets/mandate/src/weights.rs
 mv weights/pallet_reserve.rs pallets/reserve/src/weights.rs
+mv weights/pallet_staking.rs pallets/staking/src/weights.rs
 
 
 for PALLET in $external
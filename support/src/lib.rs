@@ -21,3 +21,22 @@
 pub trait WithAccountId<AccountId> {
 	fn account_id() -> AccountId;
 }
+
+/// An account collecting protocol fees, told about every payment so that it can keep track of
+/// them.
+pub trait ProtocolFeeHandler<AccountId, Balance>: WithAccountId<AccountId> {
+	/// `amount` was just transferred to `account_id()` as protocol fees.
+	fn on_protocol_fee(_amount: Balance) {}
+}
+
+/// Tells whether the chain is under an emergency shutdown, in which case pallets should stop
+/// moving funds around until governance resumes operations.
+pub trait IsShutdown {
+	fn is_shutdown() -> bool;
+}
+
+impl IsShutdown for () {
+	fn is_shutdown() -> bool {
+		false
+	}
+}